@@ -0,0 +1,352 @@
+use crate::num::*;
+use crate::vec::*;
+
+use crate::primitives::{Aabb, Sphere, Frustum, Triangle, Bounded};
+use crate::{RayHit, ray_vs_aabb_interval, ray_vs_triangle_hit};
+
+/// number of bins each axis is split into when evaluating the surface area heuristic
+const BVH_BINS: usize = 12;
+
+/// leaves with more items than this are always split if the centroids can be separated
+const BVH_MAX_LEAF_ITEMS: usize = 4;
+
+/// node of a `Bvh`, leaves reference a range of `Bvh::indices` and interior nodes reference a pair of children
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BvhNode<T, V = Vec3<T>> {
+    /// bounds of all items below the node
+    pub aabb: Aabb<T, V>,
+    /// index of the left child for interior nodes with the right child following it, or the first entry in `indices` for leaves
+    pub first: usize,
+    /// number of items in a leaf, or 0 for interior nodes
+    pub count: usize
+}
+
+/// bounding volume hierarchy in 2D or 3D built with binned surface area heuristic over items with an aabb.
+/// queries return the item indices in the slice the hierarchy was built from
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Bvh<T, V = Vec3<T>> {
+    /// nodes with the root first, children are always stored after their parent
+    pub nodes: Vec<BvhNode<T, V>>,
+    /// item indices ordered so each leaf references a contiguous range
+    pub indices: Vec<usize>
+}
+
+impl<T, V> BvhNode<T, V> {
+    /// returns true if the node references items rather than children
+    pub fn is_leaf(&self) -> bool {
+        self.count > 0
+    }
+}
+
+/// returns half the surface area of `aabb` in 3D, or half the perimeter in 2D, used as the cost of visiting a node
+pub(crate) fn half_area<T, V>(aabb: &Aabb<T, V>) -> T where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    if aabb.is_empty() {
+        return T::zero();
+    }
+    let e = aabb.size();
+    if V::len() == 2 {
+        return e[0] + e[1];
+    }
+    let mut area = T::zero();
+    for i in 0..V::len() {
+        for j in i + 1..V::len() {
+            area += e[i] * e[j];
+        }
+    }
+    area
+}
+
+/// returns the distance along the ray with origin `r0` and direction `rv` where it enters `aabb`, 0 if it starts inside
+pub(crate) fn ray_entry<T, V>(aabb: &Aabb<T, V>, r0: V, rv: V, t_max: T) -> Option<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> {
+    ray_vs_aabb_interval(r0, rv, aabb.min, aabb.max)
+        .map(|(tmin, _, _, _)| T::max(tmin, T::zero()))
+        .filter(|t| *t <= t_max)
+}
+
+/// node access for binary aabb trees so `Bvh` and `DynamicTree` share the same traversals
+pub(crate) trait AabbTree<T, V> {
+    /// returns the index of the root node, or `None` if the tree is empty
+    fn root_node(&self) -> Option<usize>;
+    /// returns the bounds of `node`
+    fn node_aabb(&self, node: usize) -> &Aabb<T, V>;
+    /// returns the 2 children of an interior `node`, or `None` if it is a leaf
+    fn node_children(&self, node: usize) -> Option<[usize; 2]>;
+    /// calls `visit` for each item in the leaf `node`, stopping early and returning the item when `visit` returns true
+    fn leaf_items<F: FnMut(usize) -> bool>(&self, node: usize, visit: F) -> Option<usize>;
+}
+
+/// calls `visit` for each item in nodes of `tree` which pass `overlaps`, stopping early and returning the item when `visit` returns true
+pub(crate) fn tree_query<T, V, A: AabbTree<T, V>, O: Fn(&Aabb<T, V>) -> bool, F: FnMut(usize) -> bool>(tree: &A, overlaps: O, mut visit: F) -> Option<usize> {
+    let mut stack: Vec<usize> = tree.root_node().into_iter().collect();
+    while let Some(n) = stack.pop() {
+        if !overlaps(tree.node_aabb(n)) {
+            continue;
+        }
+        match tree.node_children(n) {
+            Some([c0, c1]) => {
+                stack.push(c1);
+                stack.push(c0);
+            }
+            None => {
+                if let Some(i) = tree.leaf_items(n, &mut visit) {
+                    return Some(i);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// returns all items in nodes of `tree` which pass `overlaps`
+pub(crate) fn tree_query_all<T, V, A: AabbTree<T, V>, O: Fn(&Aabb<T, V>) -> bool>(tree: &A, overlaps: O) -> Vec<usize> {
+    let mut items = Vec::new();
+    tree_query(tree, overlaps, |i| {
+        items.push(i);
+        false
+    });
+    items
+}
+
+/// returns `(item, t)` for the closest item in `tree` hit by the ray with origin `r0` and direction `rv` no further than `t_max`, visiting
+/// the nearest child first so the closest hit shrinks the search sooner
+pub(crate) fn tree_ray_closest<T, V, A: AabbTree<T, V>, F: FnMut(usize, T) -> Option<T>>(tree: &A, r0: V, rv: V, t_max: T, mut hit: F) -> Option<(usize, T)>
+where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> {
+    let mut closest: Option<(usize, T)> = None;
+    let mut t_best = t_max;
+    let mut stack = Vec::new();
+    if let Some(root) = tree.root_node() {
+        if let Some(t) = ray_entry(tree.node_aabb(root), r0, rv, t_best) {
+            stack.push((root, t));
+        }
+    }
+    while let Some((n, t_entry)) = stack.pop() {
+        if t_entry > t_best {
+            continue;
+        }
+        match tree.node_children(n) {
+            Some([c0, c1]) => {
+                let l = ray_entry(tree.node_aabb(c0), r0, rv, t_best).map(|t| (c0, t));
+                let r = ray_entry(tree.node_aabb(c1), r0, rv, t_best).map(|t| (c1, t));
+                match (l, r) {
+                    (Some(l), Some(r)) => {
+                        let (near, far) = if l.1 <= r.1 { (l, r) } else { (r, l) };
+                        stack.push(far);
+                        stack.push(near);
+                    }
+                    (Some(c), None) | (None, Some(c)) => stack.push(c),
+                    (None, None) => ()
+                }
+            }
+            None => {
+                tree.leaf_items(n, |i| {
+                    if let Some(t) = hit(i, t_best) {
+                        if t <= t_best {
+                            t_best = t;
+                            closest = Some((i, t));
+                        }
+                    }
+                    false
+                });
+            }
+        }
+    }
+    closest
+}
+
+impl<T, V> AabbTree<T, V> for Bvh<T, V> {
+    fn root_node(&self) -> Option<usize> {
+        if self.nodes.is_empty() { None } else { Some(0) }
+    }
+
+    fn node_aabb(&self, node: usize) -> &Aabb<T, V> {
+        &self.nodes[node].aabb
+    }
+
+    fn node_children(&self, node: usize) -> Option<[usize; 2]> {
+        let node = &self.nodes[node];
+        if node.is_leaf() { None } else { Some([node.first, node.first + 1]) }
+    }
+
+    fn leaf_items<F: FnMut(usize) -> bool>(&self, node: usize, mut visit: F) -> Option<usize> {
+        let node = &self.nodes[node];
+        self.indices[node.first..node.first + node.count].iter().copied().find(|i| visit(*i))
+    }
+}
+
+impl<T, V> Bvh<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> + Cast<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    /// builds a bvh over items with `bounds`, query results are indices into `bounds`
+    pub fn build(bounds: &[Aabb<T, V>]) -> Self {
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            indices: (0..bounds.len()).collect()
+        };
+        if !bounds.is_empty() {
+            let centroids: Vec<V> = bounds.iter().map(|b| b.center()).collect();
+            bvh.nodes.push(BvhNode {
+                aabb: Aabb::empty(),
+                first: 0,
+                count: bounds.len()
+            });
+            bvh.subdivide(0, bounds, &centroids);
+        }
+        bvh
+    }
+
+    /// builds a bvh over the bounds of `items`, query results are indices into `items`
+    pub fn from_items<I: Bounded<T, V>>(items: &[I]) -> Self {
+        let bounds: Vec<Aabb<T, V>> = items.iter().map(|i| i.aabb()).collect();
+        Self::build(&bounds)
+    }
+
+    /// returns the bounds of everything in the bvh, or `Aabb::empty()` if there are no items
+    pub fn aabb(&self) -> Aabb<T, V> {
+        self.nodes.first().map(|n| n.aabb).unwrap_or_else(Aabb::empty)
+    }
+
+    /// splits the items in leaf `node` into children where the surface area heuristic finds it cheaper to do so
+    fn subdivide(&mut self, node: usize, bounds: &[Aabb<T, V>], centroids: &[V]) {
+        let first = self.nodes[node].first;
+        let count = self.nodes[node].count;
+        let items = &self.indices[first..first + count];
+        let aabb = items.iter().fold(Aabb::empty(), |b, i| b.merge(&bounds[*i]));
+        let cb = items.iter().fold(Aabb::empty(), |b, i| b.expand(centroids[*i]));
+        self.nodes[node].aabb = aabb;
+        if count == 1 {
+            return;
+        }
+
+        // bin the centroids along each axis and find the split plane with the lowest cost
+        let bin_of = |c: T, lo: T, scale: T| std::cmp::min(((c - lo) * scale).as_usize(), BVH_BINS - 1);
+        let mut best: Option<(T, usize, usize)> = None;
+        for axis in 0..V::len() {
+            let extent = cb.max[axis] - cb.min[axis];
+            if extent <= T::zero() {
+                continue;
+            }
+            let scale = T::from_usize(BVH_BINS) / extent;
+            let mut bins = [(Aabb::<T, V>::empty(), 0); BVH_BINS];
+            for i in items {
+                let c = centroids[*i];
+                let b = bin_of(c[axis], cb.min[axis], scale);
+                bins[b].0 = bins[b].0.merge(&bounds[*i]);
+                bins[b].1 += 1;
+            }
+
+            // sweep from the right to find the cost of everything after each split, then from the left
+            let mut right_cost = [T::zero(); BVH_BINS];
+            let mut acc = (Aabb::<T, V>::empty(), 0);
+            for b in (1..BVH_BINS).rev() {
+                acc = (acc.0.merge(&bins[b].0), acc.1 + bins[b].1);
+                right_cost[b - 1] = half_area(&acc.0) * T::from_usize(acc.1);
+            }
+            let mut acc = (Aabb::<T, V>::empty(), 0);
+            for (b, bin) in bins.iter().enumerate().take(BVH_BINS - 1) {
+                acc = (acc.0.merge(&bin.0), acc.1 + bin.1);
+                let cost = half_area(&acc.0) * T::from_usize(acc.1) + right_cost[b];
+                if best.is_none_or(|(c, _, _)| cost < c) {
+                    best = Some((cost, axis, b));
+                }
+            }
+        }
+
+        // all centroids coincide so the items cannot be separated
+        let Some((cost, axis, split)) = best else {
+            return;
+        };
+        let leaf_cost = half_area(&aabb) * T::from_usize(count);
+        if cost >= leaf_cost && count <= BVH_MAX_LEAF_ITEMS {
+            return;
+        }
+
+        // partition the items in place either side of the split
+        let lo = cb.min[axis];
+        let scale = T::from_usize(BVH_BINS) / (cb.max[axis] - lo);
+        let mut i = first;
+        let mut j = first + count;
+        while i < j {
+            if bin_of(centroids[self.indices[i]][axis], lo, scale) <= split {
+                i += 1;
+            }
+            else {
+                j -= 1;
+                self.indices.swap(i, j);
+            }
+        }
+        let left_count = i - first;
+        if left_count == 0 || left_count == count {
+            return;
+        }
+
+        let left = self.nodes.len();
+        self.nodes.push(BvhNode { aabb: Aabb::empty(), first, count: left_count });
+        self.nodes.push(BvhNode { aabb: Aabb::empty(), first: i, count: count - left_count });
+        self.nodes[node].first = left;
+        self.nodes[node].count = 0;
+        self.subdivide(left, bounds, centroids);
+        self.subdivide(left + 1, bounds, centroids);
+    }
+
+    /// updates the node bounds after the items have moved or deformed, ie. vertex animation, keeping the same tree structure.
+    /// `bounds` must be the same length and order as the bvh was built with, rebuild if the items have moved far
+    pub fn refit(&mut self, bounds: &[Aabb<T, V>]) {
+        // children are always stored after their parent so walking backwards visits children first
+        for n in (0..self.nodes.len()).rev() {
+            let node = self.nodes[n];
+            self.nodes[n].aabb = if node.is_leaf() {
+                self.indices[node.first..node.first + node.count].iter().fold(Aabb::empty(), |b, i| b.merge(&bounds[*i]))
+            }
+            else {
+                self.nodes[node.first].aabb.merge(&self.nodes[node.first + 1].aabb)
+            };
+        }
+    }
+
+    /// updates the node bounds from the current bounds of `items`, see `refit`
+    pub fn refit_items<I: Bounded<T, V>>(&mut self, items: &[I]) {
+        let bounds: Vec<Aabb<T, V>> = items.iter().map(|i| i.aabb()).collect();
+        self.refit(&bounds);
+    }
+
+    /// returns `(item, t)` for the closest item hit by the ray with origin `r0` and direction `rv` no further than `t_max`.
+    /// `hit` is called with each item whose bounds the ray crosses and the current closest distance, and returns the distance the item is hit at
+    pub fn ray_closest<F: FnMut(usize, T) -> Option<T>>(&self, r0: V, rv: V, t_max: T, hit: F) -> Option<(usize, T)> {
+        tree_ray_closest(self, r0, rv, t_max, hit)
+    }
+
+    /// returns the first item found which is hit by the ray with origin `r0` and direction `rv` no further than `t_max`, ie. for shadow rays.
+    /// `hit` is called with each item whose bounds the ray crosses and returns true if the item is hit
+    pub fn ray_any<F: FnMut(usize) -> bool>(&self, r0: V, rv: V, t_max: T, hit: F) -> Option<usize> {
+        tree_query(self, |aabb| ray_entry(aabb, r0, rv, t_max).is_some(), hit)
+    }
+
+    /// returns the items whose bounds overlap `aabb`
+    pub fn query_aabb(&self, aabb: &Aabb<T, V>) -> Vec<usize> {
+        tree_query_all(self, |b| b.intersects_aabb(aabb))
+    }
+
+    /// returns the items whose bounds overlap `sphere`
+    pub fn query_sphere(&self, sphere: &Sphere<T, V>) -> Vec<usize> {
+        tree_query_all(self, |b| b.intersects_sphere(sphere))
+    }
+}
+
+impl<T> Bvh<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> + Cast<T> {
+    /// returns the items whose bounds are inside or intersect `frustum`, ie. for culling
+    pub fn query_frustum(&self, frustum: &Frustum<T>) -> Vec<usize> {
+        tree_query_all(self, |b| frustum.intersects_aabb(b))
+    }
+
+    /// returns the closest hit of the ray with origin `r0` and direction `rv` against `triangles` no further than `t_max`, with the index of
+    /// the triangle hit. the bvh must have been built from `triangles`
+    pub fn ray_closest_triangle(&self, triangles: &[Triangle<T>], r0: Vec3<T>, rv: Vec3<T>, t_max: T) -> Option<(usize, RayHit<T, Vec3<T>>)> {
+        let mut closest = None;
+        self.ray_closest(r0, rv, t_max, |i, t_best| {
+            let tri = &triangles[i];
+            let hit = ray_vs_triangle_hit(r0, rv, tri.v0, tri.v1, tri.v2, t_best)?;
+            closest = Some(hit);
+            Some(hit.t)
+        }).and_then(|(i, _)| closest.map(|hit| (i, hit)))
+    }
+}
@@ -0,0 +1,290 @@
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Add;
+use std::ops::Neg;
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::num::*;
+use crate::vec::*;
+use crate::mat::*;
+use crate::quat::*;
+
+/// dual quaternion `real + ε dual` representing a rigid transform (rotation and translation)
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(C)]
+pub struct DualQuat<T> {
+    /// rotation part
+    pub real: Quat<T>,
+    /// translation part, `0.5 * t * real`
+    pub dual: Quat<T>
+}
+
+impl<T> DualQuat<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// constructs an identity dual quaternion which has no rotation or translation
+    pub fn identity() -> Self {
+        DualQuat {
+            real: Quat::identity(),
+            dual: Quat::new(T::zero(), T::zero(), T::zero(), T::zero())
+        }
+    }
+
+    /// constructs a dual quaternion from the `real` and `dual` parts
+    pub fn new(real: Quat<T>, dual: Quat<T>) -> Self {
+        DualQuat {
+            real,
+            dual
+        }
+    }
+
+    /// constructs a rigid transform which applies rotation `r` followed by translation `t`
+    pub fn from_rotation_translation(r: Quat<T>, t: Vec3<T>) -> Self {
+        DualQuat {
+            real: r,
+            dual: Quat::new(t.x, t.y, t.z, T::zero()) * r * T::point_five()
+        }
+    }
+
+    /// constructs a rigid transform with rotation `r` only
+    pub fn from_rotation(r: Quat<T>) -> Self {
+        Self::from_rotation_translation(r, Vec3::zero())
+    }
+
+    /// constructs a rigid transform with translation `t` only
+    pub fn from_translation(t: Vec3<T>) -> Self {
+        Self::from_rotation_translation(Quat::identity(), t)
+    }
+
+    /// constructs a rigid transform from matrix `m`, `m` must only contain rotation and translation
+    pub fn from_matrix(m: Mat34<T>) -> Self {
+        Self::from_rotation_translation(Quat::from_matrix(Mat3::from(m)), m.get_column(3))
+    }
+
+    /// returns the rotation part of the transform
+    pub fn get_rotation(self) -> Quat<T> {
+        self.real
+    }
+
+    /// returns the translation part of the transform
+    pub fn get_translation(self) -> Vec3<T> {
+        let t = self.dual * self.real.reverse() * T::two();
+        Vec3::new(t.x, t.y, t.z)
+    }
+
+    /// returns a 3x4 matrix with the same rotation and translation
+    pub fn get_matrix(self) -> Mat34<T> {
+        let mut m = Mat34::from(self.real);
+        m.set_column(3, self.get_translation());
+        m
+    }
+
+    /// returns the quaternion conjugate of both the real and dual parts, for unit dual quaternions this is the inverse
+    pub fn conjugate(self) -> Self {
+        DualQuat {
+            real: self.real.reverse(),
+            dual: self.dual.reverse()
+        }
+    }
+
+    /// returns the inverse transform
+    pub fn inverse(self) -> Self {
+        let real = self.real.inverse();
+        DualQuat {
+            real,
+            dual: -(real * self.dual * real)
+        }
+    }
+
+    /// returns the dual quaternion normalised to unit length, with the dual part made orthogonal to the real part
+    pub fn normalize(self) -> Self {
+        let m = Quat::mag(self.real);
+        let real = self.real / m;
+        let dual = self.dual / m;
+        DualQuat {
+            real,
+            dual: dual - real * Quat::dot(real, dual)
+        }
+    }
+
+    /// returns point `p` transformed by rotation and translation
+    pub fn transform_point(self, p: Vec3<T>) -> Vec3<T> {
+        self.real * p + self.get_translation()
+    }
+
+    /// returns direction `v` transformed by the rotation only
+    pub fn transform_vector(self, v: Vec3<T>) -> Vec3<T> {
+        self.real * v
+    }
+
+    /// returns the screw parameters `(angle, pitch, axis, moment)` of a unit dual quaternion
+    pub fn to_screw(self) -> (T, T, Vec3<T>, Vec3<T>) {
+        let r = self.real;
+        let d = self.dual;
+        let s = T::sqrt(r.x * r.x + r.y * r.y + r.z * r.z);
+        if s < T::small_epsilon() {
+            // pure translation, the axis is the translation direction
+            let t = self.get_translation();
+            let pitch = Vec3::mag(t);
+            let axis = if pitch > T::zero() { t / pitch } else { Vec3::unit_z() };
+            return (T::zero(), pitch, axis, Vec3::zero());
+        }
+        let angle = T::two() * T::atan2(s, r.w);
+        let axis = Vec3::new(r.x, r.y, r.z) / s;
+        let pitch = -T::two() * d.w / s;
+        let moment = (Vec3::new(d.x, d.y, d.z) - axis * (pitch * T::point_five() * r.w)) / s;
+        (angle, pitch, axis, moment)
+    }
+
+    /// constructs a unit dual quaternion from screw parameters `angle`, `pitch`, `axis` and `moment`
+    pub fn from_screw(angle: T, pitch: T, axis: Vec3<T>, moment: Vec3<T>) -> Self {
+        let (s, c) = T::sin_cos(angle * T::point_five());
+        let half_pitch = pitch * T::point_five();
+        let m = moment * s + axis * (half_pitch * c);
+        DualQuat {
+            real: Quat::new(axis.x * s, axis.y * s, axis.z * s, c),
+            dual: Quat::new(m.x, m.y, m.z, -half_pitch * s)
+        }
+    }
+
+    /// returns the unit dual quaternion raised to the power `t`, scaling the screw motion
+    pub fn pow(self, t: T) -> Self {
+        let (angle, pitch, axis, moment) = self.to_screw();
+        Self::from_screw(angle * t, pitch * t, axis, moment)
+    }
+
+    /// returns screw linear interpolation (ScLERP) between unit dual quaternions `e0` and `e1` by `t`,
+    /// which has constant velocity and follows the shortest screw motion
+    pub fn sclerp(e0: Self, e1: Self, t: T) -> Self {
+        let e1 = if Quat::dot(e0.real, e1.real) < T::zero() { -e1 } else { e1 };
+        e0 * (e0.conjugate() * e1).pow(t)
+    }
+
+    /// returns dual quaternion linear blending (DLB) of `dqs` with `weights`, used for skinning.
+    /// each input is flipped onto the hemisphere of the first to take the shortest path
+    pub fn dlb(dqs: &[Self], weights: &[T]) -> Self {
+        assert_eq!(dqs.len(), weights.len());
+        let zero = Quat::new(T::zero(), T::zero(), T::zero(), T::zero());
+        let mut blend = DualQuat::new(zero, zero);
+        if let Some(pivot) = dqs.first() {
+            for (dq, w) in dqs.iter().zip(weights) {
+                let w = if Quat::dot(pivot.real, dq.real) < T::zero() { -*w } else { *w };
+                blend = blend + *dq * w;
+            }
+        }
+        blend.normalize()
+    }
+}
+
+/// composes transforms, `lhs * rhs` applies `rhs` first then `lhs`
+impl<T> Mul<Self> for DualQuat<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        DualQuat {
+            real: self.real * other.real,
+            dual: self.real * other.dual + self.dual * other.real
+        }
+    }
+}
+
+impl<T> MulAssign<Self> for DualQuat<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+/// transforms point `other` by rotation and translation
+impl<T> Mul<Vec3<T>> for DualQuat<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    type Output = Vec3<T>;
+    fn mul(self, other: Vec3<T>) -> Vec3<T> {
+        self.transform_point(other)
+    }
+}
+
+impl<T> Mul<T> for DualQuat<T> where T: Float {
+    type Output = Self;
+    fn mul(self, other: T) -> Self {
+        DualQuat {
+            real: self.real * other,
+            dual: self.dual * other
+        }
+    }
+}
+
+impl<T> Add<Self> for DualQuat<T> where T: Float {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        DualQuat {
+            real: self.real + other.real,
+            dual: self.dual + other.dual
+        }
+    }
+}
+
+impl<T> Neg for DualQuat<T> where T: Float {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        DualQuat {
+            real: -self.real,
+            dual: -self.dual
+        }
+    }
+}
+
+/// default to identity
+impl<T> Default for DualQuat<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// returns screw linear interpolation between `e0` and `e1` by percentage `t`
+impl<T> Slerp<T> for DualQuat<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn slerp(e0: Self, e1: Self, t: T) -> Self {
+        Self::sclerp(e0, e1, t)
+    }
+}
+
+/// returns dual quaternion linear blending between `e0` and `e1` by percentage `t`
+impl<T> Nlerp<T> for DualQuat<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn nlerp(e0: Self, e1: Self, t: T) -> Self {
+        Self::dlb(&[e0, e1], &[T::one() - t, t])
+    }
+}
+
+/// constructs from a tuple of rotation and translation
+impl<T> From<(Quat<T>, Vec3<T>)> for DualQuat<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from(other: (Quat<T>, Vec3<T>)) -> Self {
+        DualQuat::from_rotation_translation(other.0, other.1)
+    }
+}
+
+/// constructs from a rigid 3x4 matrix containing only rotation and translation
+impl<T> From<Mat34<T>> for DualQuat<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from(other: Mat34<T>) -> Self {
+        DualQuat::from_matrix(other)
+    }
+}
+
+/// constructs a 3x4 rotation and translation matrix from a dual quaternion
+impl<T> From<DualQuat<T>> for Mat34<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from(other: DualQuat<T>) -> Self {
+        other.get_matrix()
+    }
+}
+
+/// constructs a 4x4 rotation and translation matrix from a dual quaternion
+impl<T> From<DualQuat<T>> for Mat4<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from(other: DualQuat<T>) -> Self {
+        Mat4::from(other.get_matrix())
+    }
+}
+
+/// displays like [[1.0, 2.0, 3.0, 4.0], [1.0, 2.0, 3.0, 4.0]]
+impl<T> Display for DualQuat<T> where T: Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}]", self.real, self.dual)
+    }
+}
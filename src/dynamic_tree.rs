@@ -0,0 +1,389 @@
+use crate::num::*;
+use crate::vec::*;
+
+use crate::primitives::{Aabb, Sphere, Frustum};
+use crate::bvh::{AabbTree, half_area, ray_entry, tree_query, tree_query_all, tree_ray_closest};
+
+/// fat aabbs are extended by this multiple of the displacement passed to `move_proxy` to predict where the proxy is heading
+const AABB_DISPLACEMENT_MULTIPLIER: usize = 4;
+
+/// node of a `DynamicTree`, leaves hold a proxy and interior nodes hold a pair of children
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DynamicTreeNode<T, V = Vec3<T>> {
+    /// fattened bounds of a leaf proxy, or the bounds of both children for interior nodes
+    pub aabb: Aabb<T, V>,
+    /// index of the parent node or `None` for the root
+    pub parent: Option<usize>,
+    /// indices of the 2 children for interior nodes or `None` for leaves
+    pub children: Option<[usize; 2]>,
+    /// 0 for leaves, otherwise the longest path to a leaf below the node
+    pub height: usize
+}
+
+/// dynamic aabb tree broadphase in 2D or 3D with incremental insert, remove and move of proxies. leaves store aabbs fattened by a margin
+/// so small movements do not need the tree to be updated, and rotations keep the tree balanced. proxies are identified by the index
+/// returned from `insert`, which may be reused after `remove`
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DynamicTree<T, V = Vec3<T>> {
+    nodes: Vec<DynamicTreeNode<T, V>>,
+    root: Option<usize>,
+    free: Vec<usize>,
+    moved: Vec<usize>,
+    proxies: usize,
+    margin: T
+}
+
+impl<T, V> DynamicTreeNode<T, V> {
+    /// returns true if the node is a proxy rather than an interior node
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_none()
+    }
+}
+
+impl<T, V> AabbTree<T, V> for DynamicTree<T, V> {
+    fn root_node(&self) -> Option<usize> {
+        self.root
+    }
+
+    fn node_aabb(&self, node: usize) -> &Aabb<T, V> {
+        &self.nodes[node].aabb
+    }
+
+    fn node_children(&self, node: usize) -> Option<[usize; 2]> {
+        self.nodes[node].children
+    }
+
+    fn leaf_items<F: FnMut(usize) -> bool>(&self, node: usize, mut visit: F) -> Option<usize> {
+        // each leaf holds a single proxy identified by its node index
+        if visit(node) { Some(node) } else { None }
+    }
+}
+
+impl<T, V> DynamicTree<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> + Cast<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    /// constructs an empty tree where proxy aabbs are grown by `margin` on each side
+    pub fn new(margin: T) -> Self {
+        DynamicTree {
+            nodes: Vec::new(),
+            root: None,
+            free: Vec::new(),
+            moved: Vec::new(),
+            proxies: 0,
+            margin
+        }
+    }
+
+    /// returns the number of proxies in the tree
+    pub fn len(&self) -> usize {
+        self.proxies
+    }
+
+    /// returns true if there are no proxies in the tree
+    pub fn is_empty(&self) -> bool {
+        self.proxies == 0
+    }
+
+    /// returns the nodes of the tree, including unused nodes waiting to be reused
+    pub fn nodes(&self) -> &[DynamicTreeNode<T, V>] {
+        &self.nodes
+    }
+
+    /// returns the index of the root node or `None` if the tree is empty
+    pub fn root(&self) -> Option<usize> {
+        self.root
+    }
+
+    /// returns the height of the tree, 0 for a single proxy or an empty tree
+    pub fn height(&self) -> usize {
+        self.root.map(|r| self.nodes[r].height).unwrap_or(0)
+    }
+
+    /// returns the largest height difference between the children of any interior node
+    pub fn max_balance(&self) -> usize {
+        let mut balance = 0;
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(n) = stack.pop() {
+            if let Some([c0, c1]) = self.nodes[n].children {
+                balance = std::cmp::max(balance, self.nodes[c0].height.abs_diff(self.nodes[c1].height));
+                stack.push(c0);
+                stack.push(c1);
+            }
+        }
+        balance
+    }
+
+    /// returns the bounds of everything in the tree, or `Aabb::empty()` if there are no proxies
+    pub fn aabb(&self) -> Aabb<T, V> {
+        self.root.map(|r| self.nodes[r].aabb).unwrap_or_else(Aabb::empty)
+    }
+
+    /// returns the fattened aabb stored for `proxy`
+    pub fn fat_aabb(&self, proxy: usize) -> Aabb<T, V> {
+        self.nodes[proxy].aabb
+    }
+
+    /// inserts a proxy with bounds `aabb` and returns its id, the proxy is reported by the next `update_pairs`
+    pub fn insert(&mut self, aabb: &Aabb<T, V>) -> usize {
+        let proxy = self.allocate(aabb.grow(self.margin));
+        self.insert_leaf(proxy);
+        self.moved.push(proxy);
+        self.proxies += 1;
+        proxy
+    }
+
+    /// removes `proxy` from the tree, the id may be returned by a later `insert`
+    pub fn remove(&mut self, proxy: usize) {
+        debug_assert!(self.nodes[proxy].is_leaf());
+        self.remove_leaf(proxy);
+        self.moved.retain(|m| *m != proxy);
+        self.free.push(proxy);
+        self.proxies -= 1;
+    }
+
+    /// updates `proxy` to new bounds `aabb` having moved by `displacement`. the tree is only updated if `aabb` has left the fattened
+    /// aabb or the fattened aabb has become much larger than needed, returns true if the proxy was reinserted
+    pub fn move_proxy(&mut self, proxy: usize, aabb: &Aabb<T, V>, displacement: V) -> bool {
+        debug_assert!(self.nodes[proxy].is_leaf());
+
+        // extend the fat aabb in the direction of motion to predict where the proxy is heading
+        let mut fat = aabb.grow(self.margin);
+        let d = displacement * T::from_usize(AABB_DISPLACEMENT_MULTIPLIER);
+        for i in 0..V::len() {
+            if d[i] < T::zero() {
+                fat.min[i] += d[i];
+            }
+            else {
+                fat.max[i] += d[i];
+            }
+        }
+
+        let current = self.nodes[proxy].aabb;
+        if current.contains_aabb(aabb) {
+            // keep the current fat aabb unless it is so large it would produce too many pairs
+            let huge = fat.grow(self.margin * T::from_usize(AABB_DISPLACEMENT_MULTIPLIER));
+            if huge.contains_aabb(&current) {
+                return false;
+            }
+        }
+
+        self.remove_leaf(proxy);
+        self.nodes[proxy].aabb = fat;
+        self.insert_leaf(proxy);
+        self.moved.push(proxy);
+        true
+    }
+
+    /// returns a node from the free list or a new node with bounds `aabb`
+    fn allocate(&mut self, aabb: Aabb<T, V>) -> usize {
+        let node = DynamicTreeNode {
+            aabb,
+            parent: None,
+            children: None,
+            height: 0
+        };
+        if let Some(n) = self.free.pop() {
+            self.nodes[n] = node;
+            n
+        }
+        else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    /// returns the cost of descending into `child` when inserting `aabb` beneath it
+    fn descend_cost(&self, child: usize, aabb: &Aabb<T, V>, inheritance: T) -> T {
+        let node = &self.nodes[child];
+        let combined = half_area(&node.aabb.merge(aabb));
+        if node.is_leaf() {
+            combined + inheritance
+        }
+        else {
+            combined - half_area(&node.aabb) + inheritance
+        }
+    }
+
+    /// links `leaf` into the tree next to the sibling which increases the total surface area the least
+    fn insert_leaf(&mut self, leaf: usize) {
+        let Some(root) = self.root else {
+            self.root = Some(leaf);
+            self.nodes[leaf].parent = None;
+            return;
+        };
+
+        // descend the tree while pushing the leaf further down is cheaper than creating a new parent here
+        let aabb = self.nodes[leaf].aabb;
+        let mut sibling = root;
+        while let Some([c0, c1]) = self.nodes[sibling].children {
+            let area = half_area(&self.nodes[sibling].aabb);
+            let combined = half_area(&self.nodes[sibling].aabb.merge(&aabb));
+            let cost = combined * T::two();
+            let inheritance = (combined - area) * T::two();
+            let cost0 = self.descend_cost(c0, &aabb, inheritance);
+            let cost1 = self.descend_cost(c1, &aabb, inheritance);
+            if cost < cost0 && cost < cost1 {
+                break;
+            }
+            sibling = if cost0 < cost1 { c0 } else { c1 };
+        }
+
+        // replace the sibling with a new parent of the sibling and the leaf
+        let old_parent = self.nodes[sibling].parent;
+        let parent = self.allocate(aabb.merge(&self.nodes[sibling].aabb));
+        self.nodes[parent].parent = old_parent;
+        self.nodes[parent].children = Some([sibling, leaf]);
+        self.nodes[parent].height = self.nodes[sibling].height + 1;
+        self.replace_child(old_parent, sibling, parent);
+        self.nodes[sibling].parent = Some(parent);
+        self.nodes[leaf].parent = Some(parent);
+
+        self.refit_ancestors(self.nodes[leaf].parent);
+    }
+
+    /// unlinks `leaf` from the tree, its parent is freed and the sibling takes the parent's place
+    fn remove_leaf(&mut self, leaf: usize) {
+        let Some(parent) = self.nodes[leaf].parent else {
+            self.root = None;
+            return;
+        };
+        let [c0, c1] = self.nodes[parent].children.unwrap();
+        let sibling = if c0 == leaf { c1 } else { c0 };
+        let grand_parent = self.nodes[parent].parent;
+        self.replace_child(grand_parent, parent, sibling);
+        self.nodes[sibling].parent = grand_parent;
+        self.nodes[leaf].parent = None;
+        self.free.push(parent);
+        self.refit_ancestors(grand_parent);
+    }
+
+    /// points the child slot of `parent` which referenced `old` at `new`, or makes `new` the root if there is no parent
+    fn replace_child(&mut self, parent: Option<usize>, old: usize, new: usize) {
+        match parent {
+            Some(p) => {
+                let children = self.nodes[p].children.as_mut().unwrap();
+                let slot = if children[0] == old { 0 } else { 1 };
+                children[slot] = new;
+            }
+            None => self.root = Some(new)
+        }
+    }
+
+    /// recomputes the bounds and height of `node` from its children
+    fn refit_node(&mut self, node: usize) {
+        let [c0, c1] = self.nodes[node].children.unwrap();
+        self.nodes[node].aabb = self.nodes[c0].aabb.merge(&self.nodes[c1].aabb);
+        self.nodes[node].height = std::cmp::max(self.nodes[c0].height, self.nodes[c1].height) + 1;
+    }
+
+    /// walks from `node` to the root rebalancing and refitting each ancestor
+    fn refit_ancestors(&mut self, mut node: Option<usize>) {
+        while let Some(n) = node {
+            let n = self.balance(n);
+            self.refit_node(n);
+            node = self.nodes[n].parent;
+        }
+    }
+
+    /// rotates the taller child of `a` above it if the children heights differ by more than 1, returns the node now in the place of `a`
+    fn balance(&mut self, a: usize) -> usize {
+        let Some(children) = self.nodes[a].children else {
+            return a;
+        };
+        let h0 = self.nodes[children[0]].height;
+        let h1 = self.nodes[children[1]].height;
+        if h0.abs_diff(h1) <= 1 {
+            return a;
+        }
+
+        // `x` is promoted to take the place of `a`, `a` becomes a child of `x` along with the taller grandchild
+        let side = if h1 > h0 { 1 } else { 0 };
+        let x = children[side];
+        let [g0, g1] = self.nodes[x].children.unwrap();
+        let (tall, short) = if self.nodes[g0].height > self.nodes[g1].height { (g0, g1) } else { (g1, g0) };
+
+        let parent = self.nodes[a].parent;
+        self.replace_child(parent, a, x);
+        self.nodes[x].parent = parent;
+        self.nodes[x].children = Some([a, tall]);
+        self.nodes[a].parent = Some(x);
+        self.nodes[a].children.as_mut().unwrap()[side] = short;
+        self.nodes[short].parent = Some(a);
+
+        self.refit_node(a);
+        self.refit_node(x);
+        x
+    }
+
+    /// returns the proxies whose fat aabbs overlap `aabb`
+    pub fn query_aabb(&self, aabb: &Aabb<T, V>) -> Vec<usize> {
+        tree_query_all(self, |b| b.intersects_aabb(aabb))
+    }
+
+    /// returns the proxies whose fat aabbs overlap `sphere`
+    pub fn query_sphere(&self, sphere: &Sphere<T, V>) -> Vec<usize> {
+        tree_query_all(self, |b| b.intersects_sphere(sphere))
+    }
+
+    /// returns `(proxy, t)` for the closest proxy hit by the ray with origin `r0` and direction `rv` no further than `t_max`.
+    /// `hit` is called with each proxy whose fat aabb the ray crosses and the current closest distance, and returns the distance the proxy is hit at
+    pub fn ray_closest<F: FnMut(usize, T) -> Option<T>>(&self, r0: V, rv: V, t_max: T, hit: F) -> Option<(usize, T)> {
+        tree_ray_closest(self, r0, rv, t_max, hit)
+    }
+
+    /// returns the first proxy found which is hit by the ray with origin `r0` and direction `rv` no further than `t_max`.
+    /// `hit` is called with each proxy whose fat aabb the ray crosses and returns true if the proxy is hit
+    pub fn ray_any<F: FnMut(usize) -> bool>(&self, r0: V, rv: V, t_max: T, hit: F) -> Option<usize> {
+        tree_query(self, |aabb| ray_entry(aabb, r0, rv, t_max).is_some(), hit)
+    }
+
+    /// returns the pairs of proxies with overlapping fat aabbs where at least one has been inserted or reinserted since the last call,
+    /// each pair is ordered `(lower, higher)` and reported once
+    pub fn update_pairs(&mut self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for proxy in std::mem::take(&mut self.moved) {
+            let aabb = self.nodes[proxy].aabb;
+            tree_query(self, |b| b.intersects_aabb(&aabb), |other| {
+                if other != proxy {
+                    pairs.push((std::cmp::min(proxy, other), std::cmp::max(proxy, other)));
+                }
+                false
+            });
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// returns every pair of proxies with overlapping fat aabbs, each pair is ordered `(lower, higher)` and reported once
+    pub fn all_pairs(&self) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(n) = stack.pop() {
+            match self.nodes[n].children {
+                Some([c0, c1]) => {
+                    stack.push(c0);
+                    stack.push(c1);
+                }
+                None => {
+                    let aabb = self.nodes[n].aabb;
+                    tree_query(self, |b| b.intersects_aabb(&aabb), |other| {
+                        if other > n {
+                            pairs.push((n, other));
+                        }
+                        false
+                    });
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+}
+
+impl<T> DynamicTree<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> + Cast<T> {
+    /// returns the proxies whose fat aabbs are inside or intersect `frustum`, ie. for culling
+    pub fn query_frustum(&self, frustum: &Frustum<T>) -> Vec<usize> {
+        tree_query_all(self, |b| frustum.intersects_aabb(b))
+    }
+}
@@ -0,0 +1,151 @@
+use crate::num::*;
+use crate::vec::*;
+use crate::mat::*;
+use crate::quat::*;
+
+/// rotation order for euler angles. the axes are listed in the order the rotations are applied about the fixed world axes (extrinsic),
+/// so `XYZ` rotates about x first, then y, then z and produces the matrix `rz * ry * rx`. this is the same as intrinsic rotations in
+/// the reverse order (z, then local y, then local x). the first 6 orders are tait-bryan angles and the last 6 are proper euler angles
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EulerOrder {
+    /// rotate about x, then y, then z
+    XYZ,
+    /// rotate about x, then z, then y
+    XZY,
+    /// rotate about y, then x, then z
+    YXZ,
+    /// rotate about y, then z, then x
+    YZX,
+    /// rotate about z, then x, then y
+    ZXY,
+    /// rotate about z, then y, then x
+    ZYX,
+    /// rotate about x, then y, then x
+    XYX,
+    /// rotate about x, then z, then x
+    XZX,
+    /// rotate about y, then x, then y
+    YXY,
+    /// rotate about y, then z, then y
+    YZY,
+    /// rotate about z, then x, then z
+    ZXZ,
+    /// rotate about z, then y, then z
+    ZYZ
+}
+
+impl EulerOrder {
+    /// returns the axis indices `(first, second, other)` where `other` is the axis not used by the first and second rotations
+    pub fn axes(self) -> (usize, usize, usize) {
+        match self {
+            EulerOrder::XYZ | EulerOrder::XYX => (0, 1, 2),
+            EulerOrder::XZY | EulerOrder::XZX => (0, 2, 1),
+            EulerOrder::YXZ | EulerOrder::YXY => (1, 0, 2),
+            EulerOrder::YZX | EulerOrder::YZY => (1, 2, 0),
+            EulerOrder::ZXY | EulerOrder::ZXZ => (2, 0, 1),
+            EulerOrder::ZYX | EulerOrder::ZYZ => (2, 1, 0)
+        }
+    }
+
+    /// returns true if the order is a proper euler order where the first and last axes are the same
+    pub fn is_proper(self) -> bool {
+        matches!(self,
+            EulerOrder::XYX | EulerOrder::XZX | EulerOrder::YXY | EulerOrder::YZY | EulerOrder::ZXZ | EulerOrder::ZYZ
+        )
+    }
+
+    /// returns the axis index of the last rotation
+    pub fn last_axis(self) -> usize {
+        let (i, _, k) = self.axes();
+        if self.is_proper() { i } else { k }
+    }
+}
+
+/// trait for rotations which can be converted to and from euler angles
+pub trait EulerAngles<T> {
+    /// construct a rotation from angles `a`, `b` and `c` in radians which rotate about the axes of `order` in sequence
+    fn from_euler(order: EulerOrder, a: T, b: T, c: T) -> Self;
+    /// returns the angles in radians as tuple `(a, b, c)` about the axes of `order` in sequence. for tait-bryan orders `b` is in the range -pi/2 to pi/2
+    /// and for proper orders `b` is in the range 0 to pi, `a` and `c` are in the range -pi to pi. in gimbal lock `c` is 0 and `a` holds the combined rotation
+    fn to_euler(&self, order: EulerOrder) -> (T, T, T);
+}
+
+/// returns the unit vector for axis index `i`
+fn unit_axis<T: Number>(i: usize) -> Vec3<T> {
+    let mut v = Vec3::zero();
+    v[i] = T::one();
+    v
+}
+
+impl<T> EulerAngles<T> for Quat<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from_euler(order: EulerOrder, a: T, b: T, c: T) -> Self {
+        let (i, j, _) = order.axes();
+        let qa = Quat::from_axis_angle(unit_axis(i), a);
+        let qb = Quat::from_axis_angle(unit_axis(j), b);
+        let qc = Quat::from_axis_angle(unit_axis(order.last_axis()), c);
+        qc * qb * qa
+    }
+
+    fn to_euler(&self, order: EulerOrder) -> (T, T, T) {
+        self.get_matrix().to_euler(order)
+    }
+}
+
+impl<T> EulerAngles<T> for Mat3<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from_euler(order: EulerOrder, a: T, b: T, c: T) -> Self {
+        Quat::from_euler(order, a, b, c).get_matrix()
+    }
+
+    fn to_euler(&self, order: EulerOrder) -> (T, T, T) {
+        let (i, j, k) = order.axes();
+        let r = |row: usize, col: usize| self.at(row as u32, col as u32);
+
+        // sign of the permutation (i, j, k), cyclic orders are positive
+        let s = if (i + 1) % 3 == j { T::one() } else { T::minus_one() };
+
+        // below this the middle rotation is treated as gimbal locked, approximately 1e-6
+        let eps = T::powi(T::two(), -20);
+
+        if order.is_proper() {
+            // r = ri(c) * rj(b) * ri(a)
+            let sb = T::sqrt(r(i, j) * r(i, j) + r(i, k) * r(i, k));
+            let b = T::atan2(sb, r(i, i));
+            if sb > eps {
+                // solve c from the residual ri(c) * rj(b) so a and c stay consistent close to gimbal lock
+                let a = T::atan2(r(i, j), s * r(i, k));
+                let m = *self * Mat3::from_rotation(unit_axis(i), -a);
+                (a, b, T::atan2(s * m.at(k as u32, j as u32), m.at(j as u32, j as u32)))
+            }
+            else {
+                // b is 0 or pi so a and c rotate about the same axis
+                (T::atan2(-s * r(j, k), r(j, j)), b, T::zero())
+            }
+        }
+        else {
+            // r = rk(c) * rj(b) * ri(a)
+            let cb = T::sqrt(r(i, i) * r(i, i) + r(j, i) * r(j, i));
+            let b = T::atan2(-s * r(k, i), cb);
+            if cb > eps {
+                // solve c from the residual rk(c) * rj(b) so a and c stay consistent close to gimbal lock
+                let a = T::atan2(s * r(k, j), r(k, k));
+                let m = *self * Mat3::from_rotation(unit_axis(i), -a);
+                (a, b, T::atan2(-s * m.at(i as u32, j as u32), m.at(j as u32, j as u32)))
+            }
+            else {
+                // b is +/- pi/2 so a and c rotate about the same axis
+                (T::atan2(-s * r(j, k), r(j, j)), b, T::zero())
+            }
+        }
+    }
+}
+
+impl<T> EulerAngles<T> for Mat4<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from_euler(order: EulerOrder, a: T, b: T, c: T) -> Self {
+        Mat4::from(Mat3::from_euler(order, a, b, c))
+    }
+
+    fn to_euler(&self, order: EulerOrder) -> (T, T, T) {
+        Mat3::from(*self).to_euler(order)
+    }
+}
@@ -0,0 +1,730 @@
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Sub;
+use std::ops::SubAssign;
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Div;
+use std::ops::DivAssign;
+use std::ops::Rem;
+use std::ops::RemAssign;
+use std::ops::Neg;
+
+use std::fmt::Display;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use crate::num::*;
+
+//
+// Q32.32 kernels
+//
+
+// all transcendental functions are evaluated with integer arithmetic in Q32.32 stored in an i64,
+// which gives bit identical results on every platform. `Fixed32` widens into Q32.32 and rounds back
+
+mod q32 {
+    pub const FRAC_BITS: u32 = 32;
+    pub const ONE: i64 = 1 << FRAC_BITS;
+    pub const PI: i64 = 13493037705;
+    pub const HALF_PI: i64 = 6746518852;
+    pub const TWO_PI: i64 = 26986075409;
+    pub const INV_PI: i64 = 1367130551;
+    pub const LN_2: i64 = 2977044472;
+    pub const LOG2_E: i64 = 6196328019;
+    pub const LOG10_2: i64 = 1292913986;
+    pub const PHI: i64 = 6949403065;
+    pub const INV_PHI: i64 = 2654435769;
+    pub const DEG_TO_RAD: i64 = 74961321;
+    pub const RAD_TO_DEG: i64 = 246083499208;
+
+    /// saturates a wide intermediate into the i64 range
+    pub fn saturate(v: i128) -> i64 {
+        v.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    pub fn mul(a: i64, b: i64) -> i64 {
+        saturate((a as i128 * b as i128) >> FRAC_BITS)
+    }
+
+    /// divide saturating to the range on divide by zero
+    pub fn div(a: i64, b: i64) -> i64 {
+        if b == 0 {
+            if a < 0 { i64::MIN } else { i64::MAX }
+        }
+        else {
+            saturate(((a as i128) << FRAC_BITS) / b as i128)
+        }
+    }
+
+    /// bitwise integer square root of `v`
+    pub fn isqrt(v: u128) -> u128 {
+        let mut rem = v;
+        let mut root = 0;
+        let mut bit = 1 << 126;
+        while bit > rem {
+            bit >>= 2;
+        }
+        while bit != 0 {
+            if rem >= root + bit {
+                rem -= root + bit;
+                root = (root >> 1) + bit;
+            }
+            else {
+                root >>= 1;
+            }
+            bit >>= 2;
+        }
+        root
+    }
+
+    pub fn sqrt(v: i64) -> i64 {
+        if v <= 0 {
+            0
+        }
+        else {
+            isqrt((v as u128) << FRAC_BITS) as i64
+        }
+    }
+
+    pub fn sin(v: i64) -> i64 {
+        // reduce to -pi..pi then to -pi/2..pi/2 using symmetry
+        let mut r = v.rem_euclid(TWO_PI);
+        if r > PI {
+            r -= TWO_PI;
+        }
+        if r > HALF_PI {
+            r = PI - r;
+        }
+        else if r < -HALF_PI {
+            r = -PI - r;
+        }
+        // taylor series to x^13 in horner form
+        let r2 = mul(r, r);
+        let mut t = ONE;
+        for k in [156, 110, 72, 42, 20, 6] {
+            t = ONE - mul(r2, t) / k;
+        }
+        mul(r, t)
+    }
+
+    pub fn cos(v: i64) -> i64 {
+        sin(v.saturating_add(HALF_PI))
+    }
+
+    pub fn atan(v: i64) -> i64 {
+        // atan(x) = pi/2 - atan(1/x) to bring the range into -1..1
+        if !(-ONE..=ONE).contains(&v) {
+            let a = atan(div(ONE, v));
+            return if v > 0 { HALF_PI - a } else { -HALF_PI - a };
+        }
+        // atan(x) = 2 * atan(x / (1 + sqrt(1 + x^2))) twice to bring the range into -tan(pi/16)..tan(pi/16)
+        let mut x = v;
+        for _ in 0..2 {
+            x = div(x, ONE + sqrt(ONE + mul(x, x)));
+        }
+        // taylor series to x^17 in horner form
+        let x2 = mul(x, x);
+        let mut t = ONE / 17;
+        for k in [15, 13, 11, 9, 7, 5, 3] {
+            t = ONE / k - mul(x2, t);
+        }
+        mul(x, ONE - mul(x2, t)) * 4
+    }
+
+    pub fn atan2(y: i64, x: i64) -> i64 {
+        if x == 0 && y == 0 {
+            0
+        }
+        else if x.unsigned_abs() >= y.unsigned_abs() {
+            let a = atan(div(y, x));
+            if x > 0 { a } else if y >= 0 { a + PI } else { a - PI }
+        }
+        else {
+            let a = atan(div(x, y));
+            if y > 0 { HALF_PI - a } else { -HALF_PI - a }
+        }
+    }
+
+    pub fn asin(v: i64) -> i64 {
+        let v = v.clamp(-ONE, ONE);
+        atan2(v, sqrt(ONE - mul(v, v)))
+    }
+
+    pub fn acos(v: i64) -> i64 {
+        let v = v.clamp(-ONE, ONE);
+        atan2(sqrt(ONE - mul(v, v)), v)
+    }
+
+    pub fn exp2(v: i64) -> i64 {
+        let n = v >> FRAC_BITS;
+        if n >= 31 {
+            return i64::MAX;
+        }
+        if n < -(FRAC_BITS as i64) {
+            return 0;
+        }
+        // 2^f = e^(f * ln2) with a taylor series for the fractional part
+        let g = mul(v & (ONE - 1), LN_2);
+        let mut t = ONE;
+        for k in (1..=12).rev() {
+            t = ONE + mul(g, t) / k;
+        }
+        if n >= 0 { saturate((t as i128) << n) } else { t >> -n }
+    }
+
+    pub fn exp(v: i64) -> i64 {
+        exp2(mul(v, LOG2_E))
+    }
+
+    pub fn log2(v: i64) -> i64 {
+        if v <= 0 {
+            return i64::MIN;
+        }
+        // integer part from the most significant bit, normalise into 1..2
+        let msb = 63 - v.leading_zeros() as i64;
+        let n = msb - FRAC_BITS as i64;
+        let mut y = if n >= 0 { v >> n } else { v << -n };
+        // fractional part bit by bit by repeated squaring
+        let mut frac = 0;
+        for i in 1..=FRAC_BITS {
+            y = mul(y, y);
+            if y >= 2 * ONE {
+                y >>= 1;
+                frac |= 1 << (FRAC_BITS - i);
+            }
+        }
+        (n << FRAC_BITS) + frac
+    }
+}
+
+/// macro to stamp out a signed fixed point type with `$frac_bits` fractional bits stored in `$raw`
+macro_rules! fixed_impl {
+    ($Fixed:ident, $raw:ident, $wide:ident, $frac_bits:expr) => {
+        #[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+        #[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(transparent)]
+        pub struct $Fixed($raw);
+
+        impl $Fixed {
+            /// number of fractional bits
+            pub const FRAC_BITS: u32 = $frac_bits;
+            /// `0.0`
+            pub const ZERO: $Fixed = $Fixed(0);
+            /// `1.0`
+            pub const ONE: $Fixed = $Fixed(1 << $frac_bits);
+            /// largest representable value
+            pub const MAX: $Fixed = $Fixed($raw::MAX);
+            /// smallest representable value
+            pub const MIN: $Fixed = $Fixed($raw::MIN);
+            /// smallest positive increment
+            pub const EPSILON: $Fixed = $Fixed(1);
+
+            /// creates a fixed point number from its raw bit representation
+            pub const fn from_raw(raw: $raw) -> Self {
+                $Fixed(raw)
+            }
+
+            /// returns the raw bit representation
+            pub const fn to_raw(self) -> $raw {
+                self.0
+            }
+
+            /// creates a fixed point number from integer `v`, saturating if out of range
+            pub const fn from_int(v: $raw) -> Self {
+                $Fixed(v.saturating_mul(1 << $frac_bits))
+            }
+
+            /// returns `v` rounded to the nearest fixed point value, saturating if out of range
+            pub fn from_f32(v: f32) -> Self {
+                Self::from_f64(v as f64)
+            }
+
+            /// returns `v` rounded to the nearest fixed point value, saturating if out of range
+            pub fn from_f64(v: f64) -> Self {
+                $Fixed((v * (1u64 << $frac_bits) as f64).round() as $raw)
+            }
+
+            /// returns the value converted to `f32`
+            pub fn to_f32(self) -> f32 {
+                self.to_f64() as f32
+            }
+
+            /// returns the value converted to `f64`
+            pub fn to_f64(self) -> f64 {
+                self.0 as f64 / (1u64 << $frac_bits) as f64
+            }
+
+            /// widen into Q32.32 for the shared kernels
+            fn to_q32(self) -> i64 {
+                (self.0 as i64) << (q32::FRAC_BITS - $frac_bits)
+            }
+
+            /// round and saturate from Q32.32
+            fn from_q32(v: i64) -> Self {
+                let shift = q32::FRAC_BITS - $frac_bits;
+                let v = if shift > 0 {
+                    (v as i128 + (1 << shift >> 1)) >> shift
+                }
+                else {
+                    v as i128
+                };
+                $Fixed(v.clamp($raw::MIN as i128, $raw::MAX as i128) as $raw)
+            }
+
+            /// apply a Q32.32 kernel to `self`
+            fn q32(self, f: fn(i64) -> i64) -> Self {
+                Self::from_q32(f(self.to_q32()))
+            }
+        }
+
+        impl Display for $Fixed {
+            fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+                Display::fmt(&self.to_f64(), f)
+            }
+        }
+
+        impl Debug for $Fixed {
+            fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+                Debug::fmt(&self.to_f64(), f)
+            }
+        }
+
+        impl Add<Self> for $Fixed {
+            type Output = Self;
+            fn add(self, other: Self) -> Self {
+                $Fixed(self.0.saturating_add(other.0))
+            }
+        }
+
+        impl AddAssign<Self> for $Fixed {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl Sub<Self> for $Fixed {
+            type Output = Self;
+            fn sub(self, other: Self) -> Self {
+                $Fixed(self.0.saturating_sub(other.0))
+            }
+        }
+
+        impl SubAssign<Self> for $Fixed {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        /// multiplication saturates on overflow
+        impl Mul<Self> for $Fixed {
+            type Output = Self;
+            fn mul(self, other: Self) -> Self {
+                let v = (self.0 as $wide * other.0 as $wide) >> $frac_bits;
+                $Fixed(v.clamp($raw::MIN as $wide, $raw::MAX as $wide) as $raw)
+            }
+        }
+
+        impl MulAssign<Self> for $Fixed {
+            fn mul_assign(&mut self, other: Self) {
+                *self = *self * other;
+            }
+        }
+
+        /// division saturates on overflow and divide by zero saturates to `MAX` or `MIN` by the sign of the numerator
+        impl Div<Self> for $Fixed {
+            type Output = Self;
+            fn div(self, other: Self) -> Self {
+                if other.0 == 0 {
+                    return if self.0 < 0 { Self::MIN } else { Self::MAX };
+                }
+                let v = ((self.0 as $wide) << $frac_bits) / other.0 as $wide;
+                $Fixed(v.clamp($raw::MIN as $wide, $raw::MAX as $wide) as $raw)
+            }
+        }
+
+        impl DivAssign<Self> for $Fixed {
+            fn div_assign(&mut self, other: Self) {
+                *self = *self / other;
+            }
+        }
+
+        /// remainder with the sign of the numerator, `x % 0` is `0`
+        impl Rem<Self> for $Fixed {
+            type Output = Self;
+            fn rem(self, other: Self) -> Self {
+                if other.0 == 0 {
+                    return Self::ZERO;
+                }
+                $Fixed(self.0.wrapping_rem(other.0))
+            }
+        }
+
+        impl RemAssign<Self> for $Fixed {
+            fn rem_assign(&mut self, other: Self) {
+                *self = *self % other;
+            }
+        }
+
+        impl Neg for $Fixed {
+            type Output = Self;
+            fn neg(self) -> Self::Output {
+                $Fixed(self.0.saturating_neg())
+            }
+        }
+
+        impl Base<$Fixed> for $Fixed {
+            fn zero() -> Self {
+                Self::ZERO
+            }
+
+            fn one() -> Self {
+                Self::ONE
+            }
+
+            fn two() -> Self {
+                Self::from_int(2)
+            }
+
+            fn three() -> Self {
+                Self::from_int(3)
+            }
+
+            fn four() -> Self {
+                Self::from_int(4)
+            }
+
+            fn min_value() -> Self {
+                Self::MIN
+            }
+
+            fn max_value() -> Self {
+                Self::MAX
+            }
+        }
+
+        impl Number for $Fixed {}
+
+        impl NumberOps<$Fixed> for $Fixed {
+            fn min(a: Self, b: Self) -> Self {
+                Ord::min(a, b)
+            }
+
+            fn max(a: Self, b: Self) -> Self {
+                Ord::max(a, b)
+            }
+
+            fn clamp(x: Self, min: Self, max: Self) -> Self {
+                NumberOps::max(NumberOps::min(x, max), min)
+            }
+
+            fn step(a: Self, b: Self) -> Self {
+                if a >= b {
+                    Self::one()
+                }
+                else {
+                    Self::zero()
+                }
+            }
+        }
+
+        impl SignedNumber for $Fixed {
+            fn minus_one() -> Self {
+                -Self::ONE
+            }
+        }
+
+        impl SignedNumberOps<$Fixed> for $Fixed {
+            fn signum(a: Self) -> Self {
+                Self::from_int(a.0.signum())
+            }
+
+            fn abs(a: Self) -> Self {
+                $Fixed(a.0.saturating_abs())
+            }
+        }
+
+        impl Float for $Fixed {
+            fn small_epsilon() -> Self {
+                Self::EPSILON
+            }
+
+            fn epsilon() -> Self {
+                Self::EPSILON
+            }
+        }
+
+        impl Lerp<$Fixed> for $Fixed {
+            fn lerp(e0: Self, e1: Self, t: Self) -> Self {
+                e0 + t * (e1 - e0)
+            }
+        }
+
+        impl FloatOps<$Fixed> for $Fixed {
+            fn point_five() -> Self {
+                $Fixed(1 << ($frac_bits - 1))
+            }
+
+            fn pi() -> Self {
+                Self::from_q32(q32::PI)
+            }
+
+            fn two_pi() -> Self {
+                Self::from_q32(q32::TWO_PI)
+            }
+
+            fn inv_pi() -> Self {
+                Self::from_q32(q32::INV_PI)
+            }
+
+            fn phi() -> Self {
+                Self::from_q32(q32::PHI)
+            }
+
+            fn inv_phi() -> Self {
+                Self::from_q32(q32::INV_PHI)
+            }
+
+            fn tau() -> Self {
+                Self::from_q32(q32::TWO_PI)
+            }
+
+            fn sqrt(a: Self) -> Self {
+                a.q32(q32::sqrt)
+            }
+
+            fn rsqrt(a: Self) -> Self {
+                Self::one() / Self::sqrt(a)
+            }
+
+            fn recip(a: Self) -> Self {
+                Self::one() / a
+            }
+
+            fn powi(a: Self, exp: i32) -> Self {
+                // exponentiation by squaring
+                let mut r = Self::one();
+                let mut b = a;
+                let mut n = exp.unsigned_abs();
+                while n > 0 {
+                    if n & 1 == 1 {
+                        r *= b;
+                    }
+                    n >>= 1;
+                    if n > 0 {
+                        b *= b;
+                    }
+                }
+                if exp < 0 { Self::recip(r) } else { r }
+            }
+
+            fn powf(a: Self, exp: Self) -> Self {
+                if a.0 <= 0 {
+                    return Self::zero();
+                }
+                Self::from_q32(q32::exp2(q32::mul(exp.to_q32(), q32::log2(a.to_q32()))))
+            }
+
+            fn mad(m: Self, a: Self, b: Self) -> Self {
+                m * a + b
+            }
+
+            fn approx(a: Self, b: Self, eps: Self) -> bool {
+                Self::abs(a - b) < eps
+            }
+
+            fn floor(a: Self) -> Self {
+                $Fixed(a.0 & !((1 << $frac_bits) - 1))
+            }
+
+            fn ceil(a: Self) -> Self {
+                Self::floor($Fixed(a.0.saturating_add((1 << $frac_bits) - 1)))
+            }
+
+            fn copysign(a: Self, sign: Self) -> Self {
+                if sign.0 < 0 { -Self::abs(a) } else { Self::abs(a) }
+            }
+
+            fn smoothstep(e0: Self, e1: Self, t: Self) -> Self {
+                if t < e0 { return Self::zero(); }
+                if t >= e1 { return Self::one(); }
+                let x = (t - e0) / (e1 - e0);
+                x * x * (Self::three() - Self::two() * x)
+            }
+
+            fn round(a: Self) -> Self {
+                Self::floor($Fixed(a.0.saturating_add(1 << ($frac_bits - 1))))
+            }
+
+            fn is_nan(_: Self) -> Self {
+                Self::zero()
+            }
+
+            fn is_infinite(a: Self) -> Self {
+                if a == Self::MAX || a == Self::MIN { Self::one() } else { Self::zero() }
+            }
+
+            fn is_finite(a: Self) -> Self {
+                Self::one() - Self::is_infinite(a)
+            }
+
+            fn saturate(v: Self) -> Self {
+                NumberOps::max(NumberOps::min(v, Self::one()), Self::zero())
+            }
+
+            fn deg_to_rad(theta: Self) -> Self {
+                Self::from_q32(q32::mul(theta.to_q32(), q32::DEG_TO_RAD))
+            }
+
+            fn rad_to_deg(theta: Self) -> Self {
+                Self::from_q32(q32::mul(theta.to_q32(), q32::RAD_TO_DEG))
+            }
+
+            fn fmod(x: Self, y: Self) -> Self {
+                x % y
+            }
+
+            fn frac(v: Self) -> Self {
+                v - Self::trunc(v)
+            }
+
+            fn trunc(v: Self) -> Self {
+                if v.0 < 0 { -Self::floor(-v) } else { Self::floor(v) }
+            }
+
+            fn modf(v: Self) -> (Self, Self) {
+                (Self::frac(v), Self::trunc(v))
+            }
+
+            fn cos(v: Self) -> Self {
+                v.q32(q32::cos)
+            }
+
+            fn sin(v: Self) -> Self {
+                v.q32(q32::sin)
+            }
+
+            fn tan(v: Self) -> Self {
+                let q = v.to_q32();
+                Self::from_q32(q32::div(q32::sin(q), q32::cos(q)))
+            }
+
+            fn acos(v: Self) -> Self {
+                v.q32(q32::acos)
+            }
+
+            fn asin(v: Self) -> Self {
+                v.q32(q32::asin)
+            }
+
+            fn atan(v: Self) -> Self {
+                v.q32(q32::atan)
+            }
+
+            fn cosh(v: Self) -> Self {
+                let q = v.to_q32();
+                // halve before adding as exp saturates near the top of the q32 range
+                Self::from_q32(q32::exp(q) / 2 + q32::exp(-q) / 2)
+            }
+
+            fn sinh(v: Self) -> Self {
+                let q = v.to_q32();
+                Self::from_q32(q32::exp(q) / 2 - q32::exp(-q) / 2)
+            }
+
+            fn tanh(v: Self) -> Self {
+                let e2 = q32::exp(v.to_q32().saturating_mul(2));
+                Self::from_q32(q32::div(e2 - q32::ONE, e2.saturating_add(q32::ONE)))
+            }
+
+            fn sin_cos(v: Self) -> (Self, Self) {
+                (Self::sin(v), Self::cos(v))
+            }
+
+            fn atan2(y: Self, x: Self) -> Self {
+                Self::from_q32(q32::atan2(y.to_q32(), x.to_q32()))
+            }
+
+            fn exp(v: Self) -> Self {
+                v.q32(q32::exp)
+            }
+
+            fn exp2(v: Self) -> Self {
+                v.q32(q32::exp2)
+            }
+
+            fn log2(v: Self) -> Self {
+                v.q32(q32::log2)
+            }
+
+            fn log10(v: Self) -> Self {
+                Self::from_q32(q32::mul(q32::log2(v.to_q32()), q32::LOG10_2))
+            }
+
+            fn log(v: Self, base: Self) -> Self {
+                Self::from_q32(q32::div(q32::log2(v.to_q32()), q32::log2(base.to_q32())))
+            }
+        }
+
+        impl Cast<$Fixed> for $Fixed {
+            fn from_f32(v: f32) -> Self {
+                $Fixed::from_f32(v)
+            }
+
+            fn from_f64(v: f64) -> Self {
+                $Fixed::from_f64(v)
+            }
+
+            fn from_u32(v: u32) -> Self {
+                Self::from_int((v as i64).clamp($raw::MIN as i64, $raw::MAX as i64) as $raw)
+            }
+
+            fn from_i32(v: i32) -> Self {
+                Self::from_int((v as i64).clamp($raw::MIN as i64, $raw::MAX as i64) as $raw)
+            }
+
+            fn from_u64(v: u64) -> Self {
+                Self::from_int(v.min($raw::MAX as u64) as $raw)
+            }
+
+            fn from_i64(v: i64) -> Self {
+                Self::from_int(v.clamp($raw::MIN as i64, $raw::MAX as i64) as $raw)
+            }
+
+            fn from_usize(v: usize) -> Self {
+                Self::from_u64(v as u64)
+            }
+
+            fn as_f32(&self) -> f32 {
+                self.to_f32()
+            }
+
+            fn as_f64(&self) -> f64 {
+                self.to_f64()
+            }
+
+            fn as_u32(&self) -> u32 {
+                (self.0 / (1 << $frac_bits)).max(0) as u32
+            }
+
+            fn as_i32(&self) -> i32 {
+                (self.0 / (1 << $frac_bits)) as i32
+            }
+
+            fn as_u64(&self) -> u64 {
+                (self.0 / (1 << $frac_bits)).max(0) as u64
+            }
+
+            fn as_i64(&self) -> i64 {
+                (self.0 / (1 << $frac_bits)) as i64
+            }
+
+            fn as_usize(&self) -> usize {
+                (self.0 / (1 << $frac_bits)).max(0) as usize
+            }
+        }
+    }
+}
+
+fixed_impl!(Fixed32, i32, i64, 16);
+fixed_impl!(Fixed64, i64, i128, 32);
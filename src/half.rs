@@ -0,0 +1,433 @@
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Sub;
+use std::ops::SubAssign;
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Div;
+use std::ops::DivAssign;
+use std::ops::Rem;
+use std::ops::RemAssign;
+use std::ops::Neg;
+
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+
+use crate::num::*;
+use crate::vec::*;
+
+/// ieee 754 binary16 half precision float, intended as a compact storage type for gpu buffers.
+/// arithmetic is performed in `f32` and rounded back to the nearest `f16`
+#[allow(non_camel_case_types)]
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Copy, Clone, Default)]
+#[repr(transparent)]
+pub struct f16(u16);
+
+impl f16 {
+    /// `0.0`
+    pub const ZERO: f16 = f16(0x0000);
+    /// `1.0`
+    pub const ONE: f16 = f16(0x3c00);
+    /// `-1.0`
+    pub const NEG_ONE: f16 = f16(0xbc00);
+    /// largest finite value `65504.0`
+    pub const MAX: f16 = f16(0x7bff);
+    /// smallest finite value `-65504.0`
+    pub const MIN: f16 = f16(0xfbff);
+    /// smallest positive normal value `2^-14`
+    pub const MIN_POSITIVE: f16 = f16(0x0400);
+    /// difference between `1.0` and the next representable value `2^-10`
+    pub const EPSILON: f16 = f16(0x1400);
+    /// positive infinity
+    pub const INFINITY: f16 = f16(0x7c00);
+    /// negative infinity
+    pub const NEG_INFINITY: f16 = f16(0xfc00);
+    /// not a number
+    pub const NAN: f16 = f16(0x7e00);
+
+    /// creates an `f16` from its raw bit representation
+    pub const fn from_bits(bits: u16) -> f16 {
+        f16(bits)
+    }
+
+    /// returns the raw bit representation
+    pub const fn to_bits(self) -> u16 {
+        self.0
+    }
+
+    /// returns `v` rounded to the nearest `f16`, ties to even. values out of range become `inf`
+    pub const fn from_f32(v: f32) -> f16 {
+        let x = v.to_bits();
+        let sign = ((x >> 16) & 0x8000) as u16;
+        let exp = ((x >> 23) & 0xff) as i32;
+        let man = x & 0x007f_ffff;
+
+        // inf or nan, keeping nan quiet
+        if exp == 0xff {
+            let nan = if man != 0 { 0x0200 | (man >> 13) as u16 } else { 0 };
+            return f16(sign | 0x7c00 | nan);
+        }
+
+        // re-bias exponent
+        let e = exp - 127 + 15;
+        if e >= 0x1f {
+            return f16(sign | 0x7c00);
+        }
+
+        if e <= 0 {
+            // too small for a subnormal, rounds to signed zero
+            if e < -10 {
+                return f16(sign);
+            }
+            // subnormal, shift the mantissa with implicit leading bit into place
+            let m = man | 0x0080_0000;
+            let shift = (14 - e) as u32;
+            let half = 1 << (shift - 1);
+            let rem = m & ((1 << shift) - 1);
+            let mut h = (m >> shift) as u16;
+            if rem > half || (rem == half && (h & 1) == 1) {
+                h += 1;
+            }
+            return f16(sign | h);
+        }
+
+        // normal, rounding may carry into the exponent which correctly produces inf on overflow
+        let mut h = sign | ((e as u16) << 10) | (man >> 13) as u16;
+        let rem = man & 0x1fff;
+        if rem > 0x1000 || (rem == 0x1000 && (h & 1) == 1) {
+            h += 1;
+        }
+        f16(h)
+    }
+
+    /// returns `v` rounded to the nearest `f16`, ties to even. values out of range become `inf`.
+    /// this converts directly from the `f64` bits so it only rounds once
+    pub const fn from_f64(v: f64) -> f16 {
+        let x = v.to_bits();
+        let sign = ((x >> 48) & 0x8000) as u16;
+        let exp = ((x >> 52) & 0x7ff) as i32;
+        let man = x & 0x000f_ffff_ffff_ffff;
+
+        // inf or nan, keeping nan quiet
+        if exp == 0x7ff {
+            let nan = if man != 0 { 0x0200 | (man >> 42) as u16 } else { 0 };
+            return f16(sign | 0x7c00 | nan);
+        }
+
+        // re-bias exponent
+        let e = exp - 1023 + 15;
+        if e >= 0x1f {
+            return f16(sign | 0x7c00);
+        }
+
+        if e <= 0 {
+            // too small for a subnormal, rounds to signed zero
+            if e < -10 {
+                return f16(sign);
+            }
+            // subnormal, shift the mantissa with implicit leading bit into place
+            let m = man | 0x0010_0000_0000_0000;
+            let shift = (43 - e) as u32;
+            let half = 1 << (shift - 1);
+            let rem = m & ((1 << shift) - 1);
+            let mut h = (m >> shift) as u16;
+            if rem > half || (rem == half && (h & 1) == 1) {
+                h += 1;
+            }
+            return f16(sign | h);
+        }
+
+        // normal, rounding may carry into the exponent which correctly produces inf on overflow
+        let mut h = sign | ((e as u16) << 10) | (man >> 42) as u16;
+        let rem = man & 0x03ff_ffff_ffff;
+        if rem > 0x0200_0000_0000 || (rem == 0x0200_0000_0000 && (h & 1) == 1) {
+            h += 1;
+        }
+        f16(h)
+    }
+
+    /// returns the value converted to `f32`, this is lossless
+    pub const fn to_f32(self) -> f32 {
+        let h = self.0 as u32;
+        let sign = (h & 0x8000) << 16;
+        let exp = (h >> 10) & 0x1f;
+        let man = h & 0x03ff;
+        if exp == 0x1f {
+            // inf or nan
+            f32::from_bits(sign | 0x7f80_0000 | (man << 13))
+        }
+        else if exp == 0 {
+            // zero or subnormal, `man * 2^-24` is exact in f32
+            let v = man as f32 * (1.0 / 16777216.0);
+            if sign != 0 { -v } else { v }
+        }
+        else {
+            f32::from_bits(sign | ((exp + 112) << 23) | (man << 13))
+        }
+    }
+
+    /// returns the value converted to `f64`, this is lossless
+    pub const fn to_f64(self) -> f64 {
+        self.to_f32() as f64
+    }
+
+    /// returns true if the value is not a number (`nan`)
+    pub const fn is_nan(self) -> bool {
+        (self.0 & 0x7c00) == 0x7c00 && (self.0 & 0x03ff) != 0
+    }
+
+    /// returns true if the value is `inf` or `-inf`
+    pub const fn is_infinite(self) -> bool {
+        (self.0 & 0x7fff) == 0x7c00
+    }
+
+    /// returns true if the value is neither `inf` or `nan`
+    pub const fn is_finite(self) -> bool {
+        (self.0 & 0x7c00) != 0x7c00
+    }
+}
+
+impl From<f16> for f32 {
+    fn from(v: f16) -> f32 {
+        v.to_f32()
+    }
+}
+
+impl From<f16> for f64 {
+    fn from(v: f16) -> f64 {
+        v.to_f64()
+    }
+}
+
+impl PartialEq for f16 {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_f32() == other.to_f32()
+    }
+}
+
+impl PartialOrd for f16 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.to_f32().partial_cmp(&other.to_f32())
+    }
+}
+
+impl Display for f16 {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        Display::fmt(&self.to_f32(), f)
+    }
+}
+
+impl Debug for f16 {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        Debug::fmt(&self.to_f32(), f)
+    }
+}
+
+impl Neg for f16 {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        f16(self.0 ^ 0x8000)
+    }
+}
+
+/// macro to stamp out arithmetic ops for `f16` evaluated in `f32`
+macro_rules! f16_op_impl {
+    ($op:ident, $func:ident, $op_assign:ident, $func_assign:ident) => {
+        impl $op<Self> for f16 {
+            type Output = Self;
+            fn $func(self, other: Self) -> Self {
+                f16::from_f32(self.to_f32().$func(other.to_f32()))
+            }
+        }
+
+        impl $op_assign<Self> for f16 {
+            fn $func_assign(&mut self, other: Self) {
+                *self = self.$func(other);
+            }
+        }
+    }
+}
+
+f16_op_impl!(Add, add, AddAssign, add_assign);
+f16_op_impl!(Sub, sub, SubAssign, sub_assign);
+f16_op_impl!(Mul, mul, MulAssign, mul_assign);
+f16_op_impl!(Div, div, DivAssign, div_assign);
+f16_op_impl!(Rem, rem, RemAssign, rem_assign);
+
+impl Base<f16> for f16 {
+    fn zero() -> Self {
+        f16::ZERO
+    }
+
+    fn one() -> Self {
+        f16::ONE
+    }
+
+    fn two() -> Self {
+        f16(0x4000)
+    }
+
+    fn three() -> Self {
+        f16(0x4200)
+    }
+
+    fn four() -> Self {
+        f16(0x4400)
+    }
+
+    fn min_value() -> Self {
+        f16::MIN
+    }
+
+    fn max_value() -> Self {
+        f16::MAX
+    }
+}
+
+impl Number for f16 {}
+
+impl NumberOps<f16> for f16 {
+    fn min(a: Self, b: Self) -> Self {
+        f16::from_f32(a.to_f32().min(b.to_f32()))
+    }
+
+    fn max(a: Self, b: Self) -> Self {
+        f16::from_f32(a.to_f32().max(b.to_f32()))
+    }
+
+    fn clamp(x: Self, min: Self, max: Self) -> Self {
+        NumberOps::max(NumberOps::min(x, max), min)
+    }
+
+    fn step(a: Self, b: Self) -> Self {
+        if a >= b {
+            Self::one()
+        }
+        else {
+            Self::zero()
+        }
+    }
+}
+
+impl SignedNumber for f16 {
+    fn minus_one() -> Self {
+        f16::NEG_ONE
+    }
+}
+
+impl SignedNumberOps<f16> for f16 {
+    fn signum(a: Self) -> Self {
+        f16::from_f32(a.to_f32().signum())
+    }
+
+    fn abs(a: Self) -> Self {
+        f16(a.0 & 0x7fff)
+    }
+}
+
+impl Cast<f16> for f16 {
+    fn from_f32(v: f32) -> Self {
+        f16::from_f32(v)
+    }
+
+    fn from_f64(v: f64) -> Self {
+        f16::from_f64(v)
+    }
+
+    fn from_u32(v: u32) -> Self {
+        f16::from_f32(v as f32)
+    }
+
+    fn from_i32(v: i32) -> Self {
+        f16::from_f32(v as f32)
+    }
+
+    fn from_u64(v: u64) -> Self {
+        f16::from_f32(v as f32)
+    }
+
+    fn from_i64(v: i64) -> Self {
+        f16::from_f32(v as f32)
+    }
+
+    fn from_usize(v: usize) -> Self {
+        f16::from_f32(v as f32)
+    }
+
+    fn as_f32(&self) -> f32 {
+        self.to_f32()
+    }
+
+    fn as_f64(&self) -> f64 {
+        self.to_f64()
+    }
+
+    fn as_u32(&self) -> u32 {
+        self.to_f32() as u32
+    }
+
+    fn as_i32(&self) -> i32 {
+        self.to_f32() as i32
+    }
+
+    fn as_u64(&self) -> u64 {
+        self.to_f32() as u64
+    }
+
+    fn as_i64(&self) -> i64 {
+        self.to_f32() as i64
+    }
+
+    fn as_usize(&self) -> usize {
+        self.to_f32() as usize
+    }
+}
+
+/// converts `src` into half precision writing the results into `dst`, panics if the slices differ in length
+pub fn vec4f_to_vec4h_slice(src: &[Vec4<f32>], dst: &mut [Vec4<f16>]) {
+    assert_eq!(src.len(), dst.len());
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = Vec4 {
+            x: f16::from_f32(s.x),
+            y: f16::from_f32(s.y),
+            z: f16::from_f32(s.z),
+            w: f16::from_f32(s.w),
+        };
+    }
+}
+
+/// converts half precision `src` into single precision writing the results into `dst`, panics if the slices differ in length
+pub fn vec4h_to_vec4f_slice(src: &[Vec4<f16>], dst: &mut [Vec4<f32>]) {
+    assert_eq!(src.len(), dst.len());
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = Vec4 {
+            x: s.x.to_f32(),
+            y: s.y.to_f32(),
+            z: s.z.to_f32(),
+            w: s.w.to_f32(),
+        };
+    }
+}
+
+/// returns `src` converted into a newly allocated half precision vec
+pub fn vec4f_to_vec4h(src: &[Vec4<f32>]) -> Vec<Vec4<f16>> {
+    src.iter().map(|s| Vec4 {
+        x: f16::from_f32(s.x),
+        y: f16::from_f32(s.y),
+        z: f16::from_f32(s.z),
+        w: f16::from_f32(s.w),
+    }).collect()
+}
+
+/// returns half precision `src` converted into a newly allocated single precision vec
+pub fn vec4h_to_vec4f(src: &[Vec4<f16>]) -> Vec<Vec4<f32>> {
+    src.iter().map(|s| Vec4 {
+        x: s.x.to_f32(),
+        y: s.y.to_f32(),
+        z: s.z.to_f32(),
+        w: s.w.to_f32(),
+    }).collect()
+}
@@ -30,13 +30,50 @@ pub mod mat;
 /// generic quaternion for varying floating point precision
 pub mod quat;
 
+/// generic dual quaternion for rigid transforms and skinning
+pub mod dual_quat;
+
+/// generic translation, rotation and scale transform with composition and interpolation
+pub mod transform;
+
+/// euler angle conversions in all twelve rotation orders for quaternions and matrices
+pub mod euler;
+
+/// const generic row-major matrix and column vector for dimensions not covered by the fixed size types
+pub mod matrix;
+
+/// geometric primitive types which wrap the free intersection, distance and closest point functions
+pub mod primitives;
+
+/// 2D separating axis tests for convex polygons, circles and capsules returning the minimum translation vector and contacts
+pub mod sat;
+
+/// bounding volume hierarchy built with the surface area heuristic for ray casting and overlap queries over many items
+pub mod bvh;
+
+/// dynamic aabb tree broadphase with fattened proxies, incremental insert, remove and move, and pair queries
+pub mod dynamic_tree;
+
 /// module containing vector swizzling traits
 pub mod swizz;
 
+/// software half precision `f16` float type for compact vertex and instance data storage
+pub mod half;
+
+/// deterministic Q16.16 and Q32.32 fixed point types which implement `Float` and `FloatOps` using integer arithmetic
+pub mod fixed;
+
+/// 4-wide matrix, quaternion and vector kernels with `std::arch` paths for `f32` behind the `simd` feature
+mod simd;
+
 use num::*;
 use vec::*;
 use mat::*;
 use quat::*;
+use dual_quat::*;
+use transform::*;
+use matrix::*;
+use primitives::Support;
 
 /// opinionated type abbreviations
 #[cfg(feature = "short_types")]
@@ -57,6 +94,15 @@ pub type Vec3d = Vec3<f64>;
 #[cfg(feature = "short_types")]
 pub type Vec4d = Vec4<f64>;
 
+#[cfg(feature = "short_types")]
+pub type Vec2h = Vec2<half::f16>;
+
+#[cfg(feature = "short_types")]
+pub type Vec3h = Vec3<half::f16>;
+
+#[cfg(feature = "short_types")]
+pub type Vec4h = Vec4<half::f16>;
+
 #[cfg(feature = "short_types")]
 pub type Vec2i = Vec2<i32>;
 
@@ -75,6 +121,69 @@ pub type Vec3u = Vec3<u32>;
 #[cfg(feature = "short_types")]
 pub type Vec4u = Vec4<u32>;
 
+#[cfg(feature = "short_types")]
+pub type Vec2i8 = Vec2<i8>;
+
+#[cfg(feature = "short_types")]
+pub type Vec3i8 = Vec3<i8>;
+
+#[cfg(feature = "short_types")]
+pub type Vec4i8 = Vec4<i8>;
+
+#[cfg(feature = "short_types")]
+pub type Vec2u8 = Vec2<u8>;
+
+#[cfg(feature = "short_types")]
+pub type Vec3u8 = Vec3<u8>;
+
+#[cfg(feature = "short_types")]
+pub type Vec4u8 = Vec4<u8>;
+
+#[cfg(feature = "short_types")]
+pub type Vec2i16 = Vec2<i16>;
+
+#[cfg(feature = "short_types")]
+pub type Vec3i16 = Vec3<i16>;
+
+#[cfg(feature = "short_types")]
+pub type Vec4i16 = Vec4<i16>;
+
+#[cfg(feature = "short_types")]
+pub type Vec2u16 = Vec2<u16>;
+
+#[cfg(feature = "short_types")]
+pub type Vec3u16 = Vec3<u16>;
+
+#[cfg(feature = "short_types")]
+pub type Vec4u16 = Vec4<u16>;
+
+#[cfg(feature = "short_types")]
+pub type Vec2i64 = Vec2<i64>;
+
+#[cfg(feature = "short_types")]
+pub type Vec3i64 = Vec3<i64>;
+
+#[cfg(feature = "short_types")]
+pub type Vec4i64 = Vec4<i64>;
+
+#[cfg(feature = "short_types")]
+pub type Vec2u64 = Vec2<u64>;
+
+#[cfg(feature = "short_types")]
+pub type Vec3u64 = Vec3<u64>;
+
+#[cfg(feature = "short_types")]
+pub type Vec4u64 = Vec4<u64>;
+
+#[cfg(feature = "short_types")]
+pub type BVec2 = Vec2<bool>;
+
+#[cfg(feature = "short_types")]
+pub type BVec3 = Vec3<bool>;
+
+#[cfg(feature = "short_types")]
+pub type BVec4 = Vec4<bool>;
+
 #[cfg(feature = "short_types")]
 pub type Mat2f = Mat2<f32>;
 
@@ -111,6 +220,30 @@ pub type Quatf = Quat<f32>;
 #[cfg(feature = "short_types")]
 pub type Quatd = Quat<f64>;
 
+#[cfg(feature = "short_types")]
+pub type DualQuatf = DualQuat<f32>;
+
+#[cfg(feature = "short_types")]
+pub type DualQuatd = DualQuat<f64>;
+
+#[cfg(feature = "short_types")]
+pub type Transformf = Transform<f32>;
+
+#[cfg(feature = "short_types")]
+pub type Transformd = Transform<f64>;
+
+#[cfg(feature = "short_types")]
+pub type Vectorf<const N: usize> = Vector<f32, N>;
+
+#[cfg(feature = "short_types")]
+pub type Vectord<const N: usize> = Vector<f64, N>;
+
+#[cfg(feature = "short_types")]
+pub type Matrixf<const R: usize, const C: usize> = Matrix<f32, R, C>;
+
+#[cfg(feature = "short_types")]
+pub type Matrixd<const R: usize, const C: usize> = Matrix<f64, R, C>;
+
 /// classification for tests vs planes (behind, infront or intersects)
 #[derive(PartialEq, Debug)]
 pub enum Classification {
@@ -122,6 +255,69 @@ pub enum Classification {
     Intersects,
 }
 
+/// result of a ray intersection, the hit point is `r0 + rv * t` where `r0` and `rv` are the ray origin and direction
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RayHit<T, V> {
+    /// distance along the ray in units of the ray direction
+    pub t: T,
+    /// position of the hit
+    pub point: V,
+    /// unit outward surface normal at the hit point
+    pub normal: V,
+    /// true if the ray hit the outside of the surface, false if it started inside or hit the back of a triangle
+    pub front_face: bool,
+    /// barycentric weight of the second triangle vertex, 0 for other shapes
+    pub u: T,
+    /// barycentric weight of the third triangle vertex, 0 for other shapes
+    pub v: T
+}
+
+impl<T: Number, V> RayHit<T, V> {
+    /// constructs a hit with zero barycentrics
+    pub fn new(t: T, point: V, normal: V, front_face: bool) -> Self {
+        RayHit {
+            t,
+            point,
+            normal,
+            front_face,
+            u: T::zero(),
+            v: T::zero()
+        }
+    }
+
+    /// returns the barycentric coordinates `(w, u, v)` of the hit for the first, second and third triangle vertices
+    pub fn barycentric(&self) -> (T, T, T) {
+        (T::one() - self.u - self.v, self.u, self.v)
+    }
+}
+
+/// penetration of two overlapping convex shapes `a` and `b`, translating `b` by `normal * depth` separates them
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Penetration<T, V> {
+    /// unit contact normal pointing from `a` toward `b`
+    pub normal: V,
+    /// penetration depth along the normal
+    pub depth: T,
+    /// deepest point of `a` inside `b`
+    pub point_a: V,
+    /// deepest point of `b` inside `a`, this is `point_a - normal * depth`
+    pub point_b: V
+}
+
+/// first contact of a moving shape against an obstacle found by the swept and conservative advancement queries
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TimeOfImpact<T, V> {
+    /// fraction `0-1` of the motion at which the shapes first touch, shapes which overlap at the start have `t = 0`
+    pub t: T,
+    /// contact point on the obstacle at the time of impact
+    pub point: V,
+    /// unit contact normal on the obstacle facing the moving shape
+    pub normal: V
+}
+
 /// returns the minimum of `a` and `b`
 pub fn min<T: Number, V: NumberOps<T>>(a: V, b: V) -> V {
     V::min(a, b)
@@ -466,6 +662,56 @@ pub fn log<T: Float, V: FloatOps<T>>(v: V, base: T) -> V {
     V::log(v, base)
 }
 
+/// returns a boolean mask with component wise `a < b`
+pub fn less_than<T: Number, V: VecCompare<T>>(a: V, b: V) -> V::Mask {
+    V::less_than(a, b)
+}
+
+/// returns a boolean mask with component wise `a <= b`
+pub fn less_equal<T: Number, V: VecCompare<T>>(a: V, b: V) -> V::Mask {
+    V::less_equal(a, b)
+}
+
+/// returns a boolean mask with component wise `a > b`
+pub fn greater_than<T: Number, V: VecCompare<T>>(a: V, b: V) -> V::Mask {
+    V::greater_than(a, b)
+}
+
+/// returns a boolean mask with component wise `a >= b`
+pub fn greater_equal<T: Number, V: VecCompare<T>>(a: V, b: V) -> V::Mask {
+    V::greater_equal(a, b)
+}
+
+/// returns a boolean mask with component wise `a == b`
+pub fn equal<T: Number, V: VecCompare<T>>(a: V, b: V) -> V::Mask {
+    V::equal(a, b)
+}
+
+/// returns a boolean mask with component wise `a != b`
+pub fn not_equal<T: Number, V: VecCompare<T>>(a: V, b: V) -> V::Mask {
+    V::not_equal(a, b)
+}
+
+/// returns the component wise logical not of boolean mask `mask`
+pub fn not<M: VecMask>(mask: M) -> M {
+    !mask
+}
+
+/// returns true if all components of boolean mask `mask` are true
+pub fn all<M: VecMask>(mask: M) -> bool {
+    M::all(mask)
+}
+
+/// returns true if any component of boolean mask `mask` is true
+pub fn any<M: VecMask>(mask: M) -> bool {
+    M::any(mask)
+}
+
+/// returns a vector selecting component wise from `a` where `mask` is true and `b` where `mask` is false
+pub fn select<T: Number, V: VecCompare<T>>(mask: V::Mask, a: V, b: V) -> V {
+    V::select(mask, a, b)
+}
+
 /// returns the vec2 rotated anti-clockwise rotation by radian `angle`
 pub fn rotate_2d<T: Float + FloatOps<T>>(v: Vec2<T>, angle: T) -> Vec2<T> {
     let c = cos(angle);
@@ -555,7 +801,15 @@ pub fn project_to_sc_vdown<T: Float + FloatOps<T>>(p: Vec3<T>, view_projection:
     Vec2::<T>::from(sc) * viewport
 }
 
-/// returns the unprojected 3D world position of point `p` which is specified in normalized device coordinates
+/// returns the 2D screen coordinate of 3D point `p` projected with `view_projection`, performing homogenous divide and `viewport` correction
+/// the z component is the 0-1 window depth for a projection with `depth` range. assumes screen coordinates are vup in the y-axis y.0 = bottom y.height = top
+pub fn project_to_sc_depth<T: Float + FloatOps<T>>(p: Vec3<T>, view_projection: Mat4<T>, viewport: Vec2<T>, depth: DepthRange) -> Vec3<T> {
+    let ndc = project_to_ndc(p, view_projection);
+    let sc  = Vec2::from(ndc) * T::point_five() + T::point_five();
+    Vec3::from((sc * viewport, depth.ndc_to_window(ndc.z)))
+}
+
+/// returns the unprojected 3D world position of point `p` which is specified in normalized device coordinates, the depth `p.z` must be in the range of the projection
 pub fn unproject_ndc<T: Float>(p: Vec3<T>, view_projection: Mat4<T>) -> Vec3<T> {
     let inv = view_projection.inverse();
     inv * p
@@ -569,6 +823,14 @@ pub fn unproject_sc<T: Float>(p: Vec3<T>, view_projection: Mat4<T>, viewport: Ve
     unproject_ndc(ndc, view_projection)
 }
 
+/// returns the unprojected 3D world position of screen coordinate `p` where `p.z` is the 0-1 window depth for a projection with `depth` range
+/// assumes screen coordinates are vup in the y-axis y.0 = bottom y.height = top
+pub fn unproject_sc_depth<T: Float>(p: Vec3<T>, view_projection: Mat4<T>, viewport: Vec2<T>, depth: DepthRange) -> Vec3<T> {
+    let ndc_xy = (Vec2::from(p) / viewport) * Vec2::from(T::two()) - Vec2::from(T::one());
+    let ndc = Vec3::from((ndc_xy, depth.window_to_ndc(p.z)));
+    unproject_ndc(ndc, view_projection)
+}
+
 /// returns the unprojected 3D world position of screen coordinate `p`
 /// coordinates are vdown in the y-axis vdown = y.0 = top y.height = bottom
 pub fn unproject_sc_vdown<T: Float>(p: Vec3<T>, view_projection: Mat4<T>, viewport: Vec2<T>) -> Vec3<T> {
@@ -976,7 +1238,10 @@ pub fn cone_vs_plane<T: Float + SignedNumberOps<T>, V: VecN<T> + Cross<T> + Dot<
     // check if the tip and cones extent are on different sides of the plane
     let d1 = dot(n, cp) + pd;
     // extent from the tip is at the base centre point perp of cv at the radius edge... we need to choose the side toward the plane
-    let perp = normalize(cross(cross(n, cv), cv));
+    // when the axis is parallel to the normal the base is parallel to the plane and any perpendicular has no extent along it
+    let perp = cross(cross(n, cv), cv);
+    let len = mag(perp);
+    let perp = if len > T::small_epsilon() { perp / len } else { V::zero() };
     let extent = l2 + perp * r;
     let extent2 = l2 + perp * -r;
     let d2 = dot(n, extent) + pd;
@@ -1091,7 +1356,7 @@ pub fn aabb_vs_obb<T: Number + Float + SignedNumber + SignedNumberOps<T> + Numbe
         verts1.push(obb * corner);
     }
 
-    gjk_3d(verts0, verts1)
+    gjk_3d(&verts0, &verts1)
 }
 
 /// returns true if the sphere with centre `s0` and radius `r0` overlaps obb defined by matrix `obb`, where the matrix
@@ -1127,7 +1392,7 @@ pub fn obb_vs_obb<T: Number + Float + SignedNumber + SignedNumberOps<T> + Number
         verts1.push(obb1 * corner);
     }
 
-    gjk_3d(verts0, verts1)
+    gjk_3d(&verts0, &verts1)
 }
 
 /// returns true if the capsule `cp0-cp1` with radius `cr0` overlaps the capsule `cp2-cp3` with radius `cr1`
@@ -1167,8 +1432,8 @@ pub fn capsule_vs_capsule<T: Float + FloatOps<T> + SignedNumberOps<T>, V: VecN<T
     }
 }
 
-/// returns the intersection point of ray with origin `r0` and direction `rv` against the sphere (or circle) centred at `s0` with radius `r`
-pub fn ray_vs_sphere<T: Float + FloatOps<T> + NumberOps<T>, V: VecN<T> + VecFloatOps<T>>(r0: V, rv: V, s0: V, r: T) -> Option<V> {
+/// returns `(t1, t2)` the ray parameters where the ray with origin `r0` and direction `rv` enters and exits the sphere centred at `s0` with radius `r`
+fn ray_vs_sphere_interval<T: Float + FloatOps<T> + NumberOps<T>, V: VecN<T> + VecFloatOps<T>>(r0: V, rv: V, s0: V, r: T) -> Option<(T, T)> {
     let oc = r0 - s0;
     let a = dot(rv, rv);
     let b = T::two() * dot(oc, rv);
@@ -1181,6 +1446,33 @@ pub fn ray_vs_sphere<T: Float + FloatOps<T> + NumberOps<T>, V: VecN<T> + VecFloa
     else {
         let t1 = (-b - sqrt(discriminant)) / (T::two()*a);
         let t2 = (-b + sqrt(discriminant)) / (T::two()*a);
+        Some((t1, t2))
+    }
+}
+
+/// returns the hit of ray with origin `r0` and direction `rv` against the sphere (or circle) centred at `s0` with radius `r`, ignoring hits further than `t_max` along the ray.
+/// rays starting inside the sphere hit the back face on the way out
+pub fn ray_vs_sphere_hit<T: Float + FloatOps<T> + NumberOps<T>, V: VecN<T> + VecFloatOps<T>>(r0: V, rv: V, s0: V, r: T, t_max: T) -> Option<RayHit<T, V>> {
+    let (t1, t2) = ray_vs_sphere_interval(r0, rv, s0, r)?;
+    let (t, front_face) = if t1 >= T::zero() {
+        (t1, true)
+    }
+    else {
+        (t2, false)
+    };
+    if t < T::zero() || t > t_max {
+        None
+    }
+    else {
+        let point = r0 + rv * t;
+        Some(RayHit::new(t, point, (point - s0) / r, front_face))
+    }
+}
+
+/// returns the intersection point of ray with origin `r0` and direction `rv` against the sphere (or circle) centred at `s0` with radius `r`.
+/// rays starting inside return the exit point, and unlike `ray_vs_sphere_hit` a sphere entirely behind the ray returns its far side behind the origin
+pub fn ray_vs_sphere<T: Float + FloatOps<T> + NumberOps<T>, V: VecN<T> + VecFloatOps<T>>(r0: V, rv: V, s0: V, r: T) -> Option<V> {
+    ray_vs_sphere_interval(r0, rv, s0, r).map(|(t1, t2)| {
         let t = if t1 > T::zero() && t2 > T::zero() {
             min(t1, t2)
         }
@@ -1190,12 +1482,12 @@ pub fn ray_vs_sphere<T: Float + FloatOps<T> + NumberOps<T>, V: VecN<T> + VecFloa
         else {
             t2
         };
-        Some(r0 + rv * t)
-    }
+        r0 + rv * t
+    })
 }
 
-/// returns the intersection point of the ray with origin `r0` and direction `rv` with the aabb defined by `aabb_min` and `aabb_max`
-pub fn ray_vs_aabb<T: Number + NumberOps<T>, V: VecN<T>>(r0: V, rv: V, aabb_min: V, aabb_max: V) -> Option<V> {
+/// returns `(tmin, imin, tmax, imax)` the ray parameters where the ray enters and exits the aabb and the axes of the faces it crosses
+fn ray_vs_aabb_interval<T: Number + NumberOps<T>, V: VecN<T>>(r0: V, rv: V, aabb_min: V, aabb_max: V) -> Option<(T, usize, T, usize)> {
     // http://gamedev.stackexchange.com/a/18459
     // min / max's from aabb axes
     let dirfrac = V::one() / rv;
@@ -1203,11 +1495,19 @@ pub fn ray_vs_aabb<T: Number + NumberOps<T>, V: VecN<T>>(r0: V, rv: V, aabb_min:
     let tm = (aabb_max[0] - r0[0]) * dirfrac[0];
     let mut tmin = min(tx, tm);
     let mut tmax = max(tx, tm);
-    for i in 0..V::len() {
+    let mut imin = 0;
+    let mut imax = 0;
+    for i in 1..V::len() {
         let t1 = (aabb_min[i] - r0[i]) * dirfrac[i];
         let t2 = (aabb_max[i] - r0[i]) * dirfrac[i];
-        tmin = max(min(t1, t2), tmin);
-        tmax = min(max(t1, t2), tmax);
+        if min(t1, t2) > tmin {
+            tmin = min(t1, t2);
+            imin = i;
+        }
+        if max(t1, t2) < tmax {
+            tmax = max(t1, t2);
+            imax = i;
+        }
     }
 
     if tmax < T::zero() || tmin > tmax {
@@ -1216,27 +1516,79 @@ pub fn ray_vs_aabb<T: Number + NumberOps<T>, V: VecN<T>>(r0: V, rv: V, aabb_min:
         None
     }
     else {
-        // otherwise tmin is length along the ray we intersect at
-        Some(r0 + rv * tmin)
+        Some((tmin, imin, tmax, imax))
+    }
+}
+
+/// returns the hit of the ray with origin `r0` and direction `rv` with the aabb defined by `aabb_min` and `aabb_max`, ignoring hits further than `t_max` along the ray.
+/// rays starting inside the aabb hit the back face on the way out
+pub fn ray_vs_aabb_hit<T: SignedNumber + NumberOps<T>, V: VecN<T>>(r0: V, rv: V, aabb_min: V, aabb_max: V, t_max: T) -> Option<RayHit<T, V>> {
+    let (tmin, imin, tmax, imax) = ray_vs_aabb_interval(r0, rv, aabb_min, aabb_max)?;
+    // tmin is length along the ray we intersect at, unless we start inside and exit at tmax
+    let (t, i, front_face) = if tmin >= T::zero() {
+        (tmin, imin, true)
+    }
+    else {
+        (tmax, imax, false)
+    };
+    if t > t_max {
+        None
+    }
+    else {
+        // outward normal of the face, entering faces oppose the ray and exiting faces follow it
+        let mut normal = V::zero();
+        normal[i] = if (rv[i] > T::zero()) == front_face {
+            T::minus_one()
+        }
+        else {
+            T::one()
+        };
+        Some(RayHit::new(t, r0 + rv * t, normal, front_face))
     }
 }
 
-/// returns the intersection of the 3D ray with origin `r0` and direction `rv` with the obb defined by `mat`
+/// returns the intersection point of the ray with origin `r0` and direction `rv` with the aabb defined by `aabb_min` and `aabb_max`.
+/// if the ray starts inside the aabb this is the entry point behind the origin, use `ray_vs_aabb_hit` for the exit point
+pub fn ray_vs_aabb<T: Number + NumberOps<T>, V: VecN<T>>(r0: V, rv: V, aabb_min: V, aabb_max: V) -> Option<V> {
+    ray_vs_aabb_interval(r0, rv, aabb_min, aabb_max).map(|(tmin, _, _, _)| r0 + rv * tmin)
+}
+
+/// returns the hit of the 3D ray with origin `r0` and direction `rv` with the obb defined by `mat`, ignoring hits further than `t_max` along the ray.
+/// rays starting inside the obb hit the back face on the way out
+pub fn ray_vs_obb_hit<T: Float + NumberOps<T>,
+    V: VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + VecN<T> + SignedVecN<T>,
+    M: MatTranslate<V> + MatInverse<T> + MatRotate3D<T, V> + MatN<T, V>
+    + Into<Mat3<T>> + Copy>
+    (r0: V, rv: V, mat: M, t_max: T) -> Option<RayHit<T, V>> where Mat3<T> : MatN<T, V> {
+    // the unit cube space ray is not normalized so t is the same in both spaces
+    let invm = mat.inverse();
+    let tr1 = invm * r0;
+    let rotm : Mat3<T> = invm.into();
+    let trv = rotm * rv;
+    ray_vs_aabb_hit(tr1, trv, -V::one(), V::one(), t_max).map(|hit| {
+        // normals transform by the inverse transpose
+        RayHit::new(hit.t, r0 + rv * hit.t, normalize(rotm.transpose() * hit.normal), hit.front_face)
+    })
+}
+
+/// returns the intersection of the 3D ray with origin `r0` and direction `rv` with the obb defined by `mat`.
+/// if the ray starts inside the obb this is the entry point behind the origin, use `ray_vs_obb_hit` for the exit point
 pub fn ray_vs_obb<T: Float + NumberOps<T>,
     V: VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + VecN<T> + SignedVecN<T>,
     M: MatTranslate<V> + MatInverse<T> + MatRotate3D<T, V> + MatN<T, V>
     + Into<Mat3<T>> + Copy>
     (r0: V, rv: V, mat: M) -> Option<V> where Mat3<T> : MatN<T, V> {
+    // the unit cube space ray is not normalized so t is the same in both spaces
     let invm = mat.inverse();
     let tr1 = invm * r0;
     let rotm : Mat3<T> = invm.into();
     let trv = rotm * rv;
-    let ip = ray_vs_aabb(tr1, normalize(trv), -V::one(), V::one());
-    ip.map(|ip| mat * ip)
+    ray_vs_aabb_interval(tr1, trv, -V::one(), V::one()).map(|(tmin, _, _, _)| r0 + rv * tmin)
 }
 
-/// returns the intersection point of ray `r0` and normalized direction `rv` with triangle `t0-t1-t2`
-pub fn ray_vs_triangle<T: Float>(r0: Vec3<T>, rv: Vec3<T>, t0: Vec3<T>, t1: Vec3<T>, t2: Vec3<T>) -> Option<Vec3<T>> {
+/// returns `(t, u, v, det)` for the ray `r0` and direction `rv` hitting triangle `t0-t1-t2` in front of the origin, a positive `det` means
+/// the ray opposes the counter clockwise normal
+fn ray_vs_triangle_tuv<T: Float>(r0: Vec3<T>, rv: Vec3<T>, t0: Vec3<T>, t1: Vec3<T>, t2: Vec3<T>) -> Option<(T, T, T, T)> {
     // möller–trumbore intersection algorithm
     // ported verbatim https://en.wikipedia.org/wiki/Möller–Trumbore_intersection_algorithm
     let edge1 = t1 - t0;
@@ -1264,7 +1616,7 @@ pub fn ray_vs_triangle<T: Float>(r0: Vec3<T>, rv: Vec3<T>, t0: Vec3<T>, t1: Vec3
                 // now we can compute t to find out where the intersection point is on the line
                 let t = f * dot(edge2, q);
                 if t > T::zero() {
-                    Some(r0 + rv * t)
+                    Some((t, u, v, a))
                 }
                 else {
                     // line intersects but ray does not
@@ -1275,164 +1627,189 @@ pub fn ray_vs_triangle<T: Float>(r0: Vec3<T>, rv: Vec3<T>, t0: Vec3<T>, t1: Vec3
     }
 }
 
-/// returns the intersection point of ray wih origin `r0` and direction `rv` against the capsule with line `c0-c1` and radius `cr`
-pub fn ray_vs_capsule<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + Cross<T> + VecFloatOps<T> + SignedNumberOps<T> + FloatOps<T>>(r0: V, rv: V, c0: V, c1: V, cr: T) -> Option<V> {
-    // shortest line seg within radius will indicate we intersect an infinite cylinder about an axis
-    let seg = shortest_line_segment_between_line_and_line_segment(r0, r0 + rv, c0, c1);
-    let mut ipc = V::max_value();
-    let mut bc = false;
-    if let Some((l0, l1)) = seg {
-        // check we intesect the cylinder
-        if dist2(l0, l1) < sqr(cr) {
-            // intesection of ray and infinite cylinder about axis
-            // https://stackoverflow.com/questions/4078401/trying-to-optimize-line-vs-cylinder-intersection
-            let a = c0;
-            let b = c1;
-            let v = rv;
-            let r = cr;
-
-            let ab = b - a;
-            let ao = r0 - a;
-            let aoxab = cross(ao, ab);
-            let vxab = cross(v, ab);
-            let ab2 = dot(ab, ab);
-
-            let aa = dot(vxab, vxab);
-            let bb = T::two() * dot(vxab, aoxab);
-            let cc = dot(aoxab, aoxab) - (r*r * ab2);
-            let dd = bb * bb - T::four() * aa * cc;
-
-            if dd >= T::zero() {
-                let t = (-bb - sqrt(dd)) / (T::two() * aa);
-                if t >= T::zero() {
-                    // intersection point
-                    ipc = r0 + rv * t;
-                    // clamps to finite cylinder extents
-                    let ipd = distance_on_line(ipc, a, b);
-                    if ipd >= T::zero() && ipd <= dist(a, b) {
-                        bc = true;
-                    }
-                }
-            }
-        }
-    }
-
-    // if our line doesnt intersect the cylinder, we might still intersect the top / bottom sphere
-    // test intersections with the end spheres
-    let bs1 = ray_vs_sphere(r0, rv, c0, cr);
-    let bs2 = ray_vs_sphere(r0, rv, c1, cr);
-
-    // get optional intersection points
-    let ips1 = if let Some(ip) = bs1 {
-        ip
-    }
-    else {
-        V::max_value()
-    };
-
-    let ips2 = if let Some(ip) = bs2 {
-        ip
-    }
-    else {
-        V::max_value()
-    };
-
-    // we need to choose the closes intersection if we have multiple
-    let ips : [V; 3] = [ips1, ips2, ipc];
-    let bips : [bool; 3] = [bs1.is_some(), bs2.is_some(), bc];
-
-    let mut iclosest = -1;
-    let mut dclosest = T::max_value();
-    for i in 0..3 {
-        if bips[i] {
-            let dd = distance_on_line(ips[i], r0, r0 + rv);
-            if dd < dclosest {
-                iclosest = i as i32;
-                dclosest = dd;
-            }
-        }
-    }
+/// returns the hit of ray `r0` and normalized direction `rv` with triangle `t0-t1-t2`, ignoring hits further than `t_max` along the ray.
+/// the normal is `cross(t1 - t0, t2 - t0)` so the front face has counter clockwise winding and `u, v` weight `t1` and `t2`
+pub fn ray_vs_triangle_hit<T: Float + FloatOps<T>>(r0: Vec3<T>, rv: Vec3<T>, t0: Vec3<T>, t1: Vec3<T>, t2: Vec3<T>, t_max: T) -> Option<RayHit<T, Vec3<T>>> {
+    let (t, u, v, det) = ray_vs_triangle_tuv(r0, rv, t0, t1, t2).filter(|tuv| tuv.0 <= t_max)?;
+    let mut hit = RayHit::new(t, r0 + rv * t, normalize(cross(t1 - t0, t2 - t0)), det > T::zero());
+    hit.u = u;
+    hit.v = v;
+    Some(hit)
+}
 
-    // if we have a valid closest point
-    if iclosest != -1 {
-        Some(ips[iclosest as usize])
-    }
-    else {
-        None
-    }
+/// returns the intersection point of ray `r0` and normalized direction `rv` with triangle `t0-t1-t2`
+pub fn ray_vs_triangle<T: Float>(r0: Vec3<T>, rv: Vec3<T>, t0: Vec3<T>, t1: Vec3<T>, t2: Vec3<T>) -> Option<Vec3<T>> {
+    ray_vs_triangle_tuv(r0, rv, t0, t1, t2).map(|(t, _, _, _)| r0 + rv * t)
 }
 
-/// returns true if there is an intersection between ray wih origin `r0` and direction `rv` against the cylinder with line `c0-c1` and radius `cr`
-/// the intersection point is return as an `Option` if it exists.
-pub fn ray_vs_cylinder<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(r0: Vec3<T>, rv: Vec3<T>, c0: Vec3<T>, c1: Vec3<T>, cr: T) -> Option<Vec3<T>> {
-    // intesection of ray and infinite cylinder about axis
+/// returns the `(entry, exit)` ray parameters for the ray `r0` `rv` against the infinite cylinder about axis `a-b` with radius `r`
+fn ray_vs_infinite_cylinder_interval<T: Float + FloatOps<T>, V: VecN<T> + Cross<T> + VecFloatOps<T>>(r0: V, rv: V, a: V, b: V, r: T) -> Option<(T, T)> {
     // https://stackoverflow.com/questions/4078401/trying-to-optimize-line-vs-cylinder-intersection
-    let a = c0;
-    let b = c1;
-    let v = rv;
-    let r = cr;
-
     let ab = b - a;
     let ao = r0 - a;
     let aoxab = cross(ao, ab);
-    let vxab = cross(v, ab);
+    let vxab = cross(rv, ab);
     let ab2 = dot(ab, ab);
 
     let aa = dot(vxab, vxab);
     let bb = T::two() * dot(vxab, aoxab);
     let cc = dot(aoxab, aoxab) - (r*r * ab2);
-    let dd = bb * bb - T::four() * aa * cc;
-
-    if dd >= T::zero() {
-        let t = (-bb - sqrt(dd)) / (T::two() * aa);
-        if t >= T::zero() {
-            // intersection point
-            let ipc = r0 + rv * t;
-            // clamps to finite cylinder extents
-            let ipd = distance_on_line(ipc, a, b);
-            if ipd >= T::zero() && ipd <= dist(a, b) {
-                return Some(ipc);
-            }
+    if aa < T::small_epsilon() {
+        // parallel to the axis, either always inside or always outside
+        if cc <= T::zero() {
+            Some((T::min_value(), T::max_value()))
+        }
+        else {
+            None
         }
     }
+    else {
+        let dd = bb * bb - T::four() * aa * cc;
+        if dd < T::zero() {
+            None
+        }
+        else {
+            let sd = sqrt(dd);
+            Some(((-bb - sd) / (T::two() * aa), (-bb + sd) / (T::two() * aa)))
+        }
+    }
+}
 
-    // intersect with the top and bottom circles
-    let ip_top = ray_vs_plane(r0, rv, c0, normalize(c0 - c1));
-    let ip_bottom = ray_vs_plane(r0, rv, c1, normalize(c1 - c0));
-    let r2 = r*r;
-
-    let mut btop = false;
-    if let Some(ip_top) = ip_top {
-        if dist2(ip_top, c0) < r2 {
-            btop = true;
+/// returns the `(entry, exit)` ray parameters for the ray `r0` `rv` against the slab between the planes through `a` and `b` perpendicular to `a-b`
+fn ray_vs_slab_interval<T: Float, V: VecN<T>>(r0: V, rv: V, a: V, b: V) -> Option<(T, T)> {
+    let ab = b - a;
+    let d0 = dot(r0 - a, ab);
+    let dv = dot(rv, ab);
+    let ab2 = dot(ab, ab);
+    if dv == T::zero() {
+        if d0 >= T::zero() && d0 <= ab2 {
+            Some((T::min_value(), T::max_value()))
+        }
+        else {
+            None
+        }
+    }
+    else {
+        let t1 = -d0 / dv;
+        let t2 = (ab2 - d0) / dv;
+        if t1 < t2 {
+            Some((t1, t2))
+        }
+        else {
+            Some((t2, t1))
         }
     }
+}
 
-    if let Some(ip_bottom) = ip_bottom {
-        if dist2(ip_bottom, c1) < r2 {
-            if btop {
-                if let Some(ip_top) = ip_top {
-                    let d1 = distance_on_line(ip_top, r0, r0 + rv);
-                    let d2 = distance_on_line(ip_bottom, r0, r0 + rv);
-                    if d2 < d1 {
-                        return Some(ip_bottom);
-                    }
-                }
+/// returns the hit of ray wih origin `r0` and direction `rv` against the capsule with line `c0-c1` and radius `cr`, ignoring hits further than `t_max` along the ray.
+/// rays starting inside the capsule hit the back face on the way out
+pub fn ray_vs_capsule_hit<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + Cross<T> + VecFloatOps<T> + SignedNumberOps<T> + FloatOps<T>>(r0: V, rv: V, c0: V, c1: V, cr: T, t_max: T) -> Option<RayHit<T, V>> {
+    // the capsule is convex so the ray is inside over a single interval, which is the union of the intervals
+    // for the finite cylinder and the end spheres
+    let cylinder = ray_vs_infinite_cylinder_interval(r0, rv, c0, c1, cr).and_then(|(c_in, c_out)| {
+        ray_vs_slab_interval(r0, rv, c0, c1).and_then(|(s_in, s_out)| {
+            let t_in = max(c_in, s_in);
+            let t_out = min(c_out, s_out);
+            if t_in <= t_out {
+                Some((t_in, t_out))
             }
             else {
-                return Some(ip_bottom);
+                None
             }
+        })
+    });
+
+    let sphere = |s0: V| {
+        let oc = r0 - s0;
+        let a = dot(rv, rv);
+        let b = T::two() * dot(oc, rv);
+        let c = dot(oc, oc) - cr * cr;
+        let dd = b * b - T::four() * a * c;
+        if dd < T::zero() {
+            None
         }
-    }
+        else {
+            let sd = sqrt(dd);
+            Some(((-b - sd) / (T::two() * a), (-b + sd) / (T::two() * a)))
+        }
+    };
+
+    let (t_in, t_out) = [cylinder, sphere(c0), sphere(c1)].iter().flatten().fold(
+        (T::max_value(), T::min_value()), |(t_in, t_out), (a, b)| (min(t_in, *a), max(t_out, *b))
+    );
 
-    if btop {
-        if let Some(ip_top) = ip_top {
-            return Some(ip_top);
+    if t_in > t_out || t_out < T::zero() {
+        None
+    }
+    else {
+        let (t, front_face) = if t_in >= T::zero() {
+            (t_in, true)
+        }
+        else {
+            (t_out, false)
+        };
+        if t > t_max {
+            None
+        }
+        else {
+            let point = r0 + rv * t;
+            let normal = normalize(point - closest_point_on_line_segment(point, c0, c1));
+            Some(RayHit::new(t, point, normal, front_face))
         }
     }
+}
 
+/// returns the intersection point of ray wih origin `r0` and direction `rv` against the capsule with line `c0-c1` and radius `cr`.
+/// only hits in front of the ray origin are returned, so rays starting inside return the exit point
+pub fn ray_vs_capsule<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + Cross<T> + VecFloatOps<T> + SignedNumberOps<T> + FloatOps<T>>(r0: V, rv: V, c0: V, c1: V, cr: T) -> Option<V> {
+    ray_vs_capsule_hit(r0, rv, c0, c1, cr, T::max_value()).map(|hit| hit.point)
+}
+
+/// returns the hit of ray wih origin `r0` and direction `rv` against the capped cylinder with line `c0-c1` and radius `cr`, ignoring hits further than `t_max` along the ray.
+/// rays starting inside the cylinder hit the back face on the way out
+pub fn ray_vs_cylinder_hit<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(r0: Vec3<T>, rv: Vec3<T>, c0: Vec3<T>, c1: Vec3<T>, cr: T, t_max: T) -> Option<RayHit<T, Vec3<T>>> {
+    // the capped cylinder is the overlap of the infinite cylinder and the slab between the caps
+    let (c_in, c_out) = ray_vs_infinite_cylinder_interval(r0, rv, c0, c1, cr)?;
+    let (s_in, s_out) = ray_vs_slab_interval(r0, rv, c0, c1)?;
+    let t_in = max(c_in, s_in);
+    let t_out = min(c_out, s_out);
+    if t_in > t_out || t_out < T::zero() {
+        None
+    }
+    else {
+        let (t, front_face, cap) = if t_in >= T::zero() {
+            (t_in, true, s_in > c_in)
+        }
+        else {
+            (t_out, false, s_out < c_out)
+        };
+        if t > t_max {
+            None
+        }
+        else {
+            let point = r0 + rv * t;
+            let normal = if cap {
+                // the caps normal points away from the opposite cap
+                let axis = normalize(c1 - c0);
+                if dot(point - c0, axis) > dot(c1 - c0, axis) * T::point_five() {
+                    axis
+                }
+                else {
+                    -axis
+                }
+            }
+            else {
+                normalize(point - closest_point_on_line_segment(point, c0, c1))
+            };
+            Some(RayHit::new(t, point, normal, front_face))
+        }
+    }
+}
 
-    None
+/// returns the intersection point of ray wih origin `r0` and direction `rv` against the cylinder with line `c0-c1` and radius `cr`.
+/// only hits in front of the ray origin are returned, so rays starting inside return the exit point
+pub fn ray_vs_cylinder<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(r0: Vec3<T>, rv: Vec3<T>, c0: Vec3<T>, c1: Vec3<T>, cr: T) -> Option<Vec3<T>> {
+    ray_vs_cylinder_hit(r0, rv, c0, c1, cr, T::max_value()).map(|hit| hit.point)
 }
 
 /// returns true if the sphere with centre `s` and radius `r` is inside the frustum defined by 6 planes packed as vec4's `.xyz = normal, .w = plane distance`
@@ -1991,33 +2368,51 @@ pub fn map_to_range<T: Float, X: Base<T>>(v: X, in_start: X, in_end: X, out_star
     out_start + slope * (v - in_start)
 }
 
+/// returns the vertex in `vertices` furthest in direction `dir`
+fn furthest_point<T: Float, V: VecN<T>>(dir: V, vertices: &[V]) -> V {
+    let mut fd = -T::max_value();
+    let mut fv = vertices[0];
+    for v in vertices {
+        let d = dot(dir, *v);
+        if d > fd {
+            fv = *v;
+            fd = d;
+        }
+    }
+    fv
+}
+
 /// finds support vertices for gjk based on convex meshses where `convex0` and `convex1` are an array of vertices that form a convex hull
 pub fn gjk_mesh_support_function<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + SignedNumberOps<T> + FloatOps<T>> (convex0: &[V], convex1: &[V], dir: V) -> V {
-    let furthest_point = |dir: V, vertices: &[V]| -> V {
-        let mut fd = -T::max_value();
-        let mut fv = vertices[0];
-        for v in vertices {
-            let d = dot(dir, *v);
-            if d > fd {
-                fv = *v;
-                fd = d;
-            }
-        }
-        fv
-    };
+    furthest_point(dir, convex0) - furthest_point(-dir, convex1)
+}
 
-    // selects the furthest points on the 2 meshes in opposite directions
-    let fp0 = furthest_point(dir, convex0);
-    let fp1 = furthest_point(-dir, convex1);
-    fp0 - fp1
+/// vertex of the minkowski difference `a - b` with the support points `a` on `convex0` and `b` on `convex1` that produced it
+#[derive(Copy, Clone)]
+struct SupportVertex<V> {
+    v: V,
+    a: V,
+    b: V
+}
+
+/// returns the support vertex of the minkowski difference of `convex0` and `convex1` in direction `dir`
+fn gjk_support_vertex<T: Float + SignedNumberOps<T>, V: VecN<T> + SignedVecN<T>, A: Support<V> + ?Sized, B: Support<V> + ?Sized> (convex0: &A, convex1: &B, dir: V) -> SupportVertex<V> {
+    // selects the furthest points on the 2 shapes in opposite directions
+    let a = convex0.support(dir);
+    let b = convex1.support(-dir);
+    SupportVertex {
+        v: a - b,
+        a,
+        b
+    }
 }
 
 /// simplex evolution for 2d mesh overlaps using gjk
-fn handle_simplex_2d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + Triple<T>> (simplex: &mut Vec<V>, dir: &mut V) -> bool {
+fn handle_simplex_2d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + Triple<T>> (simplex: &mut Vec<SupportVertex<V>>, dir: &mut V) -> bool {
     match simplex.len() {
         2 => {
-            let a = simplex[1];
-            let b = simplex[0];
+            let a = simplex[1].v;
+            let b = simplex[0].v;
             let ab = b - a;
             let ao = -a;
 
@@ -2026,9 +2421,9 @@ fn handle_simplex_2d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + Sign
             false
         },
         3 => {
-            let a = simplex[2];
-            let b = simplex[1];
-            let c = simplex[0];
+            let a = simplex[2].v;
+            let b = simplex[1].v;
+            let c = simplex[0].v;
 
             let ab = b - a;
             let ac = c - a;
@@ -2057,47 +2452,52 @@ fn handle_simplex_2d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + Sign
     }
 }
 
-/// returns true if the 2d convex hull `convex0` overlaps with `convex1` using the gjk algorithm
-pub fn gjk_2d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + FloatOps<T> + SignedNumberOps<T> + VecFloatOps<T> + Triple<T>>(convex0: Vec<V>, convex1: Vec<V>) -> bool {
+/// returns the final triangle simplex enclosing the origin if the 2d convex hull `convex0` overlaps with `convex1`
+fn gjk_2d_simplex<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + FloatOps<T> + SignedNumberOps<T> + VecFloatOps<T> + Triple<T>, A: Support<V> + ?Sized, B: Support<V> + ?Sized>(convex0: &A, convex1: &B) -> Option<Vec<SupportVertex<V>>> {
     // implemented following details in this insightful video: https://www.youtube.com/watch?v=ajv46BSqcK4
 
     // start with arbitrary direction
     let mut dir = V::unit_x();
-    let support = gjk_mesh_support_function(&convex0, &convex1, dir);
-    dir = normalize(-support);
+    let support = gjk_support_vertex(convex0, convex1, dir);
+    dir = normalize(-support.v);
 
     // iterative build and test simplex
     let mut simplex = vec![support];
 
     let max_iters = 32;
     for _i in 0..max_iters {
-        let a = gjk_mesh_support_function(&convex0, &convex1, dir);
+        let a = gjk_support_vertex(convex0, convex1, dir);
 
-        if dot(a, dir) < T::zero() {
-            return false;
+        if dot(a.v, dir) < T::zero() {
+            return None;
         }
         simplex.push(a);
 
         if handle_simplex_2d(&mut simplex, &mut dir) {
-            return true;
+            return Some(simplex);
         }
     }
 
     // if we reach here we likely have got stuck in a simplex building loop, we assume the shapes are touching but not intersecting
-    false
+    None
+}
+
+/// returns true if the 2d convex hull `convex0` overlaps with `convex1` using the gjk algorithm
+pub fn gjk_2d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + FloatOps<T> + SignedNumberOps<T> + VecFloatOps<T> + Triple<T>, A: Support<V> + ?Sized, B: Support<V> + ?Sized>(convex0: &A, convex1: &B) -> bool {
+    gjk_2d_simplex(convex0, convex1).is_some()
 }
 
 /// returns true if the convex hull `convex0` overlaps `convex1` where convex hull is an array of vertices forming a 2D convex polygon
-pub fn convex_hull_vs_convex_hull<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + FloatOps<T> + SignedNumberOps<T> + VecFloatOps<T> + Triple<T>>(convex0: Vec<V>, convex1: Vec<V>) -> bool {
+pub fn convex_hull_vs_convex_hull<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + FloatOps<T> + SignedNumberOps<T> + VecFloatOps<T> + Triple<T>, A: Support<V> + ?Sized, B: Support<V> + ?Sized>(convex0: &A, convex1: &B) -> bool {
     gjk_2d(convex0, convex1)
 }
 
 /// simplex evolution for 3d mesh overlaps
-fn handle_simplex_3d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + Triple<T> + Cross<T>> (simplex: &mut Vec<V>, dir: &mut V) -> bool {
+fn handle_simplex_3d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + Triple<T> + Cross<T>> (simplex: &mut Vec<SupportVertex<V>>, dir: &mut V) -> bool {
     match simplex.len() {
         2 => {
-            let a = simplex[1];
-            let b = simplex[0];
+            let a = simplex[1].v;
+            let b = simplex[0].v;
 
             let ab = b - a;
             let ao = -a;
@@ -2107,9 +2507,9 @@ fn handle_simplex_3d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + Sign
             false
         },
         3 => {
-            let a = simplex[2];
-            let b = simplex[1];
-            let c = simplex[0];
+            let a = simplex[2].v;
+            let b = simplex[1].v;
+            let c = simplex[0].v;
 
             let ab = b - a;
             let ac = c - a;
@@ -2125,10 +2525,10 @@ fn handle_simplex_3d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + Sign
             false
         },
         4 => {
-            let a = simplex[3];
-            let b = simplex[2];
-            let c = simplex[1];
-            let d = simplex[0];
+            let a = simplex[3].v;
+            let b = simplex[2].v;
+            let c = simplex[1].v;
+            let d = simplex[0].v;
 
             let centre = (a+b+c+d) / T::four();
 
@@ -2186,34 +2586,665 @@ fn handle_simplex_3d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + Sign
     }
 }
 
-/// returns true if the 3D convex hull `convex0` overlaps with `convex1` using the gjk algorithm
-pub fn gjk_3d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + FloatOps<T> + SignedNumberOps<T> + VecFloatOps<T> + Triple<T> + Cross<T>>(convex0: Vec<V>, convex1: Vec<V>) -> bool {
+/// returns the final tetrahedron simplex enclosing the origin if the 3D convex hull `convex0` overlaps with `convex1`
+fn gjk_3d_simplex<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + FloatOps<T> + SignedNumberOps<T> + VecFloatOps<T> + Triple<T> + Cross<T>, A: Support<V> + ?Sized, B: Support<V> + ?Sized>(convex0: &A, convex1: &B) -> Option<Vec<SupportVertex<V>>> {
     // implemented following details in this insightful video: https://www.youtube.com/watch?v=ajv46BSqcK4
 
     // start with arbitrary direction
     let mut dir = V::unit_x();
-    let support = gjk_mesh_support_function(&convex0, &convex1, dir);
-    dir = normalize(-support);
+    let support = gjk_support_vertex(convex0, convex1, dir);
+    dir = normalize(-support.v);
 
     // iterative build and test simplex
     let mut simplex = vec![support];
 
     let max_iters = 32;
     for _i in 0..max_iters {
-        let a = gjk_mesh_support_function(&convex0, &convex1, dir);
+        let a = gjk_support_vertex(convex0, convex1, dir);
 
-        if dot(a, dir) < T::zero() {
-            return false;
+        if dot(a.v, dir) < T::zero() {
+            return None;
         }
         simplex.push(a);
 
         if handle_simplex_3d(&mut simplex, &mut dir) {
-            return true;
+            return Some(simplex);
         }
     }
 
     // if we reach here we likely have got stuck in a simplex building loop, we assume the shapes are touching but not intersecting
-    false
+    None
+}
+
+/// returns true if the 3D convex hull `convex0` overlaps with `convex1` using the gjk algorithm
+pub fn gjk_3d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumber + SignedNumberOps<T>, V: VecN<T> + FloatOps<T> + SignedNumberOps<T> + VecFloatOps<T> + Triple<T> + Cross<T>, A: Support<V> + ?Sized, B: Support<V> + ?Sized>(convex0: &A, convex1: &B) -> bool {
+    gjk_3d_simplex(convex0, convex1).is_some()
+}
+
+/// returns the barycentric weights of the point closest to the origin on the affine hull of `points`, or `None` if the points are affinely dependent
+fn affine_closest_weights<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T>>(points: &[V]) -> Option<Vec<T>> {
+    // solve the normal equations for the offsets from the first point, the gram matrix is symmetric positive definite when independent
+    let p0 = points[0];
+    let e: Vec<V> = points.iter().skip(1).map(|p| *p - p0).collect();
+    let mu = match e.len() {
+        0 => vec![],
+        1 => {
+            let g = dot(e[0], e[0]);
+            if g <= T::small_epsilon() {
+                return None;
+            }
+            vec![-dot(e[0], p0) / g]
+        }
+        2 => {
+            let g = Mat2::new(
+                dot(e[0], e[0]), dot(e[0], e[1]),
+                dot(e[1], e[0]), dot(e[1], e[1])
+            );
+            let x = g.solve(Vec2::new(-dot(e[0], p0), -dot(e[1], p0)))?;
+            vec![x.x, x.y]
+        }
+        3 => {
+            let g = Mat3::new(
+                dot(e[0], e[0]), dot(e[0], e[1]), dot(e[0], e[2]),
+                dot(e[1], e[0]), dot(e[1], e[1]), dot(e[1], e[2]),
+                dot(e[2], e[0]), dot(e[2], e[1]), dot(e[2], e[2])
+            );
+            let x = g.solve(Vec3::new(-dot(e[0], p0), -dot(e[1], p0), -dot(e[2], p0)))?;
+            vec![x.x, x.y, x.z]
+        }
+        _ => return None
+    };
+    let w0 = mu.iter().fold(T::one(), |w, m| w - *m);
+    Some(std::iter::once(w0).chain(mu).collect())
+}
+
+/// johnson's distance sub-algorithm, reduces `simplex` to the smallest sub simplex containing the point closest to the origin and returns its barycentric weights
+fn gjk_closest_on_simplex<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T>>(simplex: &mut Vec<SupportVertex<V>>) -> Vec<T> {
+    // the closest point lies inside exactly one sub simplex, test each one where the projection has all positive weights and keep the nearest
+    let n = simplex.len();
+    let mut best: Option<(T, usize, Vec<T>)> = None;
+    for mask in 1..(1usize << n) {
+        let sub: Vec<V> = (0..n).filter(|i| mask & (1 << i) != 0).map(|i| simplex[i].v).collect();
+        if let Some(w) = affine_closest_weights(&sub) {
+            if sub.len() == 1 || w.iter().all(|w| *w > T::zero()) {
+                let x = sub.iter().zip(&w).fold(V::zero(), |x, (v, w)| x + *v * *w);
+                let d2 = dot(x, x);
+                if best.as_ref().is_none_or(|b| d2 < b.0) {
+                    best = Some((d2, mask, w));
+                }
+            }
+        }
+    }
+
+    // a single point always has a valid projection
+    let (_, mask, w) = best.unwrap();
+    let mut i = 0;
+    simplex.retain(|_| {
+        i += 1;
+        mask & (1 << (i - 1)) != 0
+    });
+    w
+}
+
+/// returns `(distance, point_on_a, point_on_b)` the separation distance and closest points between the convex hulls `convex0` and `convex1`
+/// in 2D or 3D using gjk with johnson's distance sub-algorithm, or `None` if the hulls overlap where `epa_2d` or `epa_3d` give the penetration
+pub fn gjk_distance<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + SignedNumberOps<T> + FloatOps<T>, A: Support<V> + ?Sized, B: Support<V> + ?Sized>(convex0: &A, convex1: &B) -> Option<(T, V, V)> {
+    let mut simplex = vec![gjk_support_vertex(convex0, convex1, V::unit_x())];
+    let mut w = vec![T::one()];
+
+    let max_iters = 64;
+    for _i in 0..max_iters {
+        // point on the current simplex closest to the origin
+        let x = simplex.iter().zip(&w).fold(V::zero(), |x, (s, w)| x + s.v * *w);
+        let d2 = dot(x, x);
+
+        // the origin is inside the minkowski difference within precision so the hulls overlap
+        let scale = simplex.iter().fold(T::zero(), |m, s| max(m, dot(s.v, s.v)));
+        if d2 <= T::epsilon() * scale {
+            return None;
+        }
+
+        // search toward the origin, stopping when the new support point gets no closer
+        let support = gjk_support_vertex(convex0, convex1, -x);
+        if d2 - dot(x, support.v) <= T::sqrt(T::epsilon()) * d2 || simplex.iter().any(|s| dist2(s.v, support.v) == T::zero()) {
+            break;
+        }
+
+        simplex.push(support);
+        w = gjk_closest_on_simplex(&mut simplex);
+    }
+
+    let a = simplex.iter().zip(&w).fold(V::zero(), |p, (s, w)| p + s.a * *w);
+    let b = simplex.iter().zip(&w).fold(V::zero(), |p, (s, w)| p + s.b * *w);
+    Some((dist(a, b), a, b))
+}
+
+/// maximum number of times the expanding polytope is expanded, curved shapes such as spheres converge slowly and stop here
+const EPA_MAX_ITERS: usize = 256;
+
+/// returns the tolerance used to decide the expanding polytope has reached the boundary at distance `d`
+fn epa_tolerance<T: Float + FloatOps<T>>(d: T) -> T {
+    T::sqrt(T::epsilon()) * (T::one() + d)
+}
+
+/// returns the vertex furthest from the current points by `dist` with more than `tol` distance, taken from `simplex` first and otherwise
+/// from the supports along `dirs`
+fn epa_furthest<T: Float + SignedNumberOps<T>, V: VecN<T> + SignedVecN<T>, A: Support<V> + ?Sized, B: Support<V> + ?Sized, D: Fn(V) -> T>(convex0: &A, convex1: &B, simplex: &[SupportVertex<V>], dirs: &[V], tol: T, dist: D) -> Option<SupportVertex<V>> {
+    let pick = |candidates: &mut dyn Iterator<Item = SupportVertex<V>>| {
+        let mut best: Option<(T, SupportVertex<V>)> = None;
+        for s in candidates {
+            let d = dist(s.v);
+            if d > best.map_or(tol, |b| b.0) {
+                best = Some((d, s));
+            }
+        }
+        best.map(|(_, s)| s)
+    };
+    pick(&mut simplex.iter().copied())
+        .or_else(|| pick(&mut dirs.iter().map(|d| gjk_support_vertex(convex0, convex1, *d))))
+}
+
+/// returns up to 3 affinely independent support vertices spanning the gjk `simplex`. gjk can finish with a flat simplex, ie. when the
+/// shapes overlap along its first search axis, so a missing dimension is filled with support points perpendicular to the segment.
+/// fewer than 3 vertices are returned only when the minkowski difference itself is flat
+fn epa_triangle<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, A: Support<Vec2<T>> + ?Sized, B: Support<Vec2<T>> + ?Sized>(convex0: &A, convex1: &B, simplex: &[SupportVertex<Vec2<T>>]) -> Vec<SupportVertex<Vec2<T>>> {
+    let scale = simplex.iter().fold(T::one(), |m, s| max(m, mag(s.v)));
+    let tol = T::sqrt(T::epsilon()) * scale;
+
+    let p0 = simplex[0];
+    let axes = [Vec2::unit_x(), -Vec2::unit_x(), Vec2::unit_y(), -Vec2::unit_y()];
+    let p1 = match epa_furthest(convex0, convex1, simplex, &axes, tol, |v| dist(v, p0.v)) {
+        Some(p1) => p1,
+        None => return vec![p0]
+    };
+
+    let n = normalize(perp(p1.v - p0.v));
+    match epa_furthest(convex0, convex1, simplex, &[n, -n], tol, |v| abs(dot(v - p0.v, n))) {
+        Some(p2) => vec![p0, p1, p2],
+        None => vec![p0, p1]
+    }
+}
+
+/// returns the penetration of the 2D convex hull `convex0` into `convex1` using gjk followed by the expanding polytope algorithm, or `None` if they do not overlap
+pub fn epa_2d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, A: Support<Vec2<T>> + ?Sized, B: Support<Vec2<T>> + ?Sized>(convex0: &A, convex1: &B) -> Option<Penetration<T, Vec2<T>>> {
+    let simplex = gjk_2d_simplex(convex0, convex1)?;
+    let mut polytope = epa_triangle(convex0, convex1, &simplex);
+    if polytope.len() < 3 {
+        // the minkowski difference is a line so the shapes only touch
+        let p0 = polytope[0];
+        let normal = if polytope.len() == 2 { normalize(perp(polytope[1].v - p0.v)) } else { Vec2::unit_x() };
+        return Some(Penetration {
+            normal,
+            depth: T::zero(),
+            point_a: p0.a,
+            point_b: p0.a
+        });
+    }
+
+    // wind the triangle counter clockwise so edge normals (e.y, -e.x) face outward
+    let (a, b, c) = (polytope[0].v, polytope[1].v, polytope[2].v);
+    if dot(perp(b - a), c - a) < T::zero() {
+        polytope.swap(1, 2);
+    }
+
+    // edge of the polytope closest to the origin as `(index, distance, normal)`, degenerate edges are skipped
+    let closest_edge = |polytope: &Vec<SupportVertex<Vec2<T>>>| {
+        let mut closest = (0, T::max_value(), Vec2::zero());
+        for i in 0..polytope.len() {
+            let a = polytope[i].v;
+            let b = polytope[(i + 1) % polytope.len()].v;
+            let e = b - a;
+            if mag2(e) > T::small_epsilon() {
+                let n = normalize(Vec2::new(e.y, -e.x));
+                let d = dot(n, a);
+                if d < closest.1 {
+                    closest = (i, d, n);
+                }
+            }
+        }
+        closest
+    };
+
+    // the closest edge is found again after every expansion so it always refers to the current polytope
+    let mut closest = closest_edge(&polytope);
+    for _i in 0..EPA_MAX_ITERS {
+        // expand toward the closest edge until the boundary of the minkowski difference is reached
+        let (i, d, n) = closest;
+        let support = gjk_support_vertex(convex0, convex1, n);
+        if dot(support.v, n) - d < epa_tolerance(d) {
+            break;
+        }
+        polytope.insert(i + 1, support);
+        closest = closest_edge(&polytope);
+    }
+
+    // interpolate the support points on the closest edge at the projection of the origin
+    let (i, depth, normal) = closest;
+    let a = polytope[i];
+    let b = polytope[(i + 1) % polytope.len()];
+    let e = b.v - a.v;
+    let t = saturate(dot(normal * depth - a.v, e) / T::max(mag2(e), T::small_epsilon()));
+    Some(Penetration {
+        normal,
+        depth,
+        point_a: lerp(a.a, b.a, t),
+        point_b: lerp(a.b, b.b, t)
+    })
+}
+
+/// returns up to 4 affinely independent support vertices spanning the gjk `simplex`. gjk can finish with a flat simplex, ie. when the
+/// shapes overlap along its first search axis, so missing dimensions are filled with support points perpendicular to the segment or
+/// triangle. fewer than 4 vertices are returned only when the minkowski difference itself is flat
+fn epa_tetrahedron<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, A: Support<Vec3<T>> + ?Sized, B: Support<Vec3<T>> + ?Sized>(convex0: &A, convex1: &B, simplex: &[SupportVertex<Vec3<T>>]) -> Vec<SupportVertex<Vec3<T>>> {
+    let scale = simplex.iter().fold(T::one(), |m, s| max(m, mag(s.v)));
+    let tol = T::sqrt(T::epsilon()) * scale;
+
+    let p0 = simplex[0];
+    let axes = [Vec3::unit_x(), -Vec3::unit_x(), Vec3::unit_y(), -Vec3::unit_y(), Vec3::unit_z(), -Vec3::unit_z()];
+    let p1 = match epa_furthest(convex0, convex1, simplex, &axes, tol, |v| dist(v, p0.v)) {
+        Some(p1) => p1,
+        None => return vec![p0]
+    };
+
+    // directions perpendicular to the segment
+    let d = normalize(p1.v - p0.v);
+    let (u, w) = get_orthonormal_basis_hughes_moeller(d);
+    let line_dist = |v: Vec3<T>| mag(cross(v - p0.v, d));
+    let p2 = match epa_furthest(convex0, convex1, simplex, &[u, -u, w, -w], tol, line_dist) {
+        Some(p2) => p2,
+        None => return vec![p0, p1]
+    };
+
+    // apex either side of the triangle
+    let n = normalize(cross(p1.v - p0.v, p2.v - p0.v));
+    let plane_dist = |v: Vec3<T>| abs(dot(v - p0.v, n));
+    match epa_furthest(convex0, convex1, simplex, &[n, -n], tol, plane_dist) {
+        Some(p3) => vec![p0, p1, p2, p3],
+        None => vec![p0, p1, p2]
+    }
+}
+
+/// returns the penetration for a flat minkowski difference spanned by `points`, the shapes only touch so the depth is zero and the
+/// normal is perpendicular to the flat difference
+fn epa_flat_penetration<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(points: &[SupportVertex<Vec3<T>>]) -> Penetration<T, Vec3<T>> {
+    let p0 = points[0];
+    let normal = match points.len() {
+        3 => normalize(cross(points[1].v - p0.v, points[2].v - p0.v)),
+        2 => get_orthonormal_basis_hughes_moeller(normalize(points[1].v - p0.v)).0,
+        _ => Vec3::unit_x()
+    };
+    Penetration {
+        normal,
+        depth: T::zero(),
+        point_a: p0.a,
+        point_b: p0.a
+    }
+}
+
+/// returns the penetration of the 3D convex hull `convex0` into `convex1` using gjk followed by the expanding polytope algorithm, or `None` if they do not overlap
+pub fn epa_3d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, A: Support<Vec3<T>> + ?Sized, B: Support<Vec3<T>> + ?Sized>(convex0: &A, convex1: &B) -> Option<Penetration<T, Vec3<T>>> {
+    let simplex = gjk_3d_simplex(convex0, convex1)?;
+    let mut polytope = epa_tetrahedron(convex0, convex1, &simplex);
+    if polytope.len() < 4 {
+        return Some(epa_flat_penetration(&polytope));
+    }
+
+    // outward facing normal and distance from the origin of face `f`, degenerate faces are pushed to max distance
+    let centre = polytope.iter().fold(Vec3::zero(), |c, p| c + p.v) / T::four();
+    let face_plane = |polytope: &Vec<SupportVertex<Vec3<T>>>, f: [usize; 3]| -> (Vec3<T>, T) {
+        let (a, b, c) = (polytope[f[0]].v, polytope[f[1]].v, polytope[f[2]].v);
+        let n = cross(b - a, c - a);
+        let l = mag(n);
+        if l > T::small_epsilon() {
+            (n / l, dot(n / l, a))
+        }
+        else {
+            (Vec3::zero(), T::max_value())
+        }
+    };
+
+    // tetrahedron faces wound so the normals face away from the centre
+    let mut faces = Vec::new();
+    for f in [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]] {
+        let (a, b, c) = (polytope[f[0]].v, polytope[f[1]].v, polytope[f[2]].v);
+        if dot(cross(b - a, c - a), a - centre) < T::zero() {
+            faces.push([f[0], f[2], f[1]]);
+        }
+        else {
+            faces.push(f);
+        }
+    }
+
+    // face of the polytope closest to the origin as `(index, distance, normal)`
+    let closest_face = |polytope: &Vec<SupportVertex<Vec3<T>>>, faces: &Vec<[usize; 3]>| {
+        let mut closest = (0, T::max_value(), Vec3::zero());
+        for (i, f) in faces.iter().enumerate() {
+            let (n, d) = face_plane(polytope, *f);
+            if d < closest.1 {
+                closest = (i, d, n);
+            }
+        }
+        closest
+    };
+
+    // the closest face is found again after every expansion so it always refers to the current faces
+    let mut closest = closest_face(&polytope, &faces);
+    for _i in 0..EPA_MAX_ITERS {
+        // expand toward the closest face until the boundary of the minkowski difference is reached
+        let (_, d, n) = closest;
+        let support = gjk_support_vertex(convex0, convex1, n);
+        if dot(support.v, n) - d < epa_tolerance(d) {
+            break;
+        }
+
+        // find the faces the new point can see and keep the edges on the horizon, edges shared by 2 visible faces cancel out
+        let visible: Vec<bool> = faces.iter().map(|f| {
+            let (face_n, face_d) = face_plane(&polytope, *f);
+            face_d != T::max_value() && dot(face_n, support.v) - face_d > T::zero()
+        }).collect();
+        let mut horizon: Vec<(usize, usize)> = Vec::new();
+        for f in faces.iter().zip(&visible).filter(|(_, v)| **v).map(|(f, _)| f) {
+            for (e0, e1) in [(f[0], f[1]), (f[1], f[2]), (f[2], f[0])] {
+                if let Some(pos) = horizon.iter().position(|e| *e == (e1, e0)) {
+                    horizon.remove(pos);
+                }
+                else {
+                    horizon.push((e0, e1));
+                }
+            }
+        }
+
+        if horizon.is_empty() {
+            break;
+        }
+
+        // remove the visible faces and stitch the horizon to the new point
+        let mut keep = visible.iter().map(|v| !*v);
+        faces.retain(|_| keep.next().unwrap());
+        polytope.push(support);
+        let p = polytope.len() - 1;
+        for (e0, e1) in horizon {
+            faces.push([e0, e1, p]);
+        }
+        closest = closest_face(&polytope, &faces);
+    }
+
+    // interpolate the support points on the closest face at the projection of the origin
+    let (i, depth, normal) = closest;
+    let f = faces[i];
+    let (a, b, c) = (polytope[f[0]], polytope[f[1]], polytope[f[2]]);
+    let (u, v, w) = barycentric(normal * depth, a.v, b.v, c.v);
+    Some(Penetration {
+        normal,
+        depth,
+        point_a: a.a * u + b.a * v + c.a * w,
+        point_b: a.b * u + b.b * v + c.b * w
+    })
+}
+
+/// returns the unit normal from `d`, or `fallback` if `d` has no length
+fn contact_normal<T: Float + FloatOps<T>, V: VecN<T> + VecFloatOps<T>>(d: V, fallback: V) -> V {
+    let l2 = dot(d, d);
+    if l2 > T::small_epsilon() {
+        d / sqrt(l2)
+    }
+    else {
+        fallback
+    }
+}
+
+/// converts the hit of a sphere centre against an obstacle inflated by radius `r` into a time of impact on the obstacle surface
+fn sphere_hit_to_toi<T: Float, V: VecN<T>>(hit: RayHit<T, V>, r: T) -> TimeOfImpact<T, V> {
+    TimeOfImpact {
+        t: hit.t,
+        point: hit.point - hit.normal * r,
+        normal: hit.normal
+    }
+}
+
+/// returns the first time of impact of the sphere (or circle) centred at `s0` with radius `r` moving by `v` over the step, against the plane
+/// through `x` with normal `n`. the sphere can approach from either side and spheres which start touching the plane return `t = 0`
+pub fn sweep_sphere_vs_plane<T: Float + FloatOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + SignedVecN<T>>(s0: V, r: T, v: V, x: V, n: V) -> Option<TimeOfImpact<T, V>> {
+    let d = point_plane_distance(s0, x, n);
+    let normal = if d < T::zero() { -n } else { n };
+    let gap = T::abs(d) - r;
+    let speed = -dot(v, normal);
+    let t = if gap <= T::zero() {
+        T::zero()
+    }
+    else if speed > T::zero() && gap <= speed {
+        gap / speed
+    }
+    else {
+        return None;
+    };
+    Some(TimeOfImpact {
+        t,
+        point: closest_point_on_plane(s0 + v * t, x, n),
+        normal
+    })
+}
+
+/// returns the first time of impact of the sphere centred at `s0` with radius `r` moving by `v` over the step, against the triangle `t0-t1-t2`.
+/// either side of the triangle can be hit and spheres which start touching the triangle return `t = 0`
+pub fn sweep_sphere_vs_triangle<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(s0: Vec3<T>, r: T, v: Vec3<T>, t0: Vec3<T>, t1: Vec3<T>, t2: Vec3<T>) -> Option<TimeOfImpact<T, Vec3<T>>> {
+    let edges = [(t0, t1), (t1, t2), (t2, t0)];
+    let n = get_triangle_normal(t0, t1, t2);
+
+    // closest point on the face if the centre projects inside the triangle, otherwise on the nearest edge
+    let fp = closest_point_on_plane(s0, t0, n);
+    let cp = if point_inside_triangle(fp, t0, t1, t2) {
+        fp
+    }
+    else {
+        edges.iter().map(|(e0, e1)| closest_point_on_line_segment(s0, *e0, *e1)).fold(t0, |cp, p| {
+            if dist2(s0, p) < dist2(s0, cp) { p } else { cp }
+        })
+    };
+    if dist(s0, cp) <= r {
+        let side = if point_plane_distance(s0, t0, n) < T::zero() { -n } else { n };
+        return Some(TimeOfImpact {
+            t: T::zero(),
+            point: cp,
+            normal: contact_normal(s0 - cp, side)
+        });
+    }
+
+    // the face is hit first if the sphere touches the plane inside the triangle, otherwise the first edge or vertex hit
+    if let Some(toi) = sweep_sphere_vs_plane(s0, r, v, t0, n) {
+        if point_inside_triangle(toi.point, t0, t1, t2) {
+            return Some(toi);
+        }
+    }
+    edges.iter()
+        .filter_map(|(e0, e1)| ray_vs_capsule_hit(s0, v, *e0, *e1, r, T::one()))
+        .fold(None, |first: Option<RayHit<T, Vec3<T>>>, hit| {
+            if first.is_none_or(|f| hit.t < f.t) { Some(hit) } else { first }
+        })
+        .map(|hit| sphere_hit_to_toi(hit, r))
+}
+
+/// returns the first time of impact of the sphere centred at `s0` with radius `r` moving by `v` over the step, against the aabb defined by
+/// `aabb_min` and `aabb_max`. spheres which start touching the aabb return `t = 0`
+pub fn sweep_sphere_vs_aabb<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(s0: Vec3<T>, r: T, v: Vec3<T>, aabb_min: Vec3<T>, aabb_max: Vec3<T>) -> Option<TimeOfImpact<T, Vec3<T>>> {
+    let cp = closest_point_on_aabb(s0, aabb_min, aabb_max);
+    if dist(s0, cp) <= r {
+        // a centre inside the aabb pushes out through the nearest face
+        let mut face = Vec3::zero();
+        let mut nearest = T::max_value();
+        for i in 0..3 {
+            for (d, sign) in [(s0[i] - aabb_min[i], T::minus_one()), (aabb_max[i] - s0[i], T::one())] {
+                if d < nearest {
+                    nearest = d;
+                    face = Vec3::zero();
+                    face[i] = sign;
+                }
+            }
+        }
+        return Some(TimeOfImpact {
+            t: T::zero(),
+            point: cp,
+            normal: contact_normal(s0 - cp, face)
+        });
+    }
+
+    // the aabb inflated by the sphere is the union of the aabb grown along each axis and capsules around the 12 edges
+    let mut first: Option<RayHit<T, Vec3<T>>> = None;
+    let mut keep = |hit: Option<RayHit<T, Vec3<T>>>| {
+        if let Some(hit) = hit {
+            if first.is_none_or(|f| hit.t < f.t) {
+                first = Some(hit);
+            }
+        }
+    };
+    for i in 0..3 {
+        let mut grow = Vec3::zero();
+        grow[i] = r;
+        keep(ray_vs_aabb_hit(s0, v, aabb_min - grow, aabb_max + grow, T::one()));
+
+        let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+        for (a, b) in [(aabb_min[j], aabb_min[k]), (aabb_max[j], aabb_min[k]), (aabb_min[j], aabb_max[k]), (aabb_max[j], aabb_max[k])] {
+            let mut e0 = aabb_min;
+            e0[j] = a;
+            e0[k] = b;
+            let mut e1 = e0;
+            e1[i] = aabb_max[i];
+            keep(ray_vs_capsule_hit(s0, v, e0, e1, r, T::one()));
+        }
+    }
+    first.map(|hit| sphere_hit_to_toi(hit, r))
+}
+
+/// returns the first time of impact of the sphere centred at `s0` with radius `r` moving by `v` over the step, against the capsule with line
+/// `c0-c1` and radius `cr`. spheres which start touching the capsule return `t = 0`
+pub fn sweep_sphere_vs_capsule<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + Cross<T> + VecFloatOps<T> + SignedNumberOps<T> + FloatOps<T>>(s0: V, r: T, v: V, c0: V, c1: V, cr: T) -> Option<TimeOfImpact<T, V>> {
+    let cp = closest_point_on_line_segment(s0, c0, c1);
+    if dist(s0, cp) <= r + cr {
+        let normal = contact_normal(s0 - cp, -contact_normal(v, V::unit_x()));
+        return Some(TimeOfImpact {
+            t: T::zero(),
+            point: cp + normal * cr,
+            normal
+        });
+    }
+    ray_vs_capsule_hit(s0, v, c0, c1, r + cr, T::one()).map(|hit| sphere_hit_to_toi(hit, r))
+}
+
+/// returns the first time of impact of the aabb `min0-max0` moving by `v0` over the step against the aabb `min1-max1` moving by `v1`,
+/// the normal is on the face of the second aabb. aabbs which start overlapping return `t = 0` with the normal of the shallowest axis
+pub fn sweep_aabb_vs_aabb<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + NumberOps<T>>(min0: V, max0: V, v0: V, min1: V, max1: V, v1: V) -> Option<TimeOfImpact<T, V>> {
+    // sweep the first aabb relative to the second, finding the interval where every axis overlaps
+    let v = v0 - v1;
+    let mut t_enter = T::zero();
+    let mut t_exit = T::one();
+    let mut axis = None;
+    for i in 0..V::len() {
+        if v[i] == T::zero() {
+            if max0[i] < min1[i] || min0[i] > max1[i] {
+                return None;
+            }
+            continue;
+        }
+        let (t0, t1) = if v[i] > T::zero() {
+            ((min1[i] - max0[i]) / v[i], (max1[i] - min0[i]) / v[i])
+        }
+        else {
+            ((max1[i] - min0[i]) / v[i], (min1[i] - max0[i]) / v[i])
+        };
+        if t0 > t_enter {
+            t_enter = t0;
+            axis = Some(i);
+        }
+        t_exit = min(t_exit, t1);
+        if t_enter > t_exit {
+            return None;
+        }
+    }
+
+    let mut normal = V::zero();
+    match axis {
+        Some(i) => {
+            normal[i] = if v[i] > T::zero() { T::minus_one() } else { T::one() };
+        }
+        None => {
+            // overlapping at the start, push out along the axis of least overlap
+            let mut shallowest = T::max_value();
+            for i in 0..V::len() {
+                let overlap = min(max0[i], max1[i]) - max(min0[i], min1[i]);
+                if overlap < shallowest {
+                    shallowest = overlap;
+                    normal = V::zero();
+                    normal[i] = if min0[i] + max0[i] < min1[i] + max1[i] { T::minus_one() } else { T::one() };
+                }
+            }
+        }
+    }
+
+    // contact point is the centre of the region where the moved aabbs touch
+    let lo = max(min0 + v0 * t_enter, min1 + v1 * t_enter);
+    let hi = min(max0 + v0 * t_enter, max1 + v1 * t_enter);
+    Some(TimeOfImpact {
+        t: t_enter,
+        point: (lo + hi) * T::point_five(),
+        normal
+    })
+}
+
+/// convex shape moved by `offset`, used to advance shapes through time for conservative advancement
+struct Translated<'a, S: ?Sized, V> {
+    shape: &'a S,
+    offset: V
+}
+
+impl<S, V> Support<V> for Translated<'_, S, V> where S: Support<V> + ?Sized, V: std::ops::Add<Output=V> + Copy {
+    fn support(&self, dir: V) -> V {
+        self.shape.support(dir) + self.offset
+    }
+}
+
+/// returns the first time of impact of the convex shape `convex0` moving by `v0` over the step against `convex1` moving by `v1`, in 2D or 3D.
+/// uses conservative advancement with `gjk_distance` so any shapes implementing `Support` can be swept, the normal is on `convex1` facing `convex0`.
+/// shapes which overlap at the start return `t = 0` with the normal opposing the relative motion
+pub fn conservative_advancement<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + SignedNumberOps<T> + FloatOps<T>, A: Support<V> + ?Sized, B: Support<V> + ?Sized>(convex0: &A, v0: V, convex1: &B, v1: V) -> Option<TimeOfImpact<T, V>> {
+    let v = v0 - v1;
+    let mut normal = contact_normal(-v, V::unit_x());
+    let mut point = None;
+    let mut tol = T::zero();
+    let mut t = T::zero();
+
+    let max_iters = 32;
+    for i in 0..max_iters {
+        let a = Translated { shape: convex0, offset: v0 * t };
+        let b = Translated { shape: convex1, offset: v1 * t };
+        let (d, pa, pb) = match gjk_distance(&a, &b) {
+            Some(closest) => closest,
+            None => {
+                // already touching within the precision of gjk
+                let point = point.unwrap_or_else(|| (a.support(-normal) + b.support(normal)) * T::point_five());
+                return Some(TimeOfImpact { t, point, normal });
+            }
+        };
+        if i == 0 {
+            tol = sqrt(T::epsilon()) * (T::one() + d);
+        }
+        normal = (pa - pb) / d;
+        point = Some(pb);
+        if d <= tol {
+            return Some(TimeOfImpact { t, point: pb, normal });
+        }
+
+        // the distance is convex in time under translation so stepping by the closing speed along the normal never passes the first contact
+        let speed = -dot(v, normal);
+        if speed <= T::zero() {
+            return None;
+        }
+        t += (d - tol * T::point_five()) / speed;
+        if t > T::one() {
+            return None;
+        }
+    }
+
+    point.map(|point| TimeOfImpact { t, point, normal })
 }
 
 /// export float types and all functions for qick and easy integration
@@ -2225,12 +3256,24 @@ pub mod prelude {
         mat::*,
         num::*,
         quat::*,
+        dual_quat::*,
+        transform::*,
+        euler::*,
+        matrix::*,
+        primitives::*,
+        sat::*,
+        bvh::*,
+        dynamic_tree::*,
         swizz::*,
+        half::*,
+        fixed::*,
 
         // types
         Vec2f, Vec3f, Vec4f,
+        BVec2, BVec3, BVec4,
         Mat2f, Mat3f, Mat34f, Mat4f,
-        Quatf,
+        Quatf, DualQuatf, Transformf,
+        Vectorf, Matrixf,
 
         // functions in lib.rs
         *,
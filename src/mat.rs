@@ -2,6 +2,7 @@
 use crate::vec::*;
 use crate::num::*;
 use crate::quat::*;
+use crate::matrix::*;
 
 use std::ops::Index;
 use std::ops::IndexMut;
@@ -15,27 +16,27 @@ use std::cmp::PartialEq;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
-/// row major matrix index layouts
-///
-/// Mat2:
-/// 00 01
-/// 02 03
-///
-/// Mat3:
-/// 00 01 02
-/// 03 04 05
-/// 06 07 08
-///
-/// Mat34:
-/// 00 01 02 03
-/// 04 05 06 07
-/// 08 09 10 11
-///
-/// Mat44:
-/// 00 01 02 03
-/// 04 05 06 07
-/// 08 09 10 11
-/// 12 13 14 15
+// row major matrix index layouts
+//
+// Mat2:
+// 00 01
+// 02 03
+//
+// Mat3:
+// 00 01 02
+// 03 04 05
+// 06 07 08
+//
+// Mat34:
+// 00 01 02 03
+// 04 05 06 07
+// 08 09 10 11
+//
+// Mat44:
+// 00 01 02 03
+// 04 05 06 07
+// 08 09 10 11
+// 12 13 14 15
 
 /// creates the basic generic traits for row-major matrices
 macro_rules! mat_impl {
@@ -955,30 +956,16 @@ impl<T> Mul<&Vec4<T>> for &Mat34<T> where T: Number {
 /// multiply 4x4 * 4x4 matrix
 fn mul4x4<T: Number>(lhs: Mat4<T>, rhs: Mat4<T>) -> Mat4<T> {
     Mat4 {
-        m: [
-            lhs.m[0] * rhs.m[0] + lhs.m[1] * rhs.m[4] + lhs.m[2] * rhs.m[8] + lhs.m[3] * rhs.m[12],
-            lhs.m[0] * rhs.m[1] + lhs.m[1] * rhs.m[5] + lhs.m[2] * rhs.m[9] + lhs.m[3] * rhs.m[13],
-            lhs.m[0] * rhs.m[2] + lhs.m[1] * rhs.m[6] + lhs.m[2] * rhs.m[10] + lhs.m[3] * rhs.m[14],
-            lhs.m[0] * rhs.m[3] + lhs.m[1] * rhs.m[7] + lhs.m[2] * rhs.m[11] + lhs.m[3] * rhs.m[15],
-
-            lhs.m[4] * rhs.m[0] + lhs.m[5] * rhs.m[4] + lhs.m[6] * rhs.m[8] + lhs.m[7] * rhs.m[12],
-            lhs.m[4] * rhs.m[1] + lhs.m[5] * rhs.m[5] + lhs.m[6] * rhs.m[9] + lhs.m[7] * rhs.m[13],
-            lhs.m[4] * rhs.m[2] + lhs.m[5] * rhs.m[6] + lhs.m[6] * rhs.m[10] + lhs.m[7] * rhs.m[14],
-            lhs.m[4] * rhs.m[3] + lhs.m[5] * rhs.m[7] + lhs.m[6] * rhs.m[11] + lhs.m[7] * rhs.m[15],
-
-            lhs.m[8] * rhs.m[0] + lhs.m[9] * rhs.m[4] + lhs.m[10] * rhs.m[8] + lhs.m[11] * rhs.m[12],
-            lhs.m[8] * rhs.m[1] + lhs.m[9] * rhs.m[5] + lhs.m[10] * rhs.m[9] + lhs.m[11] * rhs.m[13],
-            lhs.m[8] * rhs.m[2] + lhs.m[9] * rhs.m[6] + lhs.m[10] * rhs.m[10] + lhs.m[11] * rhs.m[14],
-            lhs.m[8] * rhs.m[3] + lhs.m[9] * rhs.m[7] + lhs.m[10] * rhs.m[11] + lhs.m[11] * rhs.m[15],
-
-            lhs.m[12] * rhs.m[0] + lhs.m[13] * rhs.m[4] + lhs.m[14] * rhs.m[8] + lhs.m[15] * rhs.m[12],
-            lhs.m[12] * rhs.m[1] + lhs.m[13] * rhs.m[5] + lhs.m[14] * rhs.m[9] + lhs.m[15] * rhs.m[13],
-            lhs.m[12] * rhs.m[2] + lhs.m[13] * rhs.m[6] + lhs.m[14] * rhs.m[10] + lhs.m[15] * rhs.m[14],
-            lhs.m[12] * rhs.m[3] + lhs.m[13] * rhs.m[7] + lhs.m[14] * rhs.m[11] + lhs.m[15] * rhs.m[15],
-        ]
+        m: crate::simd::mul4x4(&lhs.m, &rhs.m)
     }
 }
 
+/// multiply 4x4 matrix * vec4
+fn mul4x1<T: Number>(lhs: &Mat4<T>, rhs: Vec4<T>) -> Vec4<T> {
+    let [x, y, z, w] = crate::simd::mul4x1(&lhs.m, [rhs.x, rhs.y, rhs.z, rhs.w]);
+    Vec4 { x, y, z, w }
+}
+
 impl<T> Mul<Self> for Mat4<T> where T: Number {
     type Output = Self;
     fn mul(self, rhs: Mat4<T>) -> Self::Output {
@@ -995,12 +982,7 @@ impl<T> MulAssign<Self> for Mat4<T> where T: Number {
 impl<T> Mul<Vec4<T>> for Mat4<T> where T: Number {
     type Output = Vec4<T>;
     fn mul(self, rhs: Vec4<T>) -> Self::Output {
-        Vec4 {
-            x: self.m[0] * rhs.x + self.m[1] * rhs.y + self.m[2] * rhs.z + self.m[3] * rhs.w,
-            y: self.m[4] * rhs.x + self.m[5] * rhs.y + self.m[6] * rhs.z + self.m[7] * rhs.w,
-            z: self.m[8] * rhs.x + self.m[9] * rhs.y + self.m[10] * rhs.z + self.m[11] * rhs.w,
-            w: self.m[12] * rhs.x + self.m[13] * rhs.y + self.m[14] * rhs.z + self.m[15] * rhs.w,
-        }
+        mul4x1(&self, rhs)
     }
 }
 
@@ -1169,36 +1151,21 @@ impl<T> Mul<&Mat4<T>> for &Mat34<T> where T: Number {
 impl<T> Mul<&Vec4<T>> for Mat4<T> where T: Number {
     type Output = Vec4<T>;
     fn mul(self, rhs: &Vec4<T>) -> Self::Output {
-        Vec4 {
-            x: self.m[0] * rhs.x + self.m[1] * rhs.y + self.m[2] * rhs.z + self.m[3] * rhs.w,
-            y: self.m[4] * rhs.x + self.m[5] * rhs.y + self.m[6] * rhs.z + self.m[7] * rhs.w,
-            z: self.m[8] * rhs.x + self.m[9] * rhs.y + self.m[10] * rhs.z + self.m[11] * rhs.w,
-            w: self.m[12] * rhs.x + self.m[13] * rhs.y + self.m[14] * rhs.z + self.m[15] * rhs.w,
-        }
+        mul4x1(&self, *rhs)
     }
 }
 
 impl<T> Mul<Vec4<T>> for &Mat4<T> where T: Number {
     type Output = Vec4<T>;
     fn mul(self, rhs: Vec4<T>) -> Self::Output {
-        Vec4 {
-            x: self.m[0] * rhs.x + self.m[1] * rhs.y + self.m[2] * rhs.z + self.m[3] * rhs.w,
-            y: self.m[4] * rhs.x + self.m[5] * rhs.y + self.m[6] * rhs.z + self.m[7] * rhs.w,
-            z: self.m[8] * rhs.x + self.m[9] * rhs.y + self.m[10] * rhs.z + self.m[11] * rhs.w,
-            w: self.m[12] * rhs.x + self.m[13] * rhs.y + self.m[14] * rhs.z + self.m[15] * rhs.w,
-        }
+        mul4x1(self, rhs)
     }
 }
 
 impl<T> Mul<&Vec4<T>> for &Mat4<T> where T: Number {
     type Output = Vec4<T>;
     fn mul(self, rhs: &Vec4<T>) -> Self::Output {
-        Vec4 {
-            x: self.m[0] * rhs.x + self.m[1] * rhs.y + self.m[2] * rhs.z + self.m[3] * rhs.w,
-            y: self.m[4] * rhs.x + self.m[5] * rhs.y + self.m[6] * rhs.z + self.m[7] * rhs.w,
-            z: self.m[8] * rhs.x + self.m[9] * rhs.y + self.m[10] * rhs.z + self.m[11] * rhs.w,
-            w: self.m[12] * rhs.x + self.m[13] * rhs.y + self.m[14] * rhs.z + self.m[15] * rhs.w,
-        }
+        mul4x1(self, *rhs)
     }
 }
 
@@ -1515,6 +1482,307 @@ impl<T> MatRotate3D<T, Vec3<T>> for Mat4<T> where T: Float + FloatOps<T> + Signe
     }
 }
 
+/// trait for 3D camera view matrices, both handedness produce a view space with x-right, y-up looking along -z.
+/// this pairs with the `lh_yup` projections which give points in front of the camera a positive clip space w,
+/// the `rh_yup` projections use w = view z so would put these points behind the camera and mirror them on screen
+pub trait MatLookAt<T> {
+    /// returns a view matrix for a left handed world positioned at `eye` looking at `target` orientated with `up`
+    fn look_at_lh(eye: Vec3<T>, target: Vec3<T>, up: Vec3<T>) -> Self;
+    /// returns a view matrix for a right handed world positioned at `eye` looking at `target` orientated with `up`
+    fn look_at_rh(eye: Vec3<T>, target: Vec3<T>, up: Vec3<T>) -> Self;
+    /// returns a view matrix for a left handed world positioned at `eye` looking in direction `dir` orientated with `up`
+    fn look_to_lh(eye: Vec3<T>, dir: Vec3<T>, up: Vec3<T>) -> Self;
+    /// returns a view matrix for a right handed world positioned at `eye` looking in direction `dir` orientated with `up`
+    fn look_to_rh(eye: Vec3<T>, dir: Vec3<T>, up: Vec3<T>) -> Self;
+}
+
+/// internal utility returning normalized `right`, or any vector perpendicular to `forward` when `up` and `forward` are parallel
+fn view_right_internal<T: Float + FloatOps<T> + SignedNumberOps<T>>(right: Vec3<T>, forward: Vec3<T>) -> Vec3<T> {
+    let len = Vec3::mag(right);
+    if len > T::small_epsilon() {
+        right / len
+    }
+    else {
+        get_orthonormal_basis_hughes_moeller(forward).0
+    }
+}
+
+/// internal utility to build a view matrix from orthonormal world space `right`, `up` and `forward` vectors
+fn create_view_matrix_internal<T: Float>(eye: Vec3<T>, right: Vec3<T>, up: Vec3<T>, forward: Vec3<T>) -> Mat34<T> {
+    Mat34::from((
+        Vec4::from((right, -Vec3::dot(right, eye))),
+        Vec4::from((up, -Vec3::dot(up, eye))),
+        Vec4::from((-forward, Vec3::dot(forward, eye)))
+    ))
+}
+
+impl<T> MatLookAt<T> for Mat34<T> where T: Float + FloatOps<T> + SignedNumberOps<T> {
+    fn look_at_lh(eye: Vec3<T>, target: Vec3<T>, up: Vec3<T>) -> Self {
+        Self::look_to_lh(eye, target - eye, up)
+    }
+
+    fn look_at_rh(eye: Vec3<T>, target: Vec3<T>, up: Vec3<T>) -> Self {
+        Self::look_to_rh(eye, target - eye, up)
+    }
+
+    fn look_to_lh(eye: Vec3<T>, dir: Vec3<T>, up: Vec3<T>) -> Self {
+        let forward = Vec3::normalize(dir);
+        let right = view_right_internal(Vec3::cross(up, forward), forward);
+        create_view_matrix_internal(eye, right, Vec3::cross(forward, right), forward)
+    }
+
+    fn look_to_rh(eye: Vec3<T>, dir: Vec3<T>, up: Vec3<T>) -> Self {
+        let forward = Vec3::normalize(dir);
+        let right = view_right_internal(Vec3::cross(forward, up), forward);
+        create_view_matrix_internal(eye, right, Vec3::cross(right, forward), forward)
+    }
+}
+
+impl<T> MatLookAt<T> for Mat4<T> where T: Float + FloatOps<T> + SignedNumberOps<T> {
+    fn look_at_lh(eye: Vec3<T>, target: Vec3<T>, up: Vec3<T>) -> Self {
+        Mat4::from(Mat34::look_at_lh(eye, target, up))
+    }
+
+    fn look_at_rh(eye: Vec3<T>, target: Vec3<T>, up: Vec3<T>) -> Self {
+        Mat4::from(Mat34::look_at_rh(eye, target, up))
+    }
+
+    fn look_to_lh(eye: Vec3<T>, dir: Vec3<T>, up: Vec3<T>) -> Self {
+        Mat4::from(Mat34::look_to_lh(eye, dir, up))
+    }
+
+    fn look_to_rh(eye: Vec3<T>, dir: Vec3<T>, up: Vec3<T>) -> Self {
+        Mat4::from(Mat34::look_to_rh(eye, dir, up))
+    }
+}
+
+/// trait for affine 3D matrices to convert to and from separate translation, rotation and scale
+pub trait MatDecompose<T> {
+    /// create a matrix which applies scale `s` then rotation `r` then translation `t`
+    fn from_trs(t: Vec3<T>, r: Quat<T>, s: Vec3<T>) -> Self;
+    /// returns `(translation, rotation, scale)`, a reflection is returned as a negative x scale.
+    /// shear is discarded and zero scaled axes are filled in so the rotation is always valid
+    fn decompose(&self) -> (Vec3<T>, Quat<T>, Vec3<T>);
+}
+
+/// returns the unit rotation basis of the 3x3 part of `m` and the per axis scale
+fn decompose_basis<T>(m: &Mat34<T>) -> (Mat3<T>, Vec3<T>) where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    let x = m.get_column(0);
+    let y = m.get_column(1);
+    let z = m.get_column(2);
+    let mut scale = Vec3::new(Vec3::mag(x), Vec3::mag(y), Vec3::mag(z));
+
+    // negative determinant is a reflection, flipping x leaves a proper rotation
+    if Vec3::dot(x, Vec3::cross(y, z)) < T::zero() {
+        scale.x = -scale.x;
+    }
+
+    let eps = T::small_epsilon();
+    let valid_x = T::abs(scale.x) > eps;
+    let valid_y = scale.y > eps;
+    let valid_z = scale.z > eps;
+
+    // gram-schmidt orthonormalise, deriving any zero scaled axes from the others
+    let bx = if valid_x {
+        x / scale.x
+    }
+    else if valid_y && valid_z {
+        Vec3::normalize(Vec3::cross(y, z))
+    }
+    else if valid_y {
+        get_orthonormal_basis_hughes_moeller(y / scale.y).0
+    }
+    else if valid_z {
+        get_orthonormal_basis_hughes_moeller(z / scale.z).0
+    }
+    else {
+        Vec3::unit_x()
+    };
+
+    let oy = y - bx * Vec3::dot(bx, y);
+    let by = if valid_y && Vec3::mag(oy) > eps {
+        Vec3::normalize(oy)
+    }
+    else if valid_z {
+        Vec3::normalize(Vec3::cross(z, bx))
+    }
+    else {
+        get_orthonormal_basis_hughes_moeller(bx).0
+    };
+    let bz = Vec3::cross(bx, by);
+
+    let mut r = Mat3::identity();
+    r.set_column(0, bx);
+    r.set_column(1, by);
+    r.set_column(2, bz);
+    (r, scale)
+}
+
+impl<T> MatDecompose<T> for Mat34<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from_trs(t: Vec3<T>, r: Quat<T>, s: Vec3<T>) -> Self {
+        let mut m = Mat34::from(r);
+        m.set_column(0, m.get_column(0) * s.x);
+        m.set_column(1, m.get_column(1) * s.y);
+        m.set_column(2, m.get_column(2) * s.z);
+        m.set_column(3, t);
+        m
+    }
+
+    fn decompose(&self) -> (Vec3<T>, Quat<T>, Vec3<T>) {
+        let (r, s) = decompose_basis(self);
+        (self.get_column(3), Quat::from_matrix(r), s)
+    }
+}
+
+impl<T> MatDecompose<T> for Mat4<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from_trs(t: Vec3<T>, r: Quat<T>, s: Vec3<T>) -> Self {
+        Mat4::from(Mat34::from_trs(t, r, s))
+    }
+
+    fn decompose(&self) -> (Vec3<T>, Quat<T>, Vec3<T>) {
+        Mat34::from(*self).decompose()
+    }
+}
+
+/// trait for symmetric 3x3 matrices such as inertia or covariance tensors to compute eigenvalues and eigenvectors
+pub trait MatEigen<T> {
+    /// returns `(eigenvalues, eigenvectors)` of a symmetric matrix using jacobi rotations, only the upper triangle is read.
+    /// eigenvalues are sorted largest first and the eigenvectors are the matching columns of a rotation matrix
+    fn eigen_symmetric(&self) -> (Vec3<T>, Self);
+}
+
+/// trait for 3x3 matrices to compute the singular value decomposition
+pub trait MatSvd<T> {
+    /// returns `(u, sigma, v)` where `self = u * diag(sigma) * transpose(v)`. `u` and `v` are always rotations, so when
+    /// the determinant is negative the smallest singular value `sigma.z` is negative to account for the reflection
+    fn svd(&self) -> (Self, Vec3<T>, Self) where Self: Sized;
+}
+
+/// trait for 3x3 matrices to compute the polar decomposition
+pub trait MatPolar<T> {
+    /// returns `(rotation, stretch)` where `self = rotation * stretch` and stretch is symmetric,
+    /// a matrix with negative determinant produces a stretch with a negative eigenvalue so rotation remains a proper rotation
+    fn polar(&self) -> (Self, Self) where Self: Sized;
+}
+
+/// returns the eigen decomposition of the symmetric matrix `m` using cyclic jacobi rotations, unsorted
+fn jacobi_eigen_internal<T>(m: &Mat3<T>) -> (Vec3<T>, Mat3<T>) where T: Float + FloatOps<T> + SignedNumberOps<T> {
+    let mut a = *m;
+    let mut v = Mat3::identity();
+    let hundred = T::powi(T::two(), 7);
+    for sweep in 0..32 {
+        let off = T::abs(a.at(0, 1)) + T::abs(a.at(0, 2)) + T::abs(a.at(1, 2));
+        if off < T::small_epsilon() {
+            break;
+        }
+        for (p, q, r) in [(0, 1, 2), (0, 2, 1), (1, 2, 0)] {
+            let apq = a.at(p, q);
+            let app = a.at(p, p);
+            let aqq = a.at(q, q);
+
+            // after a few sweeps treat off diagonals which do not affect the diagonal as converged
+            let g = T::abs(apq) * hundred;
+            if sweep > 3 && T::abs(app) + g == T::abs(app) && T::abs(aqq) + g == T::abs(aqq) {
+                a.set(p, q, T::zero());
+                continue;
+            }
+            if T::abs(apq) < T::small_epsilon() {
+                continue;
+            }
+
+            // rotation which zeros apq, choosing the smaller angle for stability
+            let theta = (aqq - app) / (T::two() * apq);
+            let t = T::signum(theta) / (T::abs(theta) + T::sqrt(theta * theta + T::one()));
+            let t = if theta == T::zero() { T::one() } else { t };
+            let c = T::one() / T::sqrt(t * t + T::one());
+            let s = t * c;
+
+            // update both triangles so the matrix stays symmetric
+            let arp = a.at(r, p);
+            let arq = a.at(r, q);
+            let rp = c * arp - s * arq;
+            let rq = s * arp + c * arq;
+            a.set(p, p, app - t * apq);
+            a.set(q, q, aqq + t * apq);
+            a.set(p, q, T::zero());
+            a.set(q, p, T::zero());
+            a.set(r, p, rp);
+            a.set(p, r, rp);
+            a.set(r, q, rq);
+            a.set(q, r, rq);
+
+            for k in 0..3 {
+                let vkp = v.at(k, p);
+                let vkq = v.at(k, q);
+                v.set(k, p, c * vkp - s * vkq);
+                v.set(k, q, s * vkp + c * vkq);
+            }
+        }
+    }
+    (Vec3::new(a.at(0, 0), a.at(1, 1), a.at(2, 2)), v)
+}
+
+impl<T> MatEigen<T> for Mat3<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn eigen_symmetric(&self) -> (Vec3<T>, Self) {
+        // mirror the upper triangle so the solver sees an exactly symmetric matrix
+        let mut sym = *self;
+        sym.set(1, 0, self.at(0, 1));
+        sym.set(2, 0, self.at(0, 2));
+        sym.set(2, 1, self.at(1, 2));
+        let (mut values, mut vectors) = jacobi_eigen_internal(&sym);
+
+        // sort largest first, swapping eigenvector columns to match
+        for (i, j) in [(0, 1), (1, 2), (0, 1)] {
+            if values[j] > values[i] {
+                let vi = values[i];
+                values[i] = values[j];
+                values[j] = vi;
+                let ci = vectors.get_column(i as u32);
+                vectors.set_column(i as u32, vectors.get_column(j as u32));
+                vectors.set_column(j as u32, ci);
+            }
+        }
+
+        // eigenvectors are only defined up to sign, so make them a right handed basis
+        if vectors.determinant() < T::zero() {
+            vectors.set_column(2, -vectors.get_column(2));
+        }
+        (values, vectors)
+    }
+}
+
+impl<T> MatSvd<T> for Mat3<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn svd(&self) -> (Self, Vec3<T>, Self) {
+        // v are the eigenvectors of transpose(m) * m, sorted by largest singular value
+        let (_, v) = (self.transpose() * *self).eigen_symmetric();
+        let av0 = *self * v.get_column(0);
+        let av1 = *self * v.get_column(1);
+        let av2 = *self * v.get_column(2);
+
+        // u is built from m * v to avoid squaring the singular values, rank deficient columns are filled in orthogonally
+        let eps = T::small_epsilon();
+        let s0 = Vec3::mag(av0);
+        let u0 = if s0 > eps { av0 / s0 } else { Vec3::unit_x() };
+        let w = av1 - u0 * Vec3::dot(u0, av1);
+        let u1 = if Vec3::mag(w) > eps { Vec3::normalize(w) } else { get_orthonormal_basis_hughes_moeller(u0).0 };
+        let u2 = Vec3::cross(u0, u1);
+
+        let mut u = Mat3::identity();
+        u.set_column(0, u0);
+        u.set_column(1, u1);
+        u.set_column(2, u2);
+        (u, Vec3::new(s0, Vec3::dot(u1, av1), Vec3::dot(u2, av2)), v)
+    }
+}
+
+impl<T> MatPolar<T> for Mat3<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn polar(&self) -> (Self, Self) {
+        let (u, s, v) = self.svd();
+        let vt = v.transpose();
+        (u * vt, v * Mat3::from_scale(s) * vt)
+    }
+}
+
 /// trait for square matrices to compute determinant
 pub trait MatDeterminant<T> {
     /// return the determinant of the matrix as scalar `T`
@@ -1723,6 +1991,62 @@ impl<T> MatTranspose<T, Mat34<T>> for Mat43<T> where T: Number {
     }
 }
 
+/// trait for square matrices to factorize and solve linear systems `self * x = b`.
+/// singular input is detected relative to the largest element and machine epsilon and returns `None`
+pub trait MatSolve<T, V>: Sized {
+    /// returns `(l, u, p)` where `p * self = l * u` using partial pivoting, `l` is unit lower triangular,
+    /// `u` is upper triangular and `p` is a permutation matrix, or `None` if the matrix is singular
+    fn lu(&self) -> Option<(Self, Self, Self)>;
+    /// returns `(q, r)` where `self = q * r` using householder reflections, `q` is orthogonal and `r` is upper triangular
+    /// with a positive diagonal, or `None` if the matrix is singular
+    fn qr(&self) -> Option<(Self, Self)>;
+    /// returns lower triangular `l` where `self = l * transpose(l)`, or `None` if the matrix is not symmetric positive definite
+    fn cholesky(&self) -> Option<Self>;
+    /// returns `x` which solves `self * x = b` using lu decomposition, or `None` if the matrix is singular
+    fn solve(&self, b: V) -> Option<V>;
+    /// returns `x` which solves `self * x = b` using qr decomposition, or `None` if the matrix is singular
+    fn solve_qr(&self, b: V) -> Option<V>;
+    /// returns `x` which solves `self * x = b` using cholesky decomposition, or `None` if the matrix is not symmetric positive definite
+    fn solve_cholesky(&self, b: V) -> Option<V>;
+}
+
+/// implements `MatSolve` for fixed size square matrices by converting to the const generic `Matrix`
+macro_rules! mat_solve_impl {
+    ($MatN:ident, $VecN:ident, $n:expr) => {
+        impl<T> MatSolve<T, $VecN<T>> for $MatN<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+            fn lu(&self) -> Option<(Self, Self, Self)> {
+                let (l, u, p) = Matrix::<T, $n, $n>::from(*self).lu()?;
+                Some(($MatN::from(l), $MatN::from(u), $MatN::from(p)))
+            }
+
+            fn qr(&self) -> Option<(Self, Self)> {
+                let (q, r) = Matrix::<T, $n, $n>::from(*self).qr()?;
+                Some(($MatN::from(q), $MatN::from(r)))
+            }
+
+            fn cholesky(&self) -> Option<Self> {
+                Some($MatN::from(Matrix::<T, $n, $n>::from(*self).cholesky()?))
+            }
+
+            fn solve(&self, b: $VecN<T>) -> Option<$VecN<T>> {
+                Some($VecN::from(Matrix::<T, $n, $n>::from(*self).solve(Vector::from(b))?))
+            }
+
+            fn solve_qr(&self, b: $VecN<T>) -> Option<$VecN<T>> {
+                Some($VecN::from(Matrix::<T, $n, $n>::from(*self).solve_qr(Vector::from(b))?))
+            }
+
+            fn solve_cholesky(&self, b: $VecN<T>) -> Option<$VecN<T>> {
+                Some($VecN::from(Matrix::<T, $n, $n>::from(*self).solve_cholesky(Vector::from(b))?))
+            }
+        }
+    }
+}
+
+mat_solve_impl!(Mat2, Vec2, 2);
+mat_solve_impl!(Mat3, Vec3, 3);
+mat_solve_impl!(Mat4, Vec4, 4);
+
 /// trait for the minor of a matrix
 /// the minor is the determinant of the matrix without the given row and column
 pub trait MatMinor<T, Other> {
@@ -1779,21 +2103,21 @@ pub trait MatCofactor<T, Other> {
 
 impl <T> MatCofactor<T, T> for Mat2<T> where T: SignedNumber {
     fn cofactor(&self, row: u32, column: u32) -> T {
-        let sign = if (row + column & 1) == 1 { T::minus_one() } else { T::one() };
+        let sign = if ((row + column) & 1) == 1 { T::minus_one() } else { T::one() };
         sign * self.minor(row, column)
     }
 }
 
 impl<T> MatCofactor<T, T> for Mat3<T> where T: SignedNumber {
     fn cofactor(&self, row: u32, column: u32) -> T {
-        let sign = if (row + column & 1) == 1 { T::minus_one() } else { T::one() };
+        let sign = if ((row + column) & 1) == 1 { T::minus_one() } else { T::one() };
         sign * self.minor(row, column)
     }
 }
 
 impl<T> MatCofactor<T, T> for Mat4<T> where T: SignedNumber {
     fn cofactor(&self, row: u32, column: u32) -> T {
-        let sign = if (row + column & 1) == 1 { T::minus_one() } else { T::one() };
+        let sign = if ((row + column) & 1) == 1 { T::minus_one() } else { T::one() };
         sign * self.minor(row, column)
     }
 }
@@ -1853,8 +2177,72 @@ pub trait MatProjection<T> {
     fn create_ortho_matrix(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self;
     /// returns a perespective projection matrix (left hand coordinate system with y-up) from `fov` (radians), `aspect` ratio and `near` - `far` depth
     fn create_perspective_projection_lh_yup(fov: T, aspect: T, near: T, far: T) -> Self;
-    /// returns a perespective projection matrix (right hand coordinate system with y-up) from `fov` (radians), `aspect` ratio and `near` - `far` depth
+    /// returns a perespective projection matrix (right hand coordinate system with y-up) from `fov` (radians), `aspect` ratio and `near` - `far` depth.
+    /// clip space w is the view space z, so it does not pair with `MatLookAt` views which need the `lh_yup` projections
     fn create_perspective_projection_rh_yup(fov: T, aspect: T, near: T, far: T) -> Self;
+    /// returns 6 frustum planes as `Vec4`'s in the form `.xyz = normal, .w = plane distance` for a projection with `depth` range.
+    /// the far plane of an infinite projection is returned as zero so everything is inside it
+    fn get_frustum_planes_depth(&self, depth: DepthRange) -> [Vec4<T>; 6];
+    /// returns 8 points which are the corners of the frustum first 4 near, second 4 far for a projection with `depth` range
+    fn get_frustum_corners_depth(&self, depth: DepthRange) -> [Vec3<T>; 8];
+    /// returns a perespective projection matrix (left hand coordinate system with y-up) from `fov` (radians), `aspect` ratio and `near` - `far` depth
+    /// where `near` maps to `depth.near()` and `far` maps to `depth.far()` in ndc
+    fn create_perspective_projection_lh_yup_depth(fov: T, aspect: T, near: T, far: T, depth: DepthRange) -> Self;
+    /// returns a perespective projection matrix (right hand coordinate system with y-up) from `fov` (radians), `aspect` ratio and `near` - `far` depth
+    /// where `near` maps to `depth.near()` and `far` maps to `depth.far()` in ndc, unlike `create_perspective_projection_rh_yup` which maps `near` to 1
+    fn create_perspective_projection_rh_yup_depth(fov: T, aspect: T, near: T, far: T, depth: DepthRange) -> Self;
+    /// returns `create_perspective_projection_lh_yup_depth` with the far plane at infinity
+    fn create_perspective_projection_infinite_lh_yup(fov: T, aspect: T, near: T, depth: DepthRange) -> Self;
+    /// returns `create_perspective_projection_rh_yup_depth` with the far plane at infinity
+    fn create_perspective_projection_infinite_rh_yup(fov: T, aspect: T, near: T, depth: DepthRange) -> Self;
+}
+
+/// normalized device coordinate depth range produced by a projection matrix
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DepthRange {
+    /// OpenGL style, near maps to -1 and far maps to 1
+    NegOneToOne,
+    /// Direct3D, Vulkan and Metal style, near maps to 0 and far maps to 1
+    ZeroToOne,
+    /// reversed-Z, near maps to 1 and far maps to 0 which distributes float precision more evenly
+    Reversed
+}
+
+impl DepthRange {
+    /// returns the ndc depth of the near plane
+    pub fn near<T: Float>(self) -> T {
+        match self {
+            DepthRange::NegOneToOne => T::minus_one(),
+            DepthRange::ZeroToOne => T::zero(),
+            DepthRange::Reversed => T::one()
+        }
+    }
+
+    /// returns the ndc depth of the far plane
+    pub fn far<T: Float>(self) -> T {
+        match self {
+            DepthRange::NegOneToOne => T::one(),
+            DepthRange::ZeroToOne => T::one(),
+            DepthRange::Reversed => T::zero()
+        }
+    }
+
+    /// returns ndc depth `z` converted to a 0-1 window (depth buffer) value
+    pub fn ndc_to_window<T: Float + FloatOps<T>>(self, z: T) -> T {
+        match self {
+            DepthRange::NegOneToOne => z * T::point_five() + T::point_five(),
+            _ => z
+        }
+    }
+
+    /// returns 0-1 window (depth buffer) value `z` converted to ndc depth
+    pub fn window_to_ndc<T: Float>(self, z: T) -> T {
+        match self {
+            DepthRange::NegOneToOne => z * T::two() - T::one(),
+            _ => z
+        }
+    }
 }
 
 /// internal utility function to extract a plane in the form `.xyz=normal, w=constant` from plane corners (of a frustum)
@@ -1884,6 +2272,44 @@ fn create_perspective_matrix_internal_rh<T: Float + FloatOps<T>>(left: T, right:
     ))
 }
 
+fn create_perspective_matrix_internal_infinite_lh<T: Float + FloatOps<T>>(left: T, right: T, bottom: T, top: T, near: T) -> Mat4<T> {
+    let mut m = create_perspective_matrix_internal_lh(left, right, bottom, top, near, T::one());
+    m.set(2, 2, T::minus_one());
+    m.set(2, 3, -T::two() * near);
+    m
+}
+
+fn create_perspective_matrix_internal_infinite_rh<T: Float + FloatOps<T>>(left: T, right: T, bottom: T, top: T, near: T) -> Mat4<T> {
+    let mut m = create_perspective_matrix_internal_rh(left, right, bottom, top, near, T::one());
+    m.set(2, 2, T::minus_one());
+    m.set(2, 3, -T::two() * near);
+    m
+}
+
+/// internal utility to replace the depth row of perspective projection `m` so the view space depth `-near` maps to ndc `depth.near()` and `-far` maps to `depth.far()`.
+/// `m` must map near to -1 and far to 1 and `flip` negates the mapping for matrices which map near to 1 and far to -1
+fn remap_projection_depth_internal<T: Float + FloatOps<T>>(m: Mat4<T>, depth: DepthRange, flip: bool) -> Mat4<T> {
+    let (scale, offset) = match depth {
+        DepthRange::NegOneToOne => (T::one(), T::zero()),
+        DepthRange::ZeroToOne => (T::point_five(), T::point_five()),
+        DepthRange::Reversed => (-T::point_five(), T::point_five())
+    };
+    let scale = if flip { -scale } else { scale };
+    let mut out = m;
+    for c in 0..4 {
+        out.set(2, c, m.at(2, c) * scale + m.at(3, c) * offset);
+    }
+    out
+}
+
+/// internal utility returning the symmetric `left`, `right`, `bottom`, `top` extents of the near plane
+fn perspective_extents_internal<T: Float + FloatOps<T>>(fov: T, aspect: T, near: T) -> (T, T, T, T) {
+    let tfov = T::tan(fov * T::point_five());
+    let right = tfov * aspect * near;
+    let top = tfov * near;
+    (-right, right, -top, top)
+}
+
 impl<T> MatProjection<T> for Mat4<T> where T: Float + FloatOps<T>, Vec3<T>: FloatOps<T> {
     fn get_frustum_planes(&self) -> [Vec4<T>; 6] {
         // unproject matrix to get frustum corners grouped as 4 near, 4 far.
@@ -1951,6 +2377,63 @@ impl<T> MatProjection<T> for Mat4<T> where T: Float + FloatOps<T>, Vec3<T>: Floa
         ]
     }
 
+    fn get_frustum_planes_depth(&self, depth: DepthRange) -> [Vec4<T>; 6] {
+        // each clip plane is a combination of matrix rows, `row3 +/- row` for the sides and `row2 - d * row3` for depth `d`
+        let r0 = self.get_row(0);
+        let r1 = self.get_row(1);
+        let r2 = self.get_row(2);
+        let r3 = self.get_row(3);
+        let clip_planes = [
+            r3 + r0, // left
+            r3 - r1, // top
+            r3 - r0, // right
+            r3 + r1, // bottom
+            r2 - r3 * depth.near::<T>(), // near
+            r2 - r3 * depth.far::<T>(), // far
+        ];
+
+        // orientate normals to point outwards using a point inside the frustum, mid depth stays finite for infinite projections
+        let mid = (depth.near::<T>() + depth.far::<T>()) * T::point_five();
+        let inside = super::unproject_ndc(Vec3::new(T::zero(), T::zero(), mid), *self);
+        clip_planes.map(|p| {
+            let n = Vec3::from(p);
+            let len = Vec3::mag(n);
+            if len < T::small_epsilon() {
+                Vec4::zero()
+            }
+            else if Vec3::dot(n, inside) + p.w > T::zero() {
+                -p / len
+            }
+            else {
+                p / len
+            }
+        })
+    }
+
+    fn get_frustum_corners_depth(&self, depth: DepthRange) -> [Vec3<T>; 8] {
+        // unproject matrix to get frustum corners grouped as 4 near, 4 far.
+        let ndc_coords = [
+            Vec2::<T>::new(T::minus_one(), T::one()),
+            Vec2::<T>::new(T::one(), T::one()),
+            Vec2::<T>::new(T::minus_one(), T::minus_one()),
+            Vec2::<T>::new(T::one(), T::minus_one()),
+        ];
+
+        // construct corner points
+        let near = depth.near();
+        let far = depth.far();
+        [
+            super::unproject_ndc(Vec3::from((ndc_coords[0], near)), *self),
+            super::unproject_ndc(Vec3::from((ndc_coords[1], near)), *self),
+            super::unproject_ndc(Vec3::from((ndc_coords[2], near)), *self),
+            super::unproject_ndc(Vec3::from((ndc_coords[3], near)), *self),
+            super::unproject_ndc(Vec3::from((ndc_coords[0], far)), *self),
+            super::unproject_ndc(Vec3::from((ndc_coords[1], far)), *self),
+            super::unproject_ndc(Vec3::from((ndc_coords[2], far)), *self),
+            super::unproject_ndc(Vec3::from((ndc_coords[3], far)), *self),
+        ]
+    }
+
     fn create_ortho_matrix(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Mat4<T> {
         Mat4::from((
             Vec4::new(T::two() / (right - left), T::zero(), T::zero(), T::zero()),
@@ -1977,6 +2460,26 @@ impl<T> MatProjection<T> for Mat4<T> where T: Float + FloatOps<T>, Vec3<T>: Floa
         let bottom = -top;
         create_perspective_matrix_internal_rh(left, right, bottom, top, near, far)
     }
+
+    fn create_perspective_projection_lh_yup_depth(fov: T, aspect: T, near: T, far: T, depth: DepthRange) -> Mat4<T> {
+        let (left, right, bottom, top) = perspective_extents_internal(fov, aspect, near);
+        remap_projection_depth_internal(create_perspective_matrix_internal_lh(left, right, bottom, top, near, far), depth, false)
+    }
+
+    fn create_perspective_projection_rh_yup_depth(fov: T, aspect: T, near: T, far: T, depth: DepthRange) -> Mat4<T> {
+        let (left, right, bottom, top) = perspective_extents_internal(fov, aspect, near);
+        remap_projection_depth_internal(create_perspective_matrix_internal_rh(left, right, bottom, top, near, far), depth, true)
+    }
+
+    fn create_perspective_projection_infinite_lh_yup(fov: T, aspect: T, near: T, depth: DepthRange) -> Mat4<T> {
+        let (left, right, bottom, top) = perspective_extents_internal(fov, aspect, near);
+        remap_projection_depth_internal(create_perspective_matrix_internal_infinite_lh(left, right, bottom, top, near), depth, false)
+    }
+
+    fn create_perspective_projection_infinite_rh_yup(fov: T, aspect: T, near: T, depth: DepthRange) -> Mat4<T> {
+        let (left, right, bottom, top) = perspective_extents_internal(fov, aspect, near);
+        remap_projection_depth_internal(create_perspective_matrix_internal_infinite_rh(left, right, bottom, top, near), depth, true)
+    }
 }
 
 /// trait to construct matrix from 4 scalars
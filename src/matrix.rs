@@ -0,0 +1,799 @@
+use crate::num::*;
+use crate::vec::*;
+use crate::mat::*;
+
+use std::ops::Index;
+use std::ops::IndexMut;
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Sub;
+use std::ops::SubAssign;
+use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Div;
+use std::ops::Neg;
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+/// generic `N` dimensional column vector for sizes which are not covered by `Vec2`, `Vec3` and `Vec4`
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(C)]
+pub struct Vector<T, const N: usize> {
+    pub v: [T; N]
+}
+
+/// generic row-major matrix with `R` rows and `C` columns for sizes which are not covered by the fixed matrix types.
+/// the memory layout is the same as the fixed types so `m[row][col]` is element `row * C + col`
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(C)]
+pub struct Matrix<T, const R: usize, const C: usize> {
+    pub m: [[T; C]; R]
+}
+
+impl<T, const N: usize> Vector<T, N> where T: Number {
+    /// construct a vector from array `v`
+    pub fn new(v: [T; N]) -> Self {
+        Vector {
+            v
+        }
+    }
+
+    /// initialise vector to all zero's
+    pub fn zero() -> Self {
+        Vector {
+            v: [T::zero(); N]
+        }
+    }
+
+    /// initialise vector to all one's
+    pub fn one() -> Self {
+        Vector {
+            v: [T::one(); N]
+        }
+    }
+
+    /// returns the count of elements in the vector
+    pub const fn get_num_elements(&self) -> usize {
+        N
+    }
+
+    /// returns the dot product of `a` and `b`
+    pub fn dot(a: Self, b: Self) -> T {
+        let mut d = T::zero();
+        for i in 0..N {
+            d += a.v[i] * b.v[i];
+        }
+        d
+    }
+
+    /// returns the squared magnitude of the vector
+    pub fn mag2(self) -> T {
+        Self::dot(self, self)
+    }
+
+    /// returns a slice T of the vector
+    pub fn as_slice(&self) -> &[T] {
+        &self.v
+    }
+
+    /// returns a mutable slice T of the vector
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.v
+    }
+}
+
+impl<T, const N: usize> Vector<T, N> where T: Float + FloatOps<T> {
+    /// returns the magnitude of the vector
+    pub fn mag(self) -> T {
+        T::sqrt(self.mag2())
+    }
+
+    /// returns the vector scaled to unit length
+    pub fn normalize(self) -> Self {
+        self / self.mag()
+    }
+
+    /// compare if vectors are approximately equal to account for floating point precision
+    pub fn approx(lhs: Self, rhs: Self, eps: T) -> bool {
+        for i in 0..N {
+            if !T::approx(lhs.v[i], rhs.v[i], eps) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> where T: Number {
+    /// construct a matrix from an array of rows `m`
+    pub fn new(m: [[T; C]; R]) -> Self {
+        Matrix {
+            m
+        }
+    }
+
+    /// initialise matrix to all zero's
+    pub fn zero() -> Self {
+        Matrix {
+            m: [[T::zero(); C]; R]
+        }
+    }
+
+    /// initialise matrix to identity, non-square matrices have one's on the leading diagonal
+    pub fn identity() -> Self {
+        let mut mat = Self::zero();
+        for i in 0..R.min(C) {
+            mat.m[i][i] = T::one();
+        }
+        mat
+    }
+
+    pub const fn get_num_rows(&self) -> usize {
+        R
+    }
+
+    pub const fn get_num_columns(&self) -> usize {
+        C
+    }
+
+    /// get single element from the matrix at row, column index
+    pub fn at(self, row: u32, column: u32) -> T {
+        self.m[row as usize][column as usize]
+    }
+
+    /// set a single element of the matrix at row, column index
+    pub fn set(&mut self, row: u32, column: u32, value: T) {
+        self.m[row as usize][column as usize] = value
+    }
+
+    /// gets a single row of the matrix in a vector sized by the column count of the matrix
+    pub fn get_row(self, row: u32) -> Vector<T, C> {
+        Vector {
+            v: self.m[row as usize]
+        }
+    }
+
+    /// sets a single row of the matrix by a vector sized by the column count of the matrix
+    pub fn set_row(&mut self, row: u32, value: Vector<T, C>) {
+        self.m[row as usize] = value.v
+    }
+
+    /// gets a single column of the matrix in a vector sized by the row count of the matrix
+    pub fn get_column(self, column: u32) -> Vector<T, R> {
+        let mut v = Vector::zero();
+        for r in 0..R {
+            v.v[r] = self.m[r][column as usize];
+        }
+        v
+    }
+
+    /// sets a single column of the matrix by a vector sized by the row count of the matrix
+    pub fn set_column(&mut self, column: u32, value: Vector<T, R>) {
+        for r in 0..R {
+            self.m[r][column as usize] = value.v[r];
+        }
+    }
+
+    /// returns a slice T of the matrix
+    pub fn as_slice(&self) -> &[T] {
+        self.m.as_flattened()
+    }
+
+    /// returns a mutable slice T of the matrix
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.m.as_flattened_mut()
+    }
+}
+
+impl<T, const R: usize, const C: usize> Matrix<T, R, C> where T: Float + FloatOps<T> {
+    /// compare if matrices are approximately equal to account for floating point precision
+    pub fn approx(lhs: Self, rhs: Self, eps: T) -> bool {
+        for r in 0..R {
+            for c in 0..C {
+                if !T::approx(lhs.m[r][c], rhs.m[r][c], eps) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// index into vector \[index\]
+impl<T, const N: usize> Index<usize> for Vector<T, N> {
+    type Output = T;
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.v[index]
+    }
+}
+
+/// mutably index into vector \[index\]
+impl<T, const N: usize> IndexMut<usize> for Vector<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.v[index]
+    }
+}
+
+/// index into matrix with tuple \[(row, column)\]
+impl<T, const R: usize, const C: usize> Index<(usize, usize)> for Matrix<T, R, C> {
+    type Output = T;
+    fn index(&self, rc: (usize, usize)) -> &Self::Output {
+        &self.m[rc.0][rc.1]
+    }
+}
+
+/// mutably index into matrix with tuple \[(row, column)\]
+impl<T, const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<T, R, C> {
+    fn index_mut(&mut self, rc: (usize, usize)) -> &mut Self::Output {
+        &mut self.m[rc.0][rc.1]
+    }
+}
+
+/// default to zero vector
+impl<T, const N: usize> Default for Vector<T, N> where T: Number {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+/// default to identity matrix
+impl<T, const R: usize, const C: usize> Default for Matrix<T, R, C> where T: Number {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// displays like [1.0, 2.0, 3.0, 4.0, 5.0]
+impl<T, const N: usize> Display for Vector<T, N> where T: Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut output = String::from("[");
+        for i in 0..N {
+            output += &self.v[i].to_string();
+            if i < N-1 {
+                output += &String::from(", ");
+            }
+        }
+        output += "]";
+        write!(f, "{}", output)
+    }
+}
+
+/// displays like:
+/// [1.0, 0.0, 0.0]
+/// [0.0, 1.0, 1.0]
+impl<T, const R: usize, const C: usize> Display for Matrix<T, R, C> where T: Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut output = String::from("");
+        for r in 0..R {
+            output += &String::from("[");
+            for c in 0..C {
+                output += &self.m[r][c].to_string();
+                if c < C-1 {
+                    output += &String::from(", ");
+                }
+            }
+            output += "]";
+            if r < R-1 {
+                output += "\n";
+            }
+        }
+        write!(f, "{}", output)
+    }
+}
+
+/// component-wise arithmetic ops for vectors and matrices
+macro_rules! matrix_ops {
+    ($Type:ident < $($N:ident),+ >) => {
+        impl<T, $(const $N: usize),+> Add<Self> for $Type<T, $($N),+> where T: Number {
+            type Output = Self;
+            fn add(mut self, other: Self) -> Self {
+                for (a, b) in self.as_mut_slice().iter_mut().zip(other.as_slice()) {
+                    *a += *b;
+                }
+                self
+            }
+        }
+
+        impl<T, $(const $N: usize),+> AddAssign<Self> for $Type<T, $($N),+> where T: Number {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T, $(const $N: usize),+> Sub<Self> for $Type<T, $($N),+> where T: Number {
+            type Output = Self;
+            fn sub(mut self, other: Self) -> Self {
+                for (a, b) in self.as_mut_slice().iter_mut().zip(other.as_slice()) {
+                    *a -= *b;
+                }
+                self
+            }
+        }
+
+        impl<T, $(const $N: usize),+> SubAssign<Self> for $Type<T, $($N),+> where T: Number {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl<T, $(const $N: usize),+> Mul<T> for $Type<T, $($N),+> where T: Number {
+            type Output = Self;
+            fn mul(mut self, other: T) -> Self {
+                for a in self.as_mut_slice().iter_mut() {
+                    *a *= other;
+                }
+                self
+            }
+        }
+
+        impl<T, $(const $N: usize),+> Div<T> for $Type<T, $($N),+> where T: Number {
+            type Output = Self;
+            fn div(mut self, other: T) -> Self {
+                for a in self.as_mut_slice().iter_mut() {
+                    *a /= other;
+                }
+                self
+            }
+        }
+
+        impl<T, $(const $N: usize),+> Neg for $Type<T, $($N),+> where T: SignedNumber {
+            type Output = Self;
+            fn neg(mut self) -> Self {
+                for a in self.as_mut_slice().iter_mut() {
+                    *a = -*a;
+                }
+                self
+            }
+        }
+    }
+}
+
+matrix_ops!(Vector<N>);
+matrix_ops!(Matrix<R, C>);
+
+/// multiplies matrix `self` by matrix `other`, the column count of `self` must match the row count of `other`
+impl<T, const R: usize, const C: usize, const K: usize> Mul<Matrix<T, C, K>> for Matrix<T, R, C> where T: Number {
+    type Output = Matrix<T, R, K>;
+    fn mul(self, other: Matrix<T, C, K>) -> Matrix<T, R, K> {
+        let mut out = Matrix::zero();
+        for r in 0..R {
+            for k in 0..K {
+                let mut v = T::zero();
+                for c in 0..C {
+                    v += self.m[r][c] * other.m[c][k];
+                }
+                out.m[r][k] = v;
+            }
+        }
+        out
+    }
+}
+
+impl<T, const N: usize> MulAssign<Self> for Matrix<T, N, N> where T: Number {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+/// multiplies column vector `other` by matrix `self`, the size of `other` must match the column count of `self`
+impl<T, const R: usize, const C: usize> Mul<Vector<T, C>> for Matrix<T, R, C> where T: Number {
+    type Output = Vector<T, R>;
+    fn mul(self, other: Vector<T, C>) -> Vector<T, R> {
+        let mut out = Vector::zero();
+        for r in 0..R {
+            out.v[r] = Vector::dot(Vector::new(self.m[r]), other);
+        }
+        out
+    }
+}
+
+impl<T, const R: usize, const C: usize> MatTranspose<T, Matrix<T, C, R>> for Matrix<T, R, C> where T: Number {
+    fn transpose(&self) -> Matrix<T, C, R> {
+        let mut t = Matrix::zero();
+        for r in 0..R {
+            for c in 0..C {
+                t.m[c][r] = self.m[r][c];
+            }
+        }
+        t
+    }
+}
+
+/// returns the determinant using gaussian elimination with partial pivoting
+impl<T, const N: usize> MatDeterminant<T> for Matrix<T, N, N> where T: Float + SignedNumberOps<T> {
+    fn determinant(&self) -> T {
+        let mut a = self.m;
+        let mut det = T::one();
+        for c in 0..N {
+            let mut pivot = c;
+            for r in c+1..N {
+                if T::abs(a[r][c]) > T::abs(a[pivot][c]) {
+                    pivot = r;
+                }
+            }
+            if a[pivot][c] == T::zero() {
+                return T::zero();
+            }
+            if pivot != c {
+                a.swap(pivot, c);
+                det = -det;
+            }
+            det *= a[c][c];
+            let pivot_row = a[c];
+            for row in a.iter_mut().skip(c+1) {
+                let f = row[c] / pivot_row[c];
+                for (v, p) in row.iter_mut().zip(pivot_row).skip(c) {
+                    *v -= f * p;
+                }
+            }
+        }
+        det
+    }
+}
+
+/// returns the inverse using gauss-jordan elimination with partial pivoting, singular matrices produce inf or nan like the fixed size types
+impl<T, const N: usize> MatInverse<T> for Matrix<T, N, N> where T: Float + SignedNumberOps<T> {
+    fn inverse(&self) -> Self {
+        let mut a = self.m;
+        let mut inv = Self::identity().m;
+        for c in 0..N {
+            let mut pivot = c;
+            for r in c+1..N {
+                if T::abs(a[r][c]) > T::abs(a[pivot][c]) {
+                    pivot = r;
+                }
+            }
+            a.swap(pivot, c);
+            inv.swap(pivot, c);
+            let inv_p = T::one() / a[c][c];
+            for k in 0..N {
+                a[c][k] *= inv_p;
+                inv[c][k] *= inv_p;
+            }
+            for r in 0..N {
+                if r != c {
+                    let f = a[r][c];
+                    for k in 0..N {
+                        let (va, vi) = (a[c][k], inv[c][k]);
+                        a[r][k] -= f * va;
+                        inv[r][k] -= f * vi;
+                    }
+                }
+            }
+        }
+        Matrix {
+            m: inv
+        }
+    }
+}
+
+/// returns the tolerance below which a pivot is treated as zero, scaled by the largest element of `m`
+fn singular_tolerance<T, const N: usize>(m: &Matrix<T, N, N>) -> T where T: Float + SignedNumberOps<T> {
+    let mut max = T::zero();
+    let mut n = T::zero();
+    for row in m.m {
+        for v in row {
+            if T::abs(v) > max {
+                max = T::abs(v);
+            }
+        }
+        n += T::one();
+    }
+    max * T::epsilon() * n
+}
+
+/// solves `l * x = b` where `l` is lower triangular, treating the diagonal as one when `unit_diag` is true
+fn forward_substitute<T, const N: usize>(l: &Matrix<T, N, N>, b: Vector<T, N>, unit_diag: bool) -> Vector<T, N> where T: Float {
+    let mut x = b;
+    for r in 0..N {
+        for c in 0..r {
+            x.v[r] -= l.m[r][c] * x.v[c];
+        }
+        if !unit_diag {
+            x.v[r] /= l.m[r][r];
+        }
+    }
+    x
+}
+
+/// solves `u * x = b` where `u` is upper triangular
+fn back_substitute<T, const N: usize>(u: &Matrix<T, N, N>, b: Vector<T, N>) -> Vector<T, N> where T: Float {
+    let mut x = b;
+    for r in (0..N).rev() {
+        for c in r+1..N {
+            x.v[r] -= u.m[r][c] * x.v[c];
+        }
+        x.v[r] /= u.m[r][r];
+    }
+    x
+}
+
+impl<T, const N: usize> Matrix<T, N, N> where T: Float + FloatOps<T> + SignedNumberOps<T> {
+    /// returns the packed lu factors and row permutation where `perm[i]` is the source row of row `i`
+    fn lu_packed(&self) -> Option<(Self, [usize; N])> {
+        let tol = singular_tolerance(self);
+        let mut a = *self;
+        let mut perm = [0; N];
+        for (i, p) in perm.iter_mut().enumerate() {
+            *p = i;
+        }
+        for c in 0..N {
+            let mut pivot = c;
+            for r in c+1..N {
+                if T::abs(a.m[r][c]) > T::abs(a.m[pivot][c]) {
+                    pivot = r;
+                }
+            }
+            if T::abs(a.m[pivot][c]) <= tol {
+                return None;
+            }
+            a.m.swap(pivot, c);
+            perm.swap(pivot, c);
+            let pivot_row = a.m[c];
+            for row in a.m.iter_mut().skip(c+1) {
+                row[c] /= pivot_row[c];
+                let f = row[c];
+                for (v, p) in row.iter_mut().zip(pivot_row).skip(c+1) {
+                    *v -= f * p;
+                }
+            }
+        }
+        Some((a, perm))
+    }
+}
+
+impl<T, const N: usize> MatSolve<T, Vector<T, N>> for Matrix<T, N, N> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn lu(&self) -> Option<(Self, Self, Self)> {
+        let (a, perm) = self.lu_packed()?;
+        let mut l = Self::identity();
+        let mut u = Self::zero();
+        let mut p = Self::zero();
+        for (r, src) in perm.iter().enumerate() {
+            for c in 0..N {
+                if c < r {
+                    l.m[r][c] = a.m[r][c];
+                }
+                else {
+                    u.m[r][c] = a.m[r][c];
+                }
+            }
+            p.m[r][*src] = T::one();
+        }
+        Some((l, u, p))
+    }
+
+    fn qr(&self) -> Option<(Self, Self)> {
+        let tol = singular_tolerance(self);
+        let mut q = Self::identity();
+        let mut r = *self;
+        for k in 0..N {
+            // householder vector which reflects column k below the diagonal onto the axis
+            let mut v = Vector::<T, N>::zero();
+            for i in k..N {
+                v.v[i] = r.m[i][k];
+            }
+            let alpha = T::sqrt(v.mag2());
+            if alpha > T::small_epsilon() {
+                v.v[k] += if v.v[k] < T::zero() { -alpha } else { alpha };
+                let vv = v.mag2();
+                if vv > T::small_epsilon() {
+                    // r = h * r and q = q * h where h = i - 2 * v * transpose(v) / dot(v, v)
+                    for c in 0..N {
+                        let mut d = T::zero();
+                        for i in k..N {
+                            d += v.v[i] * r.m[i][c];
+                        }
+                        let f = T::two() * d / vv;
+                        for i in k..N {
+                            r.m[i][c] -= f * v.v[i];
+                        }
+                    }
+                    for row in q.m.iter_mut() {
+                        let mut d = T::zero();
+                        for (x, vi) in row.iter().zip(v.v).skip(k) {
+                            d += *x * vi;
+                        }
+                        let f = T::two() * d / vv;
+                        for (x, vi) in row.iter_mut().zip(v.v).skip(k) {
+                            *x -= f * vi;
+                        }
+                    }
+                }
+            }
+
+            // make the diagonal positive so the factorization is unique
+            if r.m[k][k] < T::zero() {
+                for c in 0..N {
+                    r.m[k][c] = -r.m[k][c];
+                    q.m[c][k] = -q.m[c][k];
+                }
+            }
+            if r.m[k][k] <= tol {
+                return None;
+            }
+            for i in k+1..N {
+                r.m[i][k] = T::zero();
+            }
+        }
+        Some((q, r))
+    }
+
+    fn cholesky(&self) -> Option<Self> {
+        let tol = singular_tolerance(self);
+        let mut l = Self::zero();
+        for j in 0..N {
+            for i in j+1..N {
+                if T::abs(self.m[i][j] - self.m[j][i]) > tol {
+                    return None;
+                }
+            }
+            let mut d = self.m[j][j];
+            for k in 0..j {
+                d -= l.m[j][k] * l.m[j][k];
+            }
+            if d <= tol {
+                return None;
+            }
+            let ljj = T::sqrt(d);
+            l.m[j][j] = ljj;
+            for i in j+1..N {
+                let mut s = self.m[i][j];
+                for k in 0..j {
+                    s -= l.m[i][k] * l.m[j][k];
+                }
+                l.m[i][j] = s / ljj;
+            }
+        }
+        Some(l)
+    }
+
+    fn solve(&self, b: Vector<T, N>) -> Option<Vector<T, N>> {
+        let (a, perm) = self.lu_packed()?;
+        let mut pb = Vector::zero();
+        for (x, src) in pb.v.iter_mut().zip(perm) {
+            *x = b.v[src];
+        }
+        Some(back_substitute(&a, forward_substitute(&a, pb, true)))
+    }
+
+    fn solve_qr(&self, b: Vector<T, N>) -> Option<Vector<T, N>> {
+        let (q, r) = self.qr()?;
+        Some(back_substitute(&r, q.transpose() * b))
+    }
+
+    fn solve_cholesky(&self, b: Vector<T, N>) -> Option<Vector<T, N>> {
+        let l = self.cholesky()?;
+        Some(back_substitute(&l.transpose(), forward_substitute(&l, b, false)))
+    }
+}
+
+/// constructs from an array of elements
+impl<T, const N: usize> From<[T; N]> for Vector<T, N> where T: Number {
+    fn from(other: [T; N]) -> Self {
+        Vector::new(other)
+    }
+}
+
+/// constructs from an array of rows
+impl<T, const R: usize, const C: usize> From<[[T; C]; R]> for Matrix<T, R, C> where T: Number {
+    fn from(other: [[T; C]; R]) -> Self {
+        Matrix::new(other)
+    }
+}
+
+/// lossless conversions between the fixed size vector types and `Vector`
+macro_rules! vector_convert {
+    ($VecN:ident, $len:expr, { $($field:ident, $field_index:expr),* }) => {
+        impl<T> From<$VecN<T>> for Vector<T, $len> where T: Number {
+            fn from(other: $VecN<T>) -> Self {
+                Vector {
+                    v: [$(other.$field,)+]
+                }
+            }
+        }
+
+        impl<T> From<Vector<T, $len>> for $VecN<T> where T: Number {
+            fn from(other: Vector<T, $len>) -> Self {
+                $VecN {
+                    $($field: other.v[$field_index],)+
+                }
+            }
+        }
+    }
+}
+
+vector_convert!(Vec2, 2, {x, 0, y, 1});
+vector_convert!(Vec3, 3, {x, 0, y, 1, z, 2});
+vector_convert!(Vec4, 4, {x, 0, y, 1, z, 2, w, 3});
+
+/// lossless conversions between the fixed size matrix types and `Matrix`
+macro_rules! matrix_convert {
+    ($MatN:ident, $rows:expr, $cols:expr) => {
+        impl<T> From<$MatN<T>> for Matrix<T, $rows, $cols> where T: Number {
+            fn from(other: $MatN<T>) -> Self {
+                let mut mat = Matrix::zero();
+                mat.as_mut_slice().copy_from_slice(&other.m);
+                mat
+            }
+        }
+
+        impl<T> From<Matrix<T, $rows, $cols>> for $MatN<T> where T: Number {
+            fn from(other: Matrix<T, $rows, $cols>) -> Self {
+                let mut mat = $MatN::zero();
+                mat.m.copy_from_slice(other.as_slice());
+                mat
+            }
+        }
+    }
+}
+
+matrix_convert!(Mat2, 2, 2);
+matrix_convert!(Mat3, 3, 3);
+matrix_convert!(Mat34, 3, 4);
+matrix_convert!(Mat43, 4, 3);
+matrix_convert!(Mat4, 4, 4);
+
+/// serde for arrays is limited to fixed sizes, so `Vector` and `Matrix` serialize as a flat row-major sequence of elements
+#[cfg(feature="serde")]
+mod serde_impl {
+    use super::*;
+    use serde::ser::SerializeTuple;
+
+    fn serialize_elems<S: serde::Serializer, T: serde::Serialize>(elems: &[T], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tup = serializer.serialize_tuple(elems.len())?;
+        for e in elems {
+            tup.serialize_element(e)?;
+        }
+        tup.end()
+    }
+
+    struct ElemsVisitor<T, const R: usize, const C: usize>(std::marker::PhantomData<T>);
+
+    impl<'de, T, const R: usize, const C: usize> serde::de::Visitor<'de> for ElemsVisitor<T, R, C> where T: Number + serde::Deserialize<'de> {
+        type Value = [[T; C]; R];
+
+        fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+            write!(f, "a sequence of {} elements", R * C)
+        }
+
+        fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut m = [[T::zero(); C]; R];
+            for i in 0..R * C {
+                m[i / C][i % C] = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+            }
+            Ok(m)
+        }
+    }
+
+    impl<T, const N: usize> serde::Serialize for Vector<T, N> where T: serde::Serialize {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize_elems(&self.v, serializer)
+        }
+    }
+
+    impl<'de, T, const N: usize> serde::Deserialize<'de> for Vector<T, N> where T: Number + serde::Deserialize<'de> {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let m = deserializer.deserialize_tuple(N, ElemsVisitor::<T, 1, N>(std::marker::PhantomData))?;
+            Ok(Vector {
+                v: m[0]
+            })
+        }
+    }
+
+    impl<T, const R: usize, const C: usize> serde::Serialize for Matrix<T, R, C> where T: serde::Serialize {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serialize_elems(self.m.as_flattened(), serializer)
+        }
+    }
+
+    impl<'de, T, const R: usize, const C: usize> serde::Deserialize<'de> for Matrix<T, R, C> where T: Number + serde::Deserialize<'de> {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Ok(Matrix {
+                m: deserializer.deserialize_tuple(R * C, ElemsVisitor::<T, R, C>(std::marker::PhantomData))?
+            })
+        }
+    }
+}
@@ -196,7 +196,8 @@ pub trait FloatOps<T: Float>: Lerp<T> where Self: Sized {
 macro_rules! number_trait_impl {
     ($($func:ident),*) => {
         /// base number trait for signed or unsigned, floating point or integer numbers.
-        pub trait Number: Base<Self> + Default + PartialEq + PartialOrd  {}
+        /// numbers are plain values so they are `'static`, which lets the `simd` feature identify `f32` by `TypeId`
+        pub trait Number: Base<Self> + Default + PartialEq + PartialOrd + 'static {}
         number_impl!(f64 { $($func),* }, 0.0, 1.0);
         number_impl!(f32 { $($func),* }, 0.0, 1.0);
         number_impl!(usize { $($func),* }, 0, 1);
@@ -369,6 +370,15 @@ macro_rules! float_trait_impl {
         /// floating point trait for various levels of fp precision
         pub trait Float: SignedNumber {
             fn small_epsilon() -> Self;
+            /// returns the machine epsilon, the difference between 1 and the next representable value
+            fn epsilon() -> Self {
+                // halve until adding to 1 makes no difference, implementors with a known constant should override this
+                let mut e = Self::one();
+                while Self::one() + e / Self::two() != Self::one() {
+                    e /= Self::two();
+                }
+                e
+            }
         }
         float_impl!(f64 { $($func),* });
         float_impl!(f32 { $($func),* });
@@ -381,6 +391,10 @@ macro_rules! float_impl {
             fn small_epsilon() -> Self {
                 1e-30
             }
+
+            fn epsilon() -> Self {
+                $t::EPSILON
+            }
         }
 
         impl Lerp<$t> for $t {
@@ -400,9 +414,8 @@ macro_rules! float_impl {
                 0.5 as Self
             }
 
-            #[allow(clippy::excessive_precision)]
             fn pi() -> Self {
-                3.14159265358979323846264338327950288 as Self
+                std::$t::consts::PI
             }
 
             fn two_pi() -> Self {
@@ -422,9 +435,8 @@ macro_rules! float_impl {
                 1.0 as Self / Self::phi() as Self
             }
 
-            #[allow(clippy::excessive_precision)]
             fn tau() -> Self {
-                6.2831853071795864 as Self
+                std::$t::consts::TAU
             }
 
             fn rsqrt(a: Self) -> Self {
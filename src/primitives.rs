@@ -0,0 +1,1578 @@
+use std::marker::PhantomData;
+
+use crate::num::*;
+use crate::vec::*;
+use crate::mat::*;
+
+use crate::{
+    Classification, RayHit,
+    dot, dist, normalize, barycentric, furthest_point,
+    plane_from_normal_and_point, get_triangle_normal,
+    point_plane_distance, point_line_segment_distance, point_aabb_distance, point_sphere_distance,
+    point_triangle_distance, point_obb_distance,
+    closest_point_on_line_segment, closest_point_on_plane, closest_point_on_aabb, closest_point_on_sphere,
+    closest_point_on_ray, closest_point_on_obb, closest_point_on_triangle,
+    point_inside_aabb, point_inside_sphere, point_inside_obb, point_inside_triangle, point_inside_frustum, point_inside_cone,
+    point_vs_plane, aabb_vs_plane, sphere_vs_plane, capsule_vs_plane, cone_vs_plane,
+    sphere_vs_sphere, sphere_vs_capsule, aabb_vs_sphere, aabb_vs_aabb, aabb_vs_obb, sphere_vs_obb, obb_vs_obb,
+    capsule_vs_capsule, sphere_vs_frustum, aabb_vs_frustum,
+    ray_vs_plane, ray_vs_sphere, ray_vs_aabb, ray_vs_obb, ray_vs_triangle, ray_vs_capsule, ray_vs_cylinder,
+    ray_vs_sphere_hit, ray_vs_aabb_hit, ray_vs_obb_hit, ray_vs_triangle_hit, ray_vs_capsule_hit, ray_vs_cylinder_hit
+};
+
+/// axis aligned bounding box in 2D or 3D defined by `min` and `max` corners
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Aabb<T, V = Vec3<T>> {
+    /// minimum corner
+    pub min: V,
+    /// maximum corner
+    pub max: V,
+    #[cfg_attr(feature="serde", serde(skip))]
+    phantom: PhantomData<T>
+}
+
+/// sphere in 3D or circle in 2D defined by `center` and `radius`
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Sphere<T, V = Vec3<T>> {
+    /// centre position
+    pub center: V,
+    /// radius from the centre
+    pub radius: T
+}
+
+/// capsule in 3D or 2D defined by the line segment `p0-p1` swept by `radius`
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Capsule<T, V = Vec3<T>> {
+    /// start of the capsule axis
+    pub p0: V,
+    /// end of the capsule axis
+    pub p1: V,
+    /// radius around the axis
+    pub radius: T
+}
+
+/// ray in 2D or 3D with `origin` and `direction`, the intersection functions expect a normalized direction
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Ray<T, V = Vec3<T>> {
+    /// start point of the ray
+    pub origin: V,
+    /// direction the ray travels in
+    pub direction: V,
+    #[cfg_attr(feature="serde", serde(skip))]
+    phantom: PhantomData<T>
+}
+
+/// triangle in 2D or 3D with vertices `v0-v1-v2`
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Triangle<T, V = Vec3<T>> {
+    /// first vertex
+    pub v0: V,
+    /// second vertex
+    pub v1: V,
+    /// third vertex
+    pub v2: V,
+    #[cfg_attr(feature="serde", serde(skip))]
+    phantom: PhantomData<T>
+}
+
+/// 3D oriented bounding box defined by `center`, half `extents` along each local axis and `rotation`
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Obb<T> {
+    /// centre position
+    pub center: Vec3<T>,
+    /// half size along each of the local axes
+    pub extents: Vec3<T>,
+    /// rotation matrix where the columns are the local axes
+    pub rotation: Mat3<T>
+}
+
+/// 3D plane with unit `normal` and `distance` constant, points on the plane satisfy `dot(p, normal) + distance = 0`.
+/// this is the same layout as planes packed into `Vec4` with `.xyz = normal, .w = distance`
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Plane<T> {
+    /// unit normal of the plane
+    pub normal: Vec3<T>,
+    /// plane constant, the negated distance of the plane from the origin along the normal
+    pub distance: T
+}
+
+/// 3D view frustum defined by 6 outward facing `planes` packed as `.xyz = normal, .w = distance` and the 8 `corners`
+/// ordered near (top left, top right, bottom left, bottom right) then far in the same order
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Frustum<T> {
+    /// left, top, right, bottom, near, far planes with outward facing normals
+    pub planes: [Vec4<T>; 6],
+    /// near corners followed by far corners
+    pub corners: [Vec3<T>; 8]
+}
+
+/// 3D cylinder defined by the line segment `p0-p1` between the centres of the flat caps and `radius`.
+/// it intersects rays and contains points, other shapes can be tested with `gjk_3d` through `Support`
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cylinder<T> {
+    /// centre of the first cap
+    pub p0: Vec3<T>,
+    /// centre of the second cap
+    pub p1: Vec3<T>,
+    /// radius around the axis
+    pub radius: T
+}
+
+/// 3D cone with its tip at `position` facing unit `direction` toward a base of `radius` at distance `height`
+/// this matches the parameters of `point_inside_cone` and `cone_vs_plane`, other shapes can be tested with `gjk_3d` through `Support`
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cone<T> {
+    /// position of the tip
+    pub position: Vec3<T>,
+    /// unit direction from the tip toward the base
+    pub direction: Vec3<T>,
+    /// distance from the tip to the base
+    pub height: T,
+    /// radius of the base
+    pub radius: T
+}
+
+/// 3D convex `shape` placed in the world by an affine `matrix`, so a shape can be defined once in local space and moved
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Transformed<T, S> {
+    /// shape in local space
+    pub shape: S,
+    /// local to world transform
+    pub matrix: Mat4<T>
+}
+
+/// minkowski sum of the convex shapes `a` and `b`, ie. a box summed with a sphere at the origin is a rounded box
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MinkowskiSum<A, B> {
+    /// first shape
+    pub a: A,
+    /// second shape swept over the first
+    pub b: B
+}
+
+/// trait for shapes which can test for overlap with shape `B`, implemented in both directions for every supported pair
+pub trait Intersects<B> {
+    /// returns true if `self` overlaps or touches `other`
+    fn intersects(&self, other: &B) -> bool;
+}
+
+/// trait for shapes which can test if point or shape `P` is entirely inside
+pub trait Contains<P> {
+    /// returns true if `other` is entirely inside `self`
+    fn contains(&self, other: &P) -> bool;
+}
+
+/// trait for shapes which can find the closest point on the shape to a point `V`
+pub trait ClosestPoint<V> {
+    /// returns the closest point on `self` to `p`, see the shapes `closest_point` for whether this is on the surface or the volume
+    fn closest_point(&self, p: V) -> V;
+}
+
+/// trait for shapes which can find the distance from a point `V`
+pub trait Distance<T, V> {
+    /// returns the distance from `p` to `self`, see the shapes `distance` for whether this is signed or to the surface or the volume
+    fn distance(&self, p: V) -> T;
+}
+
+/// trait for shapes which have an axis aligned bounding box, used to build a `Bvh` over any collection of shapes
+pub trait Bounded<T, V> {
+    /// returns the aabb which bounds `self`
+    fn aabb(&self) -> Aabb<T, V>;
+}
+
+/// trait for convex shapes which can find their furthest point in a direction, used by gjk and epa to work on implicit shapes
+/// without tessellating them, implemented for vertex lists, the convex primitives, `Transformed` and `MinkowskiSum`
+pub trait Support<V> {
+    /// returns the point on `self` furthest along direction `dir`, `dir` does not need to be normalized
+    fn support(&self, dir: V) -> V;
+}
+
+/// returns the corners of the unit cube with extents -1 to 1, in the same order as `Aabb::corners`
+fn unit_cube_corners<T: SignedNumber>() -> [Vec3<T>; 8] {
+    let (l, h) = (T::minus_one(), T::one());
+    [
+        Vec3::new(l, l, l),
+        Vec3::new(h, l, l),
+        Vec3::new(h, h, l),
+        Vec3::new(l, h, l),
+        Vec3::new(l, l, h),
+        Vec3::new(h, l, h),
+        Vec3::new(h, h, h),
+        Vec3::new(l, h, h)
+    ]
+}
+
+/// returns `v` normalized, or zero if `v` has no length so supports of rounded shapes stay finite
+fn normalize_or_zero<T: Float + FloatOps<T>, V: VecN<T> + VecFloatOps<T>>(v: V) -> V {
+    let l2 = dot(v, v);
+    if l2 > T::small_epsilon() {
+        v / T::sqrt(l2)
+    }
+    else {
+        V::zero()
+    }
+}
+
+/// returns the tight aabb of a 3D convex shape from its support points along each axis
+fn support_aabb<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, S: Support<Vec3<T>> + ?Sized>(shape: &S) -> Aabb<T> {
+    let axes = [Vec3::unit_x(), Vec3::unit_y(), Vec3::unit_z()];
+    let mut min = Vec3::zero();
+    let mut max = Vec3::zero();
+    for (i, axis) in axes.iter().enumerate() {
+        min[i] = shape.support(-*axis)[i];
+        max[i] = shape.support(*axis)[i];
+    }
+    Aabb::new(min, max)
+}
+
+//
+// Aabb
+//
+
+impl<T, V> Aabb<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    /// constructs an aabb from `min` and `max` corners
+    pub fn new(min: V, max: V) -> Self {
+        Aabb {
+            min,
+            max,
+            phantom: PhantomData
+        }
+    }
+
+    /// constructs an inverted aabb which contains nothing, merging or expanding it with anything returns the other
+    pub fn empty() -> Self {
+        Aabb {
+            min: V::max_value(),
+            max: V::min_value(),
+            phantom: PhantomData
+        }
+    }
+
+    /// constructs an aabb from `center` position and half size `extents`
+    pub fn from_center_extents(center: V, extents: V) -> Self {
+        Aabb {
+            min: center - extents,
+            max: center + extents,
+            phantom: PhantomData
+        }
+    }
+
+    /// constructs the smallest aabb which contains all `points`, returns `empty()` if `points` is empty
+    pub fn from_points(points: &[V]) -> Self {
+        points.iter().fold(Self::empty(), |aabb, p| aabb.expand(*p))
+    }
+
+    /// returns true if the aabb is inverted on any axis and contains nothing
+    pub fn is_empty(&self) -> bool {
+        for i in 0..V::len() {
+            if self.min[i] > self.max[i] {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// returns the centre position of the aabb
+    pub fn center(&self) -> V {
+        (self.min + self.max) * T::point_five()
+    }
+
+    /// returns the half size of the aabb along each axis
+    pub fn extents(&self) -> V {
+        (self.max - self.min) * T::point_five()
+    }
+
+    /// returns the full size of the aabb along each axis
+    pub fn size(&self) -> V {
+        self.max - self.min
+    }
+
+    /// returns the smallest aabb which contains `self` and `other`
+    pub fn merge(&self, other: &Self) -> Self {
+        Aabb {
+            min: V::min(self.min, other.min),
+            max: V::max(self.max, other.max),
+            phantom: PhantomData
+        }
+    }
+
+    /// returns the smallest aabb which contains `self` and point `p`
+    pub fn expand(&self, p: V) -> Self {
+        Aabb {
+            min: V::min(self.min, p),
+            max: V::max(self.max, p),
+            phantom: PhantomData
+        }
+    }
+
+    /// returns the aabb grown by `amount` in all directions, negative values shrink
+    pub fn grow(&self, amount: T) -> Self {
+        Aabb {
+            min: self.min - amount,
+            max: self.max + amount,
+            phantom: PhantomData
+        }
+    }
+
+    /// returns true if point `p` is inside the aabb
+    pub fn contains_point(&self, p: V) -> bool {
+        point_inside_aabb(p, self.min, self.max)
+    }
+
+    /// returns true if `other` is entirely inside the aabb
+    pub fn contains_aabb(&self, other: &Self) -> bool {
+        point_inside_aabb(other.min, self.min, self.max) && point_inside_aabb(other.max, self.min, self.max)
+    }
+
+    /// returns the closest point on the aabb to `p`, if `p` is inside it is returned unchanged
+    pub fn closest_point(&self, p: V) -> V {
+        closest_point_on_aabb(p, self.min, self.max)
+    }
+
+    /// returns the distance from point `p` to the aabb, 0 if `p` is inside
+    pub fn distance(&self, p: V) -> T {
+        point_aabb_distance(p, self.min, self.max)
+    }
+
+    /// returns true if the aabb overlaps `other`
+    pub fn intersects_aabb(&self, other: &Self) -> bool {
+        aabb_vs_aabb(self.min, self.max, other.min, other.max)
+    }
+
+    /// returns true if the aabb overlaps `sphere`
+    pub fn intersects_sphere(&self, sphere: &Sphere<T, V>) -> bool {
+        aabb_vs_sphere(self.min, self.max, sphere.center, sphere.radius)
+    }
+}
+
+impl<T> Aabb<T, Vec2<T>> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// returns the 4 corners of the 2D aabb in counter clockwise order starting at `min`
+    pub fn corners(&self) -> [Vec2<T>; 4] {
+        [
+            self.min,
+            Vec2::new(self.max.x, self.min.y),
+            self.max,
+            Vec2::new(self.min.x, self.max.y)
+        ]
+    }
+}
+
+impl<T> Aabb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// returns the 8 corners of the 3D aabb, the `min.z` face first counter clockwise from `min` then the `max.z` face
+    pub fn corners(&self) -> [Vec3<T>; 8] {
+        let c = self.center();
+        let e = self.extents();
+        unit_cube_corners().map(|u| c + u * e)
+    }
+
+    /// returns the classification of the aabb vs `plane`
+    pub fn classify_plane(&self, plane: &Plane<T>) -> Classification {
+        aabb_vs_plane(self.min, self.max, plane.point(), plane.normal)
+    }
+
+    /// returns true if the aabb overlaps `obb`
+    pub fn intersects_obb(&self, obb: &Obb<T>) -> bool {
+        aabb_vs_obb::<T, Vec3<T>, Mat4<T>>(self.min, self.max, obb.get_matrix())
+    }
+}
+
+//
+// Sphere
+//
+
+impl<T, V> Sphere<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    /// constructs a sphere from `center` position and `radius`
+    pub fn new(center: V, radius: T) -> Self {
+        Sphere {
+            center,
+            radius
+        }
+    }
+
+    /// returns the centre position of the sphere
+    pub fn center(&self) -> V {
+        self.center
+    }
+
+    /// returns the half size of the sphere bounds along each axis
+    pub fn extents(&self) -> V {
+        V::zero() + self.radius
+    }
+
+    /// returns the aabb which bounds the sphere
+    pub fn aabb(&self) -> Aabb<T, V> {
+        Aabb::from_center_extents(self.center, self.extents())
+    }
+
+    /// returns the smallest sphere which contains `self` and `other`
+    pub fn merge(&self, other: &Self) -> Self {
+        let d = dist(self.center, other.center);
+        if d + other.radius <= self.radius {
+            *self
+        }
+        else if d + self.radius <= other.radius {
+            *other
+        }
+        else {
+            let radius = (d + self.radius + other.radius) * T::point_five();
+            let v = (other.center - self.center) / d;
+            Sphere {
+                center: self.center + v * (radius - self.radius),
+                radius
+            }
+        }
+    }
+
+    /// returns the smallest sphere which contains `self` and point `p`
+    pub fn expand(&self, p: V) -> Self {
+        self.merge(&Sphere::new(p, T::zero()))
+    }
+
+    /// returns true if point `p` is inside the sphere
+    pub fn contains_point(&self, p: V) -> bool {
+        point_inside_sphere(p, self.center, self.radius)
+    }
+
+    /// returns the closest point on the surface of the sphere to `p`
+    pub fn closest_point(&self, p: V) -> V {
+        closest_point_on_sphere(p, self.center, self.radius)
+    }
+
+    /// returns the unsigned distance from point `p` to the surface of the sphere
+    pub fn distance(&self, p: V) -> T {
+        point_sphere_distance(p, self.center, self.radius)
+    }
+
+    /// returns true if the sphere overlaps `other`
+    pub fn intersects_sphere(&self, other: &Self) -> bool {
+        sphere_vs_sphere(self.center, self.radius, other.center, other.radius)
+    }
+
+    /// returns true if the sphere overlaps `aabb`
+    pub fn intersects_aabb(&self, aabb: &Aabb<T, V>) -> bool {
+        aabb_vs_sphere(aabb.min, aabb.max, self.center, self.radius)
+    }
+
+    /// returns true if the sphere overlaps `capsule`
+    pub fn intersects_capsule(&self, capsule: &Capsule<T, V>) -> bool {
+        sphere_vs_capsule(self.center, self.radius, capsule.p0, capsule.p1, capsule.radius)
+    }
+}
+
+impl<T> Sphere<T, Vec3<T>> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// returns the classification of the sphere vs `plane`
+    pub fn classify_plane(&self, plane: &Plane<T>) -> Classification {
+        sphere_vs_plane(self.center, self.radius, plane.point(), plane.normal)
+    }
+
+    /// returns true if the sphere overlaps `obb`
+    pub fn intersects_obb(&self, obb: &Obb<T>) -> bool {
+        sphere_vs_obb(self.center, self.radius, obb.get_matrix())
+    }
+}
+
+//
+// Capsule
+//
+
+impl<T, V> Capsule<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    /// constructs a capsule from axis `p0-p1` and `radius`
+    pub fn new(p0: V, p1: V, radius: T) -> Self {
+        Capsule {
+            p0,
+            p1,
+            radius
+        }
+    }
+
+    /// returns the centre position of the capsule
+    pub fn center(&self) -> V {
+        (self.p0 + self.p1) * T::point_five()
+    }
+
+    /// returns the half size of the capsule bounds along each axis
+    pub fn extents(&self) -> V {
+        V::abs(self.p1 - self.p0) * T::point_five() + self.radius
+    }
+
+    /// returns the aabb which bounds the capsule
+    pub fn aabb(&self) -> Aabb<T, V> {
+        Aabb::from_center_extents(self.center(), self.extents())
+    }
+
+    /// returns true if point `p` is inside the capsule
+    pub fn contains_point(&self, p: V) -> bool {
+        point_line_segment_distance(p, self.p0, self.p1) < self.radius
+    }
+
+    /// returns the closest point on the surface of the capsule to `p`
+    pub fn closest_point(&self, p: V) -> V {
+        let cp = closest_point_on_line_segment(p, self.p0, self.p1);
+        closest_point_on_sphere(p, cp, self.radius)
+    }
+
+    /// returns the unsigned distance from point `p` to the surface of the capsule
+    pub fn distance(&self, p: V) -> T {
+        T::abs(point_line_segment_distance(p, self.p0, self.p1) - self.radius)
+    }
+
+    /// returns true if the capsule overlaps `other`
+    pub fn intersects_capsule(&self, other: &Self) -> bool {
+        capsule_vs_capsule(self.p0, self.p1, self.radius, other.p0, other.p1, other.radius)
+    }
+
+    /// returns true if the capsule overlaps `sphere`
+    pub fn intersects_sphere(&self, sphere: &Sphere<T, V>) -> bool {
+        sphere_vs_capsule(sphere.center, sphere.radius, self.p0, self.p1, self.radius)
+    }
+}
+
+impl<T> Capsule<T, Vec3<T>> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// returns the classification of the capsule vs `plane`
+    pub fn classify_plane(&self, plane: &Plane<T>) -> Classification {
+        capsule_vs_plane(self.p0, self.p1, self.radius, plane.point(), plane.normal)
+    }
+}
+
+//
+// Ray
+//
+
+impl<T, V> Ray<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    /// constructs a ray from `origin` and `direction`
+    pub fn new(origin: V, direction: V) -> Self {
+        Ray {
+            origin,
+            direction,
+            phantom: PhantomData
+        }
+    }
+
+    /// constructs a ray starting at `origin` pointing toward `target` with a normalized direction
+    pub fn from_points(origin: V, target: V) -> Self {
+        Ray {
+            origin,
+            direction: normalize(target - origin),
+            phantom: PhantomData
+        }
+    }
+
+    /// returns the point at distance `t` along the ray
+    pub fn at(&self, t: T) -> V {
+        self.origin + self.direction * t
+    }
+
+    /// returns the closest point on the ray to `p`
+    pub fn closest_point(&self, p: V) -> V {
+        closest_point_on_ray(p, self.origin, self.direction)
+    }
+
+    /// returns the distance from point `p` to the closest point on the ray
+    pub fn distance(&self, p: V) -> T {
+        dist(p, self.closest_point(p))
+    }
+
+    /// returns the intersection point of the ray with `sphere`
+    pub fn intersect_sphere(&self, sphere: &Sphere<T, V>) -> Option<V> {
+        ray_vs_sphere(self.origin, self.direction, sphere.center, sphere.radius)
+    }
+
+    /// returns the intersection point of the ray with `aabb`
+    pub fn intersect_aabb(&self, aabb: &Aabb<T, V>) -> Option<V> {
+        ray_vs_aabb(self.origin, self.direction, aabb.min, aabb.max)
+    }
+
+    /// returns the hit of the ray with `sphere` no further than `t_max` along the ray
+    pub fn hit_sphere(&self, sphere: &Sphere<T, V>, t_max: T) -> Option<RayHit<T, V>> {
+        ray_vs_sphere_hit(self.origin, self.direction, sphere.center, sphere.radius, t_max)
+    }
+
+    /// returns the hit of the ray with `aabb` no further than `t_max` along the ray
+    pub fn hit_aabb(&self, aabb: &Aabb<T, V>, t_max: T) -> Option<RayHit<T, V>> {
+        ray_vs_aabb_hit(self.origin, self.direction, aabb.min, aabb.max, t_max)
+    }
+}
+
+impl<T> Ray<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// returns the intersection point of the ray with `plane`
+    pub fn intersect_plane(&self, plane: &Plane<T>) -> Option<Vec3<T>> {
+        ray_vs_plane(self.origin, self.direction, plane.point(), plane.normal)
+    }
+
+    /// returns the intersection point of the ray with `triangle`
+    pub fn intersect_triangle(&self, triangle: &Triangle<T>) -> Option<Vec3<T>> {
+        ray_vs_triangle(self.origin, self.direction, triangle.v0, triangle.v1, triangle.v2)
+    }
+
+    /// returns the intersection point of the ray with `obb`
+    pub fn intersect_obb(&self, obb: &Obb<T>) -> Option<Vec3<T>> {
+        ray_vs_obb(self.origin, self.direction, obb.get_matrix())
+    }
+
+    /// returns the intersection point of the ray with `capsule`
+    pub fn intersect_capsule(&self, capsule: &Capsule<T>) -> Option<Vec3<T>> {
+        ray_vs_capsule(self.origin, self.direction, capsule.p0, capsule.p1, capsule.radius)
+    }
+
+    /// returns the hit of the ray with `triangle` no further than `t_max` along the ray, including barycentrics
+    pub fn hit_triangle(&self, triangle: &Triangle<T>, t_max: T) -> Option<RayHit<T, Vec3<T>>> {
+        ray_vs_triangle_hit(self.origin, self.direction, triangle.v0, triangle.v1, triangle.v2, t_max)
+    }
+
+    /// returns the hit of the ray with `obb` no further than `t_max` along the ray
+    pub fn hit_obb(&self, obb: &Obb<T>, t_max: T) -> Option<RayHit<T, Vec3<T>>> {
+        ray_vs_obb_hit(self.origin, self.direction, obb.get_matrix(), t_max)
+    }
+
+    /// returns the hit of the ray with `capsule` no further than `t_max` along the ray
+    pub fn hit_capsule(&self, capsule: &Capsule<T>, t_max: T) -> Option<RayHit<T, Vec3<T>>> {
+        ray_vs_capsule_hit(self.origin, self.direction, capsule.p0, capsule.p1, capsule.radius, t_max)
+    }
+
+    /// returns the intersection point of the ray with `cylinder`
+    pub fn intersect_cylinder(&self, cylinder: &Cylinder<T>) -> Option<Vec3<T>> {
+        ray_vs_cylinder(self.origin, self.direction, cylinder.p0, cylinder.p1, cylinder.radius)
+    }
+
+    /// returns the hit of the ray with `cylinder` no further than `t_max` along the ray
+    pub fn hit_cylinder(&self, cylinder: &Cylinder<T>, t_max: T) -> Option<RayHit<T, Vec3<T>>> {
+        ray_vs_cylinder_hit(self.origin, self.direction, cylinder.p0, cylinder.p1, cylinder.radius, t_max)
+    }
+}
+
+//
+// Triangle
+//
+
+impl<T, V> Triangle<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    /// constructs a triangle from vertices `v0-v1-v2`
+    pub fn new(v0: V, v1: V, v2: V) -> Self {
+        Triangle {
+            v0,
+            v1,
+            v2,
+            phantom: PhantomData
+        }
+    }
+
+    /// returns the centroid of the triangle
+    pub fn center(&self) -> V {
+        (self.v0 + self.v1 + self.v2) / T::three()
+    }
+
+    /// returns the half size of the triangle bounds along each axis
+    pub fn extents(&self) -> V {
+        self.aabb().extents()
+    }
+
+    /// returns the aabb which bounds the triangle
+    pub fn aabb(&self) -> Aabb<T, V> {
+        Aabb::from_points(&[self.v0, self.v1, self.v2])
+    }
+
+    /// returns the 3 vertices of the triangle
+    pub fn corners(&self) -> [V; 3] {
+        [self.v0, self.v1, self.v2]
+    }
+
+    /// returns the barycentric coordinates of point `p` with respect to `v0`, `v1` and `v2`
+    pub fn barycentric(&self, p: V) -> (T, T, T) {
+        barycentric(p, self.v0, self.v1, self.v2)
+    }
+
+    /// returns true if point `p` is inside the triangle
+    pub fn contains_point(&self, p: V) -> bool {
+        point_inside_triangle(p, self.v0, self.v1, self.v2)
+    }
+
+    /// returns the closest point on the triangle to `p`
+    pub fn closest_point(&self, p: V) -> V {
+        closest_point_on_triangle(p, self.v0, self.v1, self.v2)
+    }
+
+    /// returns the distance from point `p` to the triangle
+    pub fn distance(&self, p: V) -> T {
+        point_triangle_distance(p, self.v0, self.v1, self.v2)
+    }
+}
+
+impl<T> Triangle<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// returns the unit normal of the triangle with counter clockwise winding
+    pub fn normal(&self) -> Vec3<T> {
+        get_triangle_normal(self.v0, self.v1, self.v2)
+    }
+
+    /// returns the plane the triangle lies on
+    pub fn plane(&self) -> Plane<T> {
+        Plane::from_normal_and_point(self.v0, self.normal())
+    }
+}
+
+//
+// Obb
+//
+
+impl<T> Obb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// constructs an obb from `center`, half size `extents` and `rotation`
+    pub fn new(center: Vec3<T>, extents: Vec3<T>, rotation: Mat3<T>) -> Self {
+        Obb {
+            center,
+            extents,
+            rotation
+        }
+    }
+
+    /// constructs an axis aligned obb which matches `aabb`
+    pub fn from_aabb(aabb: &Aabb<T>) -> Self {
+        Obb {
+            center: aabb.center(),
+            extents: aabb.extents(),
+            rotation: Mat3::identity()
+        }
+    }
+
+    /// constructs an obb from matrix `mat` which transforms a unit cube with extents -1 to 1 into the obb, shear is discarded
+    pub fn from_matrix(mat: Mat4<T>) -> Self {
+        let (center, rotation, scale) = mat.decompose();
+        Obb {
+            center,
+            extents: Vec3::abs(scale),
+            rotation: rotation.get_matrix()
+        }
+    }
+
+    /// returns the matrix which transforms a unit cube with extents -1 to 1 into the obb, as used by the obb free functions
+    pub fn get_matrix(&self) -> Mat4<T> {
+        let mut m = Mat34::from(self.rotation);
+        m.set_column(0, m.get_column(0) * self.extents.x);
+        m.set_column(1, m.get_column(1) * self.extents.y);
+        m.set_column(2, m.get_column(2) * self.extents.z);
+        m.set_column(3, self.center);
+        Mat4::from(m)
+    }
+
+    /// returns the centre position of the obb
+    pub fn center(&self) -> Vec3<T> {
+        self.center
+    }
+
+    /// returns the half size of the obb along each local axis
+    pub fn extents(&self) -> Vec3<T> {
+        self.extents
+    }
+
+    /// returns the 8 corners of the obb in the same order as `Aabb::corners`
+    pub fn corners(&self) -> [Vec3<T>; 8] {
+        let m = self.get_matrix();
+        unit_cube_corners().map(|u| m * u)
+    }
+
+    /// returns the aabb which bounds the obb
+    pub fn aabb(&self) -> Aabb<T> {
+        let r = self.rotation;
+        let e = Vec3::new(
+            dot(Vec3::abs(r.get_row(0)), self.extents),
+            dot(Vec3::abs(r.get_row(1)), self.extents),
+            dot(Vec3::abs(r.get_row(2)), self.extents)
+        );
+        Aabb::from_center_extents(self.center, e)
+    }
+
+    /// returns true if point `p` is inside the obb
+    pub fn contains_point(&self, p: Vec3<T>) -> bool {
+        point_inside_obb(p, self.get_matrix())
+    }
+
+    /// returns the closest point on the obb to `p`, if `p` is inside it is returned unchanged
+    pub fn closest_point(&self, p: Vec3<T>) -> Vec3<T> {
+        closest_point_on_obb(p, self.get_matrix())
+    }
+
+    /// returns the distance from point `p` to the obb, 0 if `p` is inside
+    pub fn distance(&self, p: Vec3<T>) -> T {
+        point_obb_distance(p, self.get_matrix())
+    }
+
+    /// returns true if the obb overlaps `other`
+    pub fn intersects_obb(&self, other: &Self) -> bool {
+        obb_vs_obb::<T, Vec3<T>, Mat4<T>>(self.get_matrix(), other.get_matrix())
+    }
+
+    /// returns true if the obb overlaps `aabb`
+    pub fn intersects_aabb(&self, aabb: &Aabb<T>) -> bool {
+        aabb.intersects_obb(self)
+    }
+
+    /// returns true if the obb overlaps `sphere`
+    pub fn intersects_sphere(&self, sphere: &Sphere<T>) -> bool {
+        sphere.intersects_obb(self)
+    }
+}
+
+//
+// Plane
+//
+
+impl<T> Plane<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// constructs a plane from unit `normal` and plane constant `distance`
+    pub fn new(normal: Vec3<T>, distance: T) -> Self {
+        Plane {
+            normal,
+            distance
+        }
+    }
+
+    /// constructs a plane from point on plane `x` and unit normal `n`
+    pub fn from_normal_and_point(x: Vec3<T>, n: Vec3<T>) -> Self {
+        Plane::from(plane_from_normal_and_point(x, n))
+    }
+
+    /// constructs the plane which passes through points `t1-t2-t3` with counter clockwise winding
+    pub fn from_points(t1: Vec3<T>, t2: Vec3<T>, t3: Vec3<T>) -> Self {
+        Plane::from_normal_and_point(t1, get_triangle_normal(t1, t2, t3))
+    }
+
+    /// returns the point on the plane closest to the origin
+    pub fn point(&self) -> Vec3<T> {
+        self.normal * -self.distance
+    }
+
+    /// returns the signed distance from point `p` to the plane, positive in front of the plane
+    pub fn distance(&self, p: Vec3<T>) -> T {
+        point_plane_distance(p, self.point(), self.normal)
+    }
+
+    /// returns the closest point on the plane to `p`
+    pub fn closest_point(&self, p: Vec3<T>) -> Vec3<T> {
+        closest_point_on_plane(p, self.point(), self.normal)
+    }
+
+    /// returns the classification of point `p` vs the plane
+    pub fn classify_point(&self, p: Vec3<T>) -> Classification {
+        point_vs_plane(p, self.point(), self.normal)
+    }
+}
+
+/// constructs from a plane packed into `Vec4` as `.xyz = normal, .w = distance`
+impl<T> From<Vec4<T>> for Plane<T> where T: Number {
+    fn from(other: Vec4<T>) -> Self {
+        Plane {
+            normal: Vec3::from(other),
+            distance: other.w
+        }
+    }
+}
+
+/// packs a plane into `Vec4` as `.xyz = normal, .w = distance`
+impl<T> From<Plane<T>> for Vec4<T> where T: Number {
+    fn from(other: Plane<T>) -> Self {
+        Vec4::from((other.normal, other.distance))
+    }
+}
+
+//
+// Frustum
+//
+
+impl<T> Frustum<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// constructs a frustum from a `view_projection` matrix with -1 to 1 depth range as produced by `create_perspective_projection_lh_yup`
+    /// and `create_perspective_projection_rh_yup`, see `from_matrix_depth` for other ranges. `create_perspective_projection_rh_yup` maps near
+    /// to 1 with negative w so the volume is correct but opposite planes and corners swap places
+    pub fn from_matrix(view_projection: Mat4<T>) -> Self {
+        Self::from_matrix_depth(view_projection, DepthRange::NegOneToOne)
+    }
+
+    /// constructs a frustum from a `view_projection` matrix with the ndc depth range `depth`
+    pub fn from_matrix_depth(view_projection: Mat4<T>, depth: DepthRange) -> Self {
+        Frustum {
+            planes: view_projection.get_frustum_planes_depth(depth),
+            corners: view_projection.get_frustum_corners_depth(depth)
+        }
+    }
+
+    /// returns plane `index` ordered left, top, right, bottom, near, far
+    pub fn plane(&self, index: usize) -> Plane<T> {
+        Plane::from(self.planes[index])
+    }
+
+    /// returns the centre position of the frustum, the average of the corners
+    pub fn center(&self) -> Vec3<T> {
+        self.corners.iter().fold(Vec3::zero(), |c, p| c + *p) / (T::four() + T::four())
+    }
+
+    /// returns the half size of the frustum bounds along each axis
+    pub fn extents(&self) -> Vec3<T> {
+        self.aabb().extents()
+    }
+
+    /// returns the 8 corners of the frustum
+    pub fn corners(&self) -> [Vec3<T>; 8] {
+        self.corners
+    }
+
+    /// returns the aabb which bounds the frustum
+    pub fn aabb(&self) -> Aabb<T> {
+        Aabb::from_points(&self.corners)
+    }
+
+    /// returns true if point `p` is inside the frustum
+    pub fn contains_point(&self, p: Vec3<T>) -> bool {
+        point_inside_frustum(p, &self.planes)
+    }
+
+    /// returns true if `sphere` is inside or intersecting the frustum, this is conservative and can return true near corners
+    pub fn intersects_sphere(&self, sphere: &Sphere<T>) -> bool {
+        sphere_vs_frustum(sphere.center, sphere.radius, &self.planes)
+    }
+
+    /// returns true if `aabb` is inside or intersecting the frustum, this is conservative and can return true near corners
+    pub fn intersects_aabb(&self, aabb: &Aabb<T>) -> bool {
+        aabb_vs_frustum(aabb.center(), aabb.extents(), &self.planes)
+    }
+}
+
+impl<T> Cylinder<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// constructs a cylinder from the cap centres `p0-p1` and `radius`
+    pub fn new(p0: Vec3<T>, p1: Vec3<T>, radius: T) -> Self {
+        Cylinder {
+            p0,
+            p1,
+            radius
+        }
+    }
+
+    /// returns the centre position of the cylinder
+    pub fn center(&self) -> Vec3<T> {
+        (self.p0 + self.p1) * T::point_five()
+    }
+
+    /// returns the half size of the cylinder bounds along each axis
+    pub fn extents(&self) -> Vec3<T> {
+        self.aabb().extents()
+    }
+
+    /// returns the aabb which bounds the cylinder
+    pub fn aabb(&self) -> Aabb<T> {
+        support_aabb(self)
+    }
+
+    /// returns true if point `p` is inside the cylinder
+    pub fn contains_point(&self, p: Vec3<T>) -> bool {
+        let axis = self.p1 - self.p0;
+        let t = dot(p - self.p0, axis);
+        t >= T::zero() && t <= dot(axis, axis) && dist(closest_point_on_line_segment(p, self.p0, self.p1), p) <= self.radius
+    }
+}
+
+impl<T> Cone<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// constructs a cone with its tip at `position` facing `direction` with `height` and base `radius`
+    pub fn new(position: Vec3<T>, direction: Vec3<T>, height: T, radius: T) -> Self {
+        Cone {
+            position,
+            direction: normalize(direction),
+            height,
+            radius
+        }
+    }
+
+    /// returns the centre of the base of the cone
+    pub fn base(&self) -> Vec3<T> {
+        self.position + self.direction * self.height
+    }
+
+    /// returns the centre position of the cone bounds
+    pub fn center(&self) -> Vec3<T> {
+        self.aabb().center()
+    }
+
+    /// returns the half size of the cone bounds along each axis
+    pub fn extents(&self) -> Vec3<T> {
+        self.aabb().extents()
+    }
+
+    /// returns the aabb which bounds the cone
+    pub fn aabb(&self) -> Aabb<T> {
+        support_aabb(self)
+    }
+
+    /// returns true if point `p` is inside the cone
+    pub fn contains_point(&self, p: Vec3<T>) -> bool {
+        point_inside_cone(p, self.position, self.direction, self.height, self.radius)
+    }
+
+    /// returns the classification of the cone vs `plane`
+    pub fn classify_plane(&self, plane: &Plane<T>) -> Classification {
+        cone_vs_plane(self.position, self.direction, self.height, self.radius, plane.point(), plane.normal)
+    }
+}
+
+impl<T, S> Transformed<T, S> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, S: Support<Vec3<T>> {
+    /// constructs from a local space `shape` and the local to world `matrix`
+    pub fn new(shape: S, matrix: Mat4<T>) -> Self {
+        Transformed {
+            shape,
+            matrix
+        }
+    }
+
+    /// returns the aabb which bounds the transformed shape
+    pub fn aabb(&self) -> Aabb<T> {
+        support_aabb(self)
+    }
+}
+
+impl<A, B> MinkowskiSum<A, B> {
+    /// constructs the minkowski sum of shapes `a` and `b`
+    pub fn new(a: A, b: B) -> Self {
+        MinkowskiSum {
+            a,
+            b
+        }
+    }
+}
+
+/// displays like [min: [0.0, 0.0, 0.0], max: [1.0, 1.0, 1.0]]
+impl<T, V> std::fmt::Display for Aabb<T, V> where V: std::fmt::Display {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[min: {}, max: {}]", self.min, self.max)
+    }
+}
+
+/// displays like [center: [0.0, 0.0, 0.0], radius: 1.0]
+impl<T, V> std::fmt::Display for Sphere<T, V> where T: std::fmt::Display, V: std::fmt::Display {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[center: {}, radius: {}]", self.center, self.radius)
+    }
+}
+
+//
+// Intersects
+//
+
+impl<T, V> Intersects<Aabb<T, V>> for Aabb<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn intersects(&self, other: &Aabb<T, V>) -> bool {
+        self.intersects_aabb(other)
+    }
+}
+
+impl<T, V> Intersects<Sphere<T, V>> for Aabb<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn intersects(&self, other: &Sphere<T, V>) -> bool {
+        self.intersects_sphere(other)
+    }
+}
+
+impl<T, V> Intersects<Aabb<T, V>> for Sphere<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn intersects(&self, other: &Aabb<T, V>) -> bool {
+        self.intersects_aabb(other)
+    }
+}
+
+impl<T, V> Intersects<Sphere<T, V>> for Sphere<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn intersects(&self, other: &Sphere<T, V>) -> bool {
+        self.intersects_sphere(other)
+    }
+}
+
+impl<T, V> Intersects<Capsule<T, V>> for Sphere<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn intersects(&self, other: &Capsule<T, V>) -> bool {
+        self.intersects_capsule(other)
+    }
+}
+
+impl<T, V> Intersects<Sphere<T, V>> for Capsule<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn intersects(&self, other: &Sphere<T, V>) -> bool {
+        self.intersects_sphere(other)
+    }
+}
+
+impl<T, V> Intersects<Capsule<T, V>> for Capsule<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn intersects(&self, other: &Capsule<T, V>) -> bool {
+        self.intersects_capsule(other)
+    }
+}
+
+impl<T, V> Intersects<Sphere<T, V>> for Ray<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn intersects(&self, other: &Sphere<T, V>) -> bool {
+        self.intersect_sphere(other).is_some()
+    }
+}
+
+impl<T, V> Intersects<Ray<T, V>> for Sphere<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn intersects(&self, other: &Ray<T, V>) -> bool {
+        other.intersects(self)
+    }
+}
+
+impl<T, V> Intersects<Aabb<T, V>> for Ray<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn intersects(&self, other: &Aabb<T, V>) -> bool {
+        self.intersect_aabb(other).is_some()
+    }
+}
+
+impl<T, V> Intersects<Ray<T, V>> for Aabb<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn intersects(&self, other: &Ray<T, V>) -> bool {
+        other.intersects(self)
+    }
+}
+
+impl<T> Intersects<Obb<T>> for Aabb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Obb<T>) -> bool {
+        self.intersects_obb(other)
+    }
+}
+
+impl<T> Intersects<Aabb<T>> for Obb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Aabb<T>) -> bool {
+        self.intersects_aabb(other)
+    }
+}
+
+impl<T> Intersects<Obb<T>> for Sphere<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Obb<T>) -> bool {
+        self.intersects_obb(other)
+    }
+}
+
+impl<T> Intersects<Sphere<T>> for Obb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Sphere<T>) -> bool {
+        self.intersects_sphere(other)
+    }
+}
+
+impl<T> Intersects<Obb<T>> for Obb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Obb<T>) -> bool {
+        self.intersects_obb(other)
+    }
+}
+
+impl<T> Intersects<Sphere<T>> for Frustum<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Sphere<T>) -> bool {
+        self.intersects_sphere(other)
+    }
+}
+
+impl<T> Intersects<Frustum<T>> for Sphere<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Frustum<T>) -> bool {
+        other.intersects_sphere(self)
+    }
+}
+
+impl<T> Intersects<Aabb<T>> for Frustum<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Aabb<T>) -> bool {
+        self.intersects_aabb(other)
+    }
+}
+
+impl<T> Intersects<Frustum<T>> for Aabb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Frustum<T>) -> bool {
+        other.intersects_aabb(self)
+    }
+}
+
+impl<T> Intersects<Plane<T>> for Aabb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Plane<T>) -> bool {
+        self.classify_plane(other) == Classification::Intersects
+    }
+}
+
+impl<T> Intersects<Aabb<T>> for Plane<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Aabb<T>) -> bool {
+        other.intersects(self)
+    }
+}
+
+impl<T> Intersects<Plane<T>> for Sphere<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Plane<T>) -> bool {
+        self.classify_plane(other) == Classification::Intersects
+    }
+}
+
+impl<T> Intersects<Sphere<T>> for Plane<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Sphere<T>) -> bool {
+        other.intersects(self)
+    }
+}
+
+impl<T> Intersects<Plane<T>> for Capsule<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Plane<T>) -> bool {
+        self.classify_plane(other) == Classification::Intersects
+    }
+}
+
+impl<T> Intersects<Capsule<T>> for Plane<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Capsule<T>) -> bool {
+        other.intersects(self)
+    }
+}
+
+impl<T> Intersects<Plane<T>> for Ray<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Plane<T>) -> bool {
+        self.intersect_plane(other).is_some()
+    }
+}
+
+impl<T> Intersects<Ray<T>> for Plane<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Ray<T>) -> bool {
+        other.intersects(self)
+    }
+}
+
+impl<T> Intersects<Triangle<T>> for Ray<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Triangle<T>) -> bool {
+        self.intersect_triangle(other).is_some()
+    }
+}
+
+impl<T> Intersects<Ray<T>> for Triangle<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Ray<T>) -> bool {
+        other.intersects(self)
+    }
+}
+
+impl<T> Intersects<Obb<T>> for Ray<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Obb<T>) -> bool {
+        self.intersect_obb(other).is_some()
+    }
+}
+
+impl<T> Intersects<Ray<T>> for Obb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Ray<T>) -> bool {
+        other.intersects(self)
+    }
+}
+
+impl<T> Intersects<Capsule<T>> for Ray<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Capsule<T>) -> bool {
+        self.intersect_capsule(other).is_some()
+    }
+}
+
+impl<T> Intersects<Ray<T>> for Capsule<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Ray<T>) -> bool {
+        other.intersects(self)
+    }
+}
+
+impl<T> Intersects<Cylinder<T>> for Ray<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Cylinder<T>) -> bool {
+        self.intersect_cylinder(other).is_some()
+    }
+}
+
+impl<T> Intersects<Ray<T>> for Cylinder<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Ray<T>) -> bool {
+        other.intersects(self)
+    }
+}
+
+impl<T> Intersects<Plane<T>> for Cone<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Plane<T>) -> bool {
+        self.classify_plane(other) == Classification::Intersects
+    }
+}
+
+impl<T> Intersects<Cone<T>> for Plane<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn intersects(&self, other: &Cone<T>) -> bool {
+        other.intersects(self)
+    }
+}
+
+//
+// Contains
+//
+
+impl<T, V> Contains<V> for Aabb<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn contains(&self, other: &V) -> bool {
+        self.contains_point(*other)
+    }
+}
+
+impl<T, V> Contains<Aabb<T, V>> for Aabb<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn contains(&self, other: &Aabb<T, V>) -> bool {
+        self.contains_aabb(other)
+    }
+}
+
+impl<T, V> Contains<Sphere<T, V>> for Aabb<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn contains(&self, other: &Sphere<T, V>) -> bool {
+        self.contains_aabb(&other.aabb())
+    }
+}
+
+impl<T, V> Contains<V> for Sphere<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn contains(&self, other: &V) -> bool {
+        self.contains_point(*other)
+    }
+}
+
+impl<T, V> Contains<Sphere<T, V>> for Sphere<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn contains(&self, other: &Sphere<T, V>) -> bool {
+        dist(self.center, other.center) + other.radius <= self.radius
+    }
+}
+
+impl<T, V> Contains<V> for Capsule<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn contains(&self, other: &V) -> bool {
+        self.contains_point(*other)
+    }
+}
+
+impl<T, V> Contains<V> for Triangle<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn contains(&self, other: &V) -> bool {
+        self.contains_point(*other)
+    }
+}
+
+impl<T> Contains<Vec3<T>> for Obb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn contains(&self, other: &Vec3<T>) -> bool {
+        self.contains_point(*other)
+    }
+}
+
+impl<T> Contains<Vec3<T>> for Cylinder<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn contains(&self, other: &Vec3<T>) -> bool {
+        self.contains_point(*other)
+    }
+}
+
+impl<T> Contains<Vec3<T>> for Cone<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn contains(&self, other: &Vec3<T>) -> bool {
+        self.contains_point(*other)
+    }
+}
+
+impl<T> Contains<Vec3<T>> for Frustum<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn contains(&self, other: &Vec3<T>) -> bool {
+        self.contains_point(*other)
+    }
+}
+
+impl<T> Contains<Sphere<T>> for Frustum<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn contains(&self, other: &Sphere<T>) -> bool {
+        self.planes.iter().all(|p| dot(Vec3::from(*p), other.center) + p.w <= -other.radius)
+    }
+}
+
+impl<T> Contains<Aabb<T>> for Frustum<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn contains(&self, other: &Aabb<T>) -> bool {
+        other.corners().iter().all(|c| self.contains_point(*c))
+    }
+}
+
+//
+// ClosestPoint and Distance
+//
+
+macro_rules! point_query_impl {
+    ($Shape:ident) => {
+        impl<T, V> ClosestPoint<V> for $Shape<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+            fn closest_point(&self, p: V) -> V {
+                $Shape::closest_point(self, p)
+            }
+        }
+
+        impl<T, V> Distance<T, V> for $Shape<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+            fn distance(&self, p: V) -> T {
+                $Shape::distance(self, p)
+            }
+        }
+    }
+}
+
+point_query_impl!(Aabb);
+point_query_impl!(Sphere);
+point_query_impl!(Capsule);
+point_query_impl!(Ray);
+point_query_impl!(Triangle);
+
+//
+// Bounded
+//
+
+impl<T, V> Bounded<T, V> for Aabb<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn aabb(&self) -> Aabb<T, V> {
+        *self
+    }
+}
+
+macro_rules! bounded_impl {
+    ($Shape:ident) => {
+        impl<T, V> Bounded<T, V> for $Shape<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+            fn aabb(&self) -> Aabb<T, V> {
+                $Shape::aabb(self)
+            }
+        }
+    };
+    ($Shape:ident, 3D) => {
+        impl<T> Bounded<T, Vec3<T>> for $Shape<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+            fn aabb(&self) -> Aabb<T> {
+                $Shape::aabb(self)
+            }
+        }
+    }
+}
+
+bounded_impl!(Sphere);
+bounded_impl!(Capsule);
+bounded_impl!(Triangle);
+bounded_impl!(Obb, 3D);
+bounded_impl!(Frustum, 3D);
+bounded_impl!(Cylinder, 3D);
+bounded_impl!(Cone, 3D);
+
+impl<T> ClosestPoint<Vec3<T>> for Obb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn closest_point(&self, p: Vec3<T>) -> Vec3<T> {
+        Obb::closest_point(self, p)
+    }
+}
+
+impl<T> Distance<T, Vec3<T>> for Obb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn distance(&self, p: Vec3<T>) -> T {
+        Obb::distance(self, p)
+    }
+}
+
+impl<T> ClosestPoint<Vec3<T>> for Plane<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn closest_point(&self, p: Vec3<T>) -> Vec3<T> {
+        Plane::closest_point(self, p)
+    }
+}
+
+impl<T> Distance<T, Vec3<T>> for Plane<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn distance(&self, p: Vec3<T>) -> T {
+        Plane::distance(self, p)
+    }
+}
+
+//
+// Support
+//
+
+/// support of the shape behind a reference, so gjk can borrow its inputs
+impl<V, S> Support<V> for &S where S: Support<V> + ?Sized {
+    fn support(&self, dir: V) -> V {
+        (**self).support(dir)
+    }
+}
+
+macro_rules! vertex_support_impl {
+    ($V:ident) => {
+        /// support of the convex hull of a vertex list
+        impl<T> Support<$V<T>> for [$V<T>] where T: Float {
+            fn support(&self, dir: $V<T>) -> $V<T> {
+                furthest_point(dir, self)
+            }
+        }
+
+        /// support of the convex hull of a vertex list
+        impl<T> Support<$V<T>> for Vec<$V<T>> where T: Float {
+            fn support(&self, dir: $V<T>) -> $V<T> {
+                furthest_point(dir, self)
+            }
+        }
+
+        /// support of the convex hull of a vertex array
+        impl<T, const N: usize> Support<$V<T>> for [$V<T>; N] where T: Float {
+            fn support(&self, dir: $V<T>) -> $V<T> {
+                furthest_point(dir, self)
+            }
+        }
+    }
+}
+
+vertex_support_impl!(Vec2);
+vertex_support_impl!(Vec3);
+
+impl<T, V> Support<V> for Aabb<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn support(&self, dir: V) -> V {
+        self.center() + self.extents() * V::signum(dir)
+    }
+}
+
+impl<T, V> Support<V> for Sphere<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn support(&self, dir: V) -> V {
+        self.center + normalize_or_zero(dir) * self.radius
+    }
+}
+
+impl<T, V> Support<V> for Capsule<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn support(&self, dir: V) -> V {
+        let p = if dot(dir, self.p1 - self.p0) > T::zero() { self.p1 } else { self.p0 };
+        p + normalize_or_zero(dir) * self.radius
+    }
+}
+
+impl<T, V> Support<V> for Triangle<T, V> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, V: VecN<T> + VecFloatOps<T> + NumberOps<T> + SignedNumberOps<T> + FloatOps<T> {
+    fn support(&self, dir: V) -> V {
+        furthest_point(dir, &[self.v0, self.v1, self.v2])
+    }
+}
+
+impl<T> Support<Vec3<T>> for Obb<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn support(&self, dir: Vec3<T>) -> Vec3<T> {
+        let local = self.rotation.transpose() * dir;
+        self.center + self.rotation * (self.extents * Vec3::signum(local))
+    }
+}
+
+impl<T> Support<Vec3<T>> for Frustum<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn support(&self, dir: Vec3<T>) -> Vec3<T> {
+        furthest_point(dir, &self.corners)
+    }
+}
+
+impl<T> Support<Vec3<T>> for Cylinder<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn support(&self, dir: Vec3<T>) -> Vec3<T> {
+        // furthest cap centre, then out to the rim along the part of `dir` perpendicular to the axis
+        let axis = normalize_or_zero(self.p1 - self.p0);
+        let p = if dot(dir, axis) > T::zero() { self.p1 } else { self.p0 };
+        p + normalize_or_zero(dir - axis * dot(dir, axis)) * self.radius
+    }
+}
+
+impl<T> Support<Vec3<T>> for Cone<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn support(&self, dir: Vec3<T>) -> Vec3<T> {
+        // the furthest point is either the tip or on the rim of the base
+        let axis = self.direction;
+        let rim = self.base() + normalize_or_zero(dir - axis * dot(dir, axis)) * self.radius;
+        if dot(dir, self.position) > dot(dir, rim) { self.position } else { rim }
+    }
+}
+
+impl<T, S> Support<Vec3<T>> for Transformed<T, S> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>, S: Support<Vec3<T>> {
+    fn support(&self, dir: Vec3<T>) -> Vec3<T> {
+        // directions transform by the transpose of the linear part
+        let local = Mat3::from(self.matrix).transpose() * dir;
+        self.matrix * self.shape.support(local)
+    }
+}
+
+impl<V, A, B> Support<V> for MinkowskiSum<A, B> where V: std::ops::Add<Output=V> + Copy, A: Support<V>, B: Support<V> {
+    fn support(&self, dir: V) -> V {
+        self.a.support(dir) + self.b.support(dir)
+    }
+}
@@ -234,6 +234,86 @@ impl<T> Quat<T> where T: Float + FloatOps<T> + SignedNumberOps<T> {
     pub fn inverse(self) -> Self {
         self.reverse() / Self::mag2(self)
     }
+
+    /// constructs the shortest arc rotation which rotates direction `a` onto direction `b`, `a` and `b` do not need to be normalized.
+    /// when `a` and `b` are opposite the rotation is 180 degrees about an arbitrary axis perpendicular to `a`
+    pub fn from_to(a: Vec3<T>, b: Vec3<T>) -> Self {
+        let a = Vec3::normalize(a);
+        let b = Vec3::normalize(b);
+        // half way quaternion avoids trig, w = 1 + cos(theta) and xyz = sin(theta) * axis
+        let c = cross(a, b);
+        let w = T::one() + dot(a, b);
+        if w < T::epsilon() * T::four() * T::two() {
+            // opposite directions have no unique axis, and rounding leaves the cross product as noise close to them
+            let (axis, _) = get_orthonormal_basis_hughes_moeller(a);
+            return Quat::new(axis.x, axis.y, axis.z, T::zero());
+        }
+        Self::normalize(Quat::new(c.x, c.y, c.z, w))
+    }
+
+    /// constructs a rotation which rotates the z-axis to `forward` and the y-axis towards `up`,
+    /// when `forward` and `up` are parallel an arbitrary up vector is chosen
+    pub fn look_rotation(forward: Vec3<T>, up: Vec3<T>) -> Self {
+        let f = Vec3::normalize(forward);
+        let r = cross(up, f);
+        let r = if Vec3::mag2(r) > T::small_epsilon() {
+            Vec3::normalize(r)
+        }
+        else {
+            get_orthonormal_basis_hughes_moeller(f).0
+        };
+        let u = cross(f, r);
+        let mut m = Mat3::identity();
+        m.set_column(0, r);
+        m.set_column(1, u);
+        m.set_column(2, f);
+        Self::from_matrix(m)
+    }
+
+    /// returns the rotation as tuple `(axis, angle)` where `axis` is normalized and `angle` is in radians in the range 0-pi.
+    /// the identity returns the x-axis with an angle of 0
+    pub fn to_axis_angle(self) -> (Vec3<T>, T) {
+        let q = Self::normalize(self);
+        let q = if q.w < T::zero() { -q } else { q };
+        let v = Vec3::new(q.x, q.y, q.z);
+        let s = Vec3::mag(v);
+        if s < T::small_epsilon() {
+            return (Vec3::unit_x(), T::zero());
+        }
+        (v / s, T::two() * T::atan2(s, q.w))
+    }
+
+    /// returns the angle in radians of the smallest rotation between unit quaternions `a` and `b`, in the range 0-pi
+    pub fn angle_between(a: Self, b: Self) -> T {
+        let d = T::abs(Self::dot(a, b));
+        let d = if d > T::one() { T::one() } else { d };
+        T::two() * T::acos(d)
+    }
+
+    /// returns the quaternion exponential, for a pure quaternion `(axis * theta, 0)` this is the unit rotation `(axis * sin(theta), cos(theta))`
+    pub fn exp(self) -> Self {
+        let v = Vec3::new(self.x, self.y, self.z);
+        let theta = Vec3::mag(v);
+        let e = T::exp(self.w);
+        let (s, c) = T::sin_cos(theta);
+        let v = if theta > T::small_epsilon() { v * (s / theta) } else { v };
+        Quat::new(v.x * e, v.y * e, v.z * e, c * e)
+    }
+
+    /// returns the quaternion natural logarithm, for a unit quaternion this is the pure quaternion `(axis * half_angle, 0)`
+    pub fn log(self) -> Self {
+        let v = Vec3::new(self.x, self.y, self.z);
+        let s = Vec3::mag(v);
+        let m = Self::mag(self);
+        let ln_m = T::log(m, T::exp(T::one()));
+        let v = if s > T::small_epsilon() { v * (T::atan2(s, self.w) / s) } else { v };
+        Quat::new(v.x, v.y, v.z, ln_m)
+    }
+
+    /// returns the quaternion raised to the power `t`, for a unit quaternion this scales the angle of rotation by `t`
+    pub fn pow(self, t: T) -> Self {
+        (self.log() * t).exp()
+    }
 }
 
 impl<T> Add<Self> for Quat<T> where T: Float {
@@ -320,16 +400,17 @@ impl<T> MulAssign<T> for Quat<T> where T: Number {
     }
 }
 
+/// multiply quaternions `lhs * rhs`
+fn quat_mul<T: Number>(lhs: &Quat<T>, rhs: &Quat<T>) -> Quat<T> {
+    let [x, y, z, w] = crate::simd::quat_mul([lhs.x, lhs.y, lhs.z, lhs.w], [rhs.x, rhs.y, rhs.z, rhs.w]);
+    Quat { x, y, z, w }
+}
+
 // value * value
 impl<T> Mul<Self> for Quat<T> where T: Number {
     type Output = Self;
     fn mul(self, other: Self) -> Self {
-        Quat {
-            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
-            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
-            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
-            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w
-        }
+        quat_mul(&self, &other)
     }
 }
 
@@ -337,12 +418,7 @@ impl<T> Mul<Self> for Quat<T> where T: Number {
 impl<T> Mul<Quat<T>> for &Quat<T> where T: Number {
     type Output = Quat<T>;
     fn mul(self, other: Quat<T>) -> Quat<T> {
-        Quat {
-            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
-            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
-            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
-            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w
-        }
+        quat_mul(self, &other)
     }
 }
 
@@ -350,12 +426,7 @@ impl<T> Mul<Quat<T>> for &Quat<T> where T: Number {
 impl<T> Mul<&Self> for Quat<T> where T: Number {
     type Output = Self;
     fn mul(self, other: &Self) -> Self {
-        Quat {
-            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
-            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
-            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
-            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w
-        }
+        quat_mul(&self, other)
     }
 }
 
@@ -363,12 +434,7 @@ impl<T> Mul<&Self> for Quat<T> where T: Number {
 impl<T> Mul<Self> for &Quat<T> where T: Number {
     type Output = Quat<T>;
     fn mul(self, other: Self) -> Quat<T> {
-        Quat {
-            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
-            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
-            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
-            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w
-        }
+        quat_mul(self, other)
     }
 }
 
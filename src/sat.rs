@@ -0,0 +1,243 @@
+use crate::num::*;
+use crate::vec::*;
+
+use crate::primitives::{Sphere, Capsule, Support};
+use crate::{dot, dist, lerp, normalize, perp, convex_hull_from_points, furthest_point};
+
+/// 2D convex polygon built from the convex hull of a point cloud
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConvexPolygon<T> {
+    /// hull vertices in order around the perimeter
+    pub vertices: Vec<Vec2<T>>
+}
+
+/// result of a 2D separating axis test between shapes `a` and `b`
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Manifold<T> {
+    /// unit contact normal pointing from `a` toward `b`
+    pub normal: Vec2<T>,
+    /// penetration depth along `normal`
+    pub depth: T,
+    /// 1 or 2 contact points which lie on the surface of `b`
+    pub contacts: Vec<Vec2<T>>
+}
+
+/// trait for 2D shapes which can find the minimum translation vector and contact points against shape `B`
+/// using the separating axis test, implemented in both directions for polygons, circles and capsules
+pub trait Sat<T, B> {
+    /// returns the contact manifold if `self` overlaps `other` or `None` if they are separate or only touching
+    fn sat(&self, other: &B) -> Option<Manifold<T>>;
+}
+
+impl<T> ConvexPolygon<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// constructs a convex polygon from the convex hull of `points`
+    pub fn new(points: &[Vec2<T>]) -> Self {
+        ConvexPolygon {
+            vertices: convex_hull_from_points(points)
+        }
+    }
+
+    /// returns the centre of the polygon, the average of the vertices
+    pub fn center(&self) -> Vec2<T> {
+        let n = self.vertices.iter().fold(T::zero(), |n, _| n + T::one());
+        self.vertices.iter().fold(Vec2::zero(), |c, v| c + *v) / n
+    }
+
+    /// returns the polygon moved by `offset`, ie. to apply the minimum translation vector
+    pub fn translated(&self, offset: Vec2<T>) -> Self {
+        ConvexPolygon {
+            vertices: self.vertices.iter().map(|v| *v + offset).collect()
+        }
+    }
+}
+
+impl<T> Manifold<T> where T: Float {
+    /// returns the minimum translation vector which moves `b` out of `a`, move `a` by the negated vector to push `a` out of `b`
+    pub fn mtv(&self) -> Vec2<T> {
+        self.normal * self.depth
+    }
+}
+
+impl<T> Support<Vec2<T>> for ConvexPolygon<T> where T: Float {
+    fn support(&self, dir: Vec2<T>) -> Vec2<T> {
+        furthest_point(dir, &self.vertices)
+    }
+}
+
+/// returns the tolerance used to group vertices into the same contact feature at distance `d`
+fn feature_tolerance<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(d: T) -> T {
+    T::sqrt(T::epsilon()) * (T::one() + T::abs(d))
+}
+
+/// returns the closest point to `p` on the segment `e0-e1`, which may have zero length
+fn closest_point_on_edge<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(p: Vec2<T>, e0: Vec2<T>, e1: Vec2<T>) -> Vec2<T> {
+    let e = e1 - e0;
+    let l2 = dot(e, e);
+    if l2 <= T::small_epsilon() {
+        return e0;
+    }
+    let t = dot(p - e0, e) / l2;
+    lerp(e0, e1, T::max(T::zero(), T::min(T::one(), t)))
+}
+
+/// returns the candidate separating axes of a core, edge normals and for a segment also its direction
+fn core_axes<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(core: &[Vec2<T>]) -> Vec<Vec2<T>> {
+    let n = core.len();
+    let mut axes = Vec::new();
+    if n < 2 {
+        return axes;
+    }
+    let edges = if n == 2 { 1 } else { n };
+    for i in 0..edges {
+        let e = core[(i + 1) % n] - core[i];
+        if dot(e, e) > T::small_epsilon() {
+            axes.push(normalize(perp(e)));
+            if n == 2 {
+                axes.push(normalize(e));
+            }
+        }
+    }
+    axes
+}
+
+/// returns the min and max projection of `core` onto `axis`
+fn project<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(core: &[Vec2<T>], axis: Vec2<T>) -> (T, T) {
+    core.iter().fold((T::max_value(), -T::max_value()), |(lo, hi), v| {
+        let d = dot(*v, axis);
+        (T::min(lo, d), T::max(hi, d))
+    })
+}
+
+/// returns `(distance, point_a, point_b)` the closest points between the separated cores `a` and `b`
+fn core_closest<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(a: &[Vec2<T>], b: &[Vec2<T>]) -> (T, Vec2<T>, Vec2<T>) {
+    // for separated convex shapes the closest points always include a vertex of one shape
+    let mut best = (T::max_value(), a[0], b[0]);
+    for (p, q, flip) in [(a, b, false), (b, a, true)] {
+        let n = q.len();
+        for v in p {
+            for i in 0..n {
+                let c = closest_point_on_edge(*v, q[i], q[(i + 1) % n]);
+                let d = dist(*v, c);
+                if d < best.0 {
+                    best = if flip { (d, c, *v) } else { (d, *v, c) };
+                }
+            }
+        }
+    }
+    best
+}
+
+/// returns the end points of the feature of `core` furthest along `normal` ordered along `perp(normal)`, the same point twice for a vertex
+fn support_feature<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(core: &[Vec2<T>], normal: Vec2<T>) -> (Vec2<T>, Vec2<T>) {
+    let (_, hi) = project(core, normal);
+    let tol = feature_tolerance(hi);
+    let tangent = perp(normal);
+    let mut lo = (T::max_value(), core[0]);
+    let mut up = (-T::max_value(), core[0]);
+    for v in core {
+        if dot(*v, normal) >= hi - tol {
+            let t = dot(*v, tangent);
+            if t < lo.0 {
+                lo = (t, *v);
+            }
+            if t > up.0 {
+                up = (t, *v);
+            }
+        }
+    }
+    (lo.1, up.1)
+}
+
+/// returns the contact points on the surface of `b` by clipping the feature of `b` facing `a` against the feature of `a` facing `b`
+fn clip_contacts<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(a: &[Vec2<T>], b: &[Vec2<T>], rb: T, normal: Vec2<T>) -> Vec<Vec2<T>> {
+    let tangent = perp(normal);
+    let (a0, a1) = support_feature(a, normal);
+    let (b1, b0) = support_feature(b, -normal);
+    let ta0 = dot(a0, tangent);
+    let ta1 = dot(a1, tangent);
+    let tb0 = dot(b0, tangent);
+    let tb1 = dot(b1, tangent);
+
+    // a single vertex on b, or features which do not overlap due to precision use the deepest point of b
+    let offset = normal * rb;
+    if tb1 - tb0 <= feature_tolerance(tb1) {
+        return vec![b0 - offset];
+    }
+    let lo = T::max(tb0, ta0);
+    let hi = T::min(tb1, ta1);
+    if hi < lo {
+        let deepest = if dot(b0, normal) < dot(b1, normal) { b0 } else { b1 };
+        return vec![deepest - offset];
+    }
+    let at = |t: T| lerp(b0, b1, (t - tb0) / (tb1 - tb0)) - offset;
+    if hi - lo <= feature_tolerance(hi) {
+        vec![at(lo)]
+    }
+    else {
+        vec![at(lo), at(hi)]
+    }
+}
+
+/// returns the contact manifold for 2D rounded convex shapes, each defined by a `core` convex polygon, segment or point swept by
+/// a radius. a polygon has radius 0, a capsule is a segment core and a circle is a point core, the normal points from `a` to `b`
+pub fn sat_2d<T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T>>(a: &[Vec2<T>], ra: T, b: &[Vec2<T>], rb: T) -> Option<Manifold<T>> {
+    // find the axis of least penetration, or the largest separation between the cores
+    let mut best: Option<(T, Vec2<T>)> = None;
+    for axis in core_axes(a).into_iter().chain(core_axes(b)) {
+        let (amin, amax) = project(a, axis);
+        let (bmin, bmax) = project(b, axis);
+        let (sep, n) = if bmin - amax > amin - bmax { (bmin - amax, axis) } else { (amin - bmax, -axis) };
+        if best.is_none_or(|(s, _)| sep > s) {
+            best = Some((sep, n));
+        }
+    }
+
+    // separated cores can still overlap once the radii are added, so find the exact distance between them
+    let (core_dist, normal) = match best {
+        Some((sep, n)) if sep <= T::zero() => (sep, n),
+        _ => {
+            let (d, pa, pb) = core_closest(a, b);
+            if d >= ra + rb {
+                return None;
+            }
+            let n = if d > T::small_epsilon() { (pb - pa) / d } else { Vec2::unit_y() };
+            (d, n)
+        }
+    };
+
+    let depth = ra + rb - core_dist;
+    if depth <= T::zero() {
+        return None;
+    }
+
+    Some(Manifold {
+        normal,
+        depth,
+        contacts: clip_contacts(a, b, rb, normal)
+    })
+}
+
+macro_rules! sat_impl {
+    ($A:ty, $B:ty, |$a:ident| $ca:expr, $ra:expr, |$b:ident| $cb:expr, $rb:expr) => {
+        impl<T> Sat<T, $B> for $A where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+            fn sat(&self, other: &$B) -> Option<Manifold<T>> {
+                let $a = self;
+                let $b = other;
+                sat_2d($ca, $ra, $cb, $rb)
+            }
+        }
+    }
+}
+
+sat_impl!(ConvexPolygon<T>, ConvexPolygon<T>, |a| &a.vertices, T::zero(), |b| &b.vertices, T::zero());
+sat_impl!(ConvexPolygon<T>, Sphere<T, Vec2<T>>, |a| &a.vertices, T::zero(), |b| &[b.center], b.radius);
+sat_impl!(ConvexPolygon<T>, Capsule<T, Vec2<T>>, |a| &a.vertices, T::zero(), |b| &[b.p0, b.p1], b.radius);
+sat_impl!(Sphere<T, Vec2<T>>, ConvexPolygon<T>, |a| &[a.center], a.radius, |b| &b.vertices, T::zero());
+sat_impl!(Sphere<T, Vec2<T>>, Sphere<T, Vec2<T>>, |a| &[a.center], a.radius, |b| &[b.center], b.radius);
+sat_impl!(Sphere<T, Vec2<T>>, Capsule<T, Vec2<T>>, |a| &[a.center], a.radius, |b| &[b.p0, b.p1], b.radius);
+sat_impl!(Capsule<T, Vec2<T>>, ConvexPolygon<T>, |a| &[a.p0, a.p1], a.radius, |b| &b.vertices, T::zero());
+sat_impl!(Capsule<T, Vec2<T>>, Sphere<T, Vec2<T>>, |a| &[a.p0, a.p1], a.radius, |b| &[b.center], b.radius);
+sat_impl!(Capsule<T, Vec2<T>>, Capsule<T, Vec2<T>>, |a| &[a.p0, a.p1], a.radius, |b| &[b.p0, b.p1], b.radius);
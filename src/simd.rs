@@ -0,0 +1,372 @@
+use crate::num::*;
+
+//
+// scalar kernels
+//
+
+// the scalar kernels are used for all number types and are the fallback on targets without a
+// vectorised path. the vectorised paths perform the same multiplies and adds in the same order so
+// results are bit identical with or without the `simd` feature
+
+/// multiply 4x4 * 4x4 row-major matrices
+fn mul4x4_scalar<T: Number>(lhs: &[T; 16], rhs: &[T; 16]) -> [T; 16] {
+    [
+        lhs[0] * rhs[0] + lhs[1] * rhs[4] + lhs[2] * rhs[8] + lhs[3] * rhs[12],
+        lhs[0] * rhs[1] + lhs[1] * rhs[5] + lhs[2] * rhs[9] + lhs[3] * rhs[13],
+        lhs[0] * rhs[2] + lhs[1] * rhs[6] + lhs[2] * rhs[10] + lhs[3] * rhs[14],
+        lhs[0] * rhs[3] + lhs[1] * rhs[7] + lhs[2] * rhs[11] + lhs[3] * rhs[15],
+
+        lhs[4] * rhs[0] + lhs[5] * rhs[4] + lhs[6] * rhs[8] + lhs[7] * rhs[12],
+        lhs[4] * rhs[1] + lhs[5] * rhs[5] + lhs[6] * rhs[9] + lhs[7] * rhs[13],
+        lhs[4] * rhs[2] + lhs[5] * rhs[6] + lhs[6] * rhs[10] + lhs[7] * rhs[14],
+        lhs[4] * rhs[3] + lhs[5] * rhs[7] + lhs[6] * rhs[11] + lhs[7] * rhs[15],
+
+        lhs[8] * rhs[0] + lhs[9] * rhs[4] + lhs[10] * rhs[8] + lhs[11] * rhs[12],
+        lhs[8] * rhs[1] + lhs[9] * rhs[5] + lhs[10] * rhs[9] + lhs[11] * rhs[13],
+        lhs[8] * rhs[2] + lhs[9] * rhs[6] + lhs[10] * rhs[10] + lhs[11] * rhs[14],
+        lhs[8] * rhs[3] + lhs[9] * rhs[7] + lhs[10] * rhs[11] + lhs[11] * rhs[15],
+
+        lhs[12] * rhs[0] + lhs[13] * rhs[4] + lhs[14] * rhs[8] + lhs[15] * rhs[12],
+        lhs[12] * rhs[1] + lhs[13] * rhs[5] + lhs[14] * rhs[9] + lhs[15] * rhs[13],
+        lhs[12] * rhs[2] + lhs[13] * rhs[6] + lhs[14] * rhs[10] + lhs[15] * rhs[14],
+        lhs[12] * rhs[3] + lhs[13] * rhs[7] + lhs[14] * rhs[11] + lhs[15] * rhs[15],
+    ]
+}
+
+/// multiply 4x4 row-major matrix * 4 component column vector
+fn mul4x1_scalar<T: Number>(lhs: &[T; 16], rhs: [T; 4]) -> [T; 4] {
+    [
+        lhs[0] * rhs[0] + lhs[1] * rhs[1] + lhs[2] * rhs[2] + lhs[3] * rhs[3],
+        lhs[4] * rhs[0] + lhs[5] * rhs[1] + lhs[6] * rhs[2] + lhs[7] * rhs[3],
+        lhs[8] * rhs[0] + lhs[9] * rhs[1] + lhs[10] * rhs[2] + lhs[11] * rhs[3],
+        lhs[12] * rhs[0] + lhs[13] * rhs[1] + lhs[14] * rhs[2] + lhs[15] * rhs[3],
+    ]
+}
+
+/// multiply quaternions stored as `[x, y, z, w]`
+fn quat_mul_scalar<T: Number>(lhs: [T; 4], rhs: [T; 4]) -> [T; 4] {
+    let [ax, ay, az, aw] = lhs;
+    let [bx, by, bz, bw] = rhs;
+    [
+        aw * bx + ax * bw + ay * bz - az * by,
+        aw * by - ax * bz + ay * bw + az * bx,
+        aw * bz + ax * by - ay * bx + az * bw,
+        aw * bw - ax * bx - ay * by - az * bz,
+    ]
+}
+
+//
+// sse2
+//
+
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod arch {
+    use std::arch::x86_64::*;
+
+    // sse2 is part of the x86_64 baseline so the intrinsics are always available
+
+    /// multiply 4x4 * 4x4 row-major f32 matrices
+    pub fn mul4x4(lhs: &[f32; 16], rhs: &[f32; 16]) -> [f32; 16] {
+        let mut out = [0.0; 16];
+        unsafe {
+            let r0 = _mm_loadu_ps(rhs.as_ptr());
+            let r1 = _mm_loadu_ps(rhs.as_ptr().add(4));
+            let r2 = _mm_loadu_ps(rhs.as_ptr().add(8));
+            let r3 = _mm_loadu_ps(rhs.as_ptr().add(12));
+            for i in (0..16).step_by(4) {
+                let mut row = _mm_mul_ps(_mm_set1_ps(lhs[i]), r0);
+                row = _mm_add_ps(row, _mm_mul_ps(_mm_set1_ps(lhs[i + 1]), r1));
+                row = _mm_add_ps(row, _mm_mul_ps(_mm_set1_ps(lhs[i + 2]), r2));
+                row = _mm_add_ps(row, _mm_mul_ps(_mm_set1_ps(lhs[i + 3]), r3));
+                _mm_storeu_ps(out.as_mut_ptr().add(i), row);
+            }
+        }
+        out
+    }
+
+    /// multiply 4x4 row-major f32 matrix * 4 component column vector
+    pub fn mul4x1(lhs: &[f32; 16], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            // transpose rows into columns
+            let r0 = _mm_loadu_ps(lhs.as_ptr());
+            let r1 = _mm_loadu_ps(lhs.as_ptr().add(4));
+            let r2 = _mm_loadu_ps(lhs.as_ptr().add(8));
+            let r3 = _mm_loadu_ps(lhs.as_ptr().add(12));
+            let t0 = _mm_unpacklo_ps(r0, r1);
+            let t1 = _mm_unpacklo_ps(r2, r3);
+            let t2 = _mm_unpackhi_ps(r0, r1);
+            let t3 = _mm_unpackhi_ps(r2, r3);
+            let c0 = _mm_movelh_ps(t0, t1);
+            let c1 = _mm_movehl_ps(t1, t0);
+            let c2 = _mm_movelh_ps(t2, t3);
+            let c3 = _mm_movehl_ps(t3, t2);
+
+            let mut v = _mm_mul_ps(c0, _mm_set1_ps(rhs[0]));
+            v = _mm_add_ps(v, _mm_mul_ps(c1, _mm_set1_ps(rhs[1])));
+            v = _mm_add_ps(v, _mm_mul_ps(c2, _mm_set1_ps(rhs[2])));
+            v = _mm_add_ps(v, _mm_mul_ps(c3, _mm_set1_ps(rhs[3])));
+            _mm_storeu_ps(out.as_mut_ptr(), v);
+        }
+        out
+    }
+
+    /// multiply f32 quaternions stored as `[x, y, z, w]`
+    pub fn quat_mul(lhs: [f32; 4], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            let b = _mm_loadu_ps(rhs.as_ptr());
+            let bwzyx = _mm_shuffle_ps::<0b00_01_10_11>(b, b);
+            let bzwxy = _mm_shuffle_ps::<0b01_00_11_10>(b, b);
+            let byxwz = _mm_shuffle_ps::<0b10_11_00_01>(b, b);
+
+            let mut q = _mm_mul_ps(_mm_set1_ps(lhs[3]), b);
+            q = _mm_add_ps(q, _mm_mul_ps(_mm_mul_ps(_mm_set1_ps(lhs[0]), bwzyx), _mm_setr_ps(1.0, -1.0, 1.0, -1.0)));
+            q = _mm_add_ps(q, _mm_mul_ps(_mm_mul_ps(_mm_set1_ps(lhs[1]), bzwxy), _mm_setr_ps(1.0, 1.0, -1.0, -1.0)));
+            q = _mm_add_ps(q, _mm_mul_ps(_mm_mul_ps(_mm_set1_ps(lhs[2]), byxwz), _mm_setr_ps(-1.0, 1.0, 1.0, -1.0)));
+            _mm_storeu_ps(out.as_mut_ptr(), q);
+        }
+        out
+    }
+
+    /// lane-wise f32 `lhs + rhs`
+    pub fn add4(lhs: [f32; 4], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            _mm_storeu_ps(out.as_mut_ptr(), _mm_add_ps(_mm_loadu_ps(lhs.as_ptr()), _mm_loadu_ps(rhs.as_ptr())));
+        }
+        out
+    }
+
+    /// lane-wise f32 `lhs - rhs`
+    pub fn sub4(lhs: [f32; 4], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            _mm_storeu_ps(out.as_mut_ptr(), _mm_sub_ps(_mm_loadu_ps(lhs.as_ptr()), _mm_loadu_ps(rhs.as_ptr())));
+        }
+        out
+    }
+
+    /// lane-wise f32 `lhs * rhs`
+    pub fn mul4(lhs: [f32; 4], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            _mm_storeu_ps(out.as_mut_ptr(), _mm_mul_ps(_mm_loadu_ps(lhs.as_ptr()), _mm_loadu_ps(rhs.as_ptr())));
+        }
+        out
+    }
+
+    /// lane-wise f32 `lhs / rhs`
+    pub fn div4(lhs: [f32; 4], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            _mm_storeu_ps(out.as_mut_ptr(), _mm_div_ps(_mm_loadu_ps(lhs.as_ptr()), _mm_loadu_ps(rhs.as_ptr())));
+        }
+        out
+    }
+}
+
+//
+// neon
+//
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+mod arch {
+    use std::arch::aarch64::*;
+
+    // neon is part of the aarch64 baseline so the intrinsics are always available
+
+    /// multiply 4x4 * 4x4 row-major f32 matrices
+    pub fn mul4x4(lhs: &[f32; 16], rhs: &[f32; 16]) -> [f32; 16] {
+        let mut out = [0.0; 16];
+        unsafe {
+            let r0 = vld1q_f32(rhs.as_ptr());
+            let r1 = vld1q_f32(rhs.as_ptr().add(4));
+            let r2 = vld1q_f32(rhs.as_ptr().add(8));
+            let r3 = vld1q_f32(rhs.as_ptr().add(12));
+            for i in (0..16).step_by(4) {
+                let mut row = vmulq_f32(vdupq_n_f32(lhs[i]), r0);
+                row = vaddq_f32(row, vmulq_f32(vdupq_n_f32(lhs[i + 1]), r1));
+                row = vaddq_f32(row, vmulq_f32(vdupq_n_f32(lhs[i + 2]), r2));
+                row = vaddq_f32(row, vmulq_f32(vdupq_n_f32(lhs[i + 3]), r3));
+                vst1q_f32(out.as_mut_ptr().add(i), row);
+            }
+        }
+        out
+    }
+
+    /// multiply 4x4 row-major f32 matrix * 4 component column vector
+    pub fn mul4x1(lhs: &[f32; 16], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            // de-interleaving load transposes rows into columns
+            let c = vld4q_f32(lhs.as_ptr());
+            let mut v = vmulq_f32(c.0, vdupq_n_f32(rhs[0]));
+            v = vaddq_f32(v, vmulq_f32(c.1, vdupq_n_f32(rhs[1])));
+            v = vaddq_f32(v, vmulq_f32(c.2, vdupq_n_f32(rhs[2])));
+            v = vaddq_f32(v, vmulq_f32(c.3, vdupq_n_f32(rhs[3])));
+            vst1q_f32(out.as_mut_ptr(), v);
+        }
+        out
+    }
+
+    /// multiply f32 quaternions stored as `[x, y, z, w]`
+    pub fn quat_mul(lhs: [f32; 4], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            let b = vld1q_f32(rhs.as_ptr());
+            let byxwz = vrev64q_f32(b);
+            let bwzyx = vextq_f32::<2>(byxwz, byxwz);
+            let bzwxy = vextq_f32::<2>(b, b);
+
+            let s1 = vld1q_f32([1.0, -1.0, 1.0, -1.0].as_ptr());
+            let s2 = vld1q_f32([1.0, 1.0, -1.0, -1.0].as_ptr());
+            let s3 = vld1q_f32([-1.0, 1.0, 1.0, -1.0].as_ptr());
+
+            let mut q = vmulq_f32(vdupq_n_f32(lhs[3]), b);
+            q = vaddq_f32(q, vmulq_f32(vmulq_f32(vdupq_n_f32(lhs[0]), bwzyx), s1));
+            q = vaddq_f32(q, vmulq_f32(vmulq_f32(vdupq_n_f32(lhs[1]), bzwxy), s2));
+            q = vaddq_f32(q, vmulq_f32(vmulq_f32(vdupq_n_f32(lhs[2]), byxwz), s3));
+            vst1q_f32(out.as_mut_ptr(), q);
+        }
+        out
+    }
+
+    /// lane-wise f32 `lhs + rhs`
+    pub fn add4(lhs: [f32; 4], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            vst1q_f32(out.as_mut_ptr(), vaddq_f32(vld1q_f32(lhs.as_ptr()), vld1q_f32(rhs.as_ptr())));
+        }
+        out
+    }
+
+    /// lane-wise f32 `lhs - rhs`
+    pub fn sub4(lhs: [f32; 4], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            vst1q_f32(out.as_mut_ptr(), vsubq_f32(vld1q_f32(lhs.as_ptr()), vld1q_f32(rhs.as_ptr())));
+        }
+        out
+    }
+
+    /// lane-wise f32 `lhs * rhs`
+    pub fn mul4(lhs: [f32; 4], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            vst1q_f32(out.as_mut_ptr(), vmulq_f32(vld1q_f32(lhs.as_ptr()), vld1q_f32(rhs.as_ptr())));
+        }
+        out
+    }
+
+    /// lane-wise f32 `lhs / rhs`
+    pub fn div4(lhs: [f32; 4], rhs: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        unsafe {
+            vst1q_f32(out.as_mut_ptr(), vdivq_f32(vld1q_f32(lhs.as_ptr()), vld1q_f32(rhs.as_ptr())));
+        }
+        out
+    }
+}
+
+//
+// dispatch
+//
+
+/// calls the vectorised `f32` kernel `f` with `args` and returns `Some` result when `T` is `f32`, `A` and `R` must be the kernel argument
+/// and result types with `T` in place of `f32` which the size checks guard against
+#[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn dispatch_f32<T: 'static, A: Copy, AF: Copy, R, RF>(args: A, f: fn(AF) -> RF) -> Option<R> {
+    use std::any::TypeId;
+    use std::mem::size_of;
+    if TypeId::of::<T>() == TypeId::of::<f32>() && size_of::<A>() == size_of::<AF>() && size_of::<R>() == size_of::<RF>() {
+        // T is f32 so the argument and result types are identical to the f32 kernel signature
+        unsafe {
+            let out = f(std::mem::transmute_copy::<A, AF>(&args));
+            Some(std::mem::transmute_copy::<RF, R>(&out))
+        }
+    }
+    else {
+        None
+    }
+}
+
+/// multiply 4x4 * 4x4 row-major matrices, vectorised for `f32` with the `simd` feature
+pub fn mul4x4<T: Number>(lhs: &[T; 16], rhs: &[T; 16]) -> [T; 16] {
+    #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+    if let Some(out) = dispatch_f32::<T, _, _, _, _>((*lhs, *rhs), |(l, r): ([f32; 16], [f32; 16])| arch::mul4x4(&l, &r)) {
+        return out;
+    }
+    mul4x4_scalar(lhs, rhs)
+}
+
+/// multiply 4x4 row-major matrix * 4 component column vector, vectorised for `f32` with the `simd` feature
+pub fn mul4x1<T: Number>(lhs: &[T; 16], rhs: [T; 4]) -> [T; 4] {
+    #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+    if let Some(out) = dispatch_f32::<T, _, _, _, _>((*lhs, rhs), |(l, r): ([f32; 16], [f32; 4])| arch::mul4x1(&l, r)) {
+        return out;
+    }
+    mul4x1_scalar(lhs, rhs)
+}
+
+/// multiply quaternions stored as `[x, y, z, w]`, vectorised for `f32` with the `simd` feature
+pub fn quat_mul<T: Number>(lhs: [T; 4], rhs: [T; 4]) -> [T; 4] {
+    #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+    if let Some(out) = dispatch_f32::<T, _, _, _, _>((lhs, rhs), |(l, r): ([f32; 4], [f32; 4])| arch::quat_mul(l, r)) {
+        return out;
+    }
+    quat_mul_scalar(lhs, rhs)
+}
+
+/// lane-wise `lhs + rhs` for `Vec4<f32>`, other types and lengths return `None` and use the scalar vector code
+#[cfg(feature = "simd")]
+#[cfg_attr(not(any(target_arch = "x86_64", target_arch = "aarch64")), allow(unused_variables))]
+pub fn add4<T: Number, const N: usize>(lhs: [T; N], rhs: [T; N]) -> Option<[T; N]> {
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    if N == 4 {
+        return dispatch_f32::<T, _, _, _, _>((lhs, rhs), |(l, r): ([f32; 4], [f32; 4])| arch::add4(l, r));
+    }
+    None
+}
+
+/// lane-wise `lhs - rhs` for `Vec4<f32>`, other types and lengths return `None` and use the scalar vector code
+#[cfg(feature = "simd")]
+#[cfg_attr(not(any(target_arch = "x86_64", target_arch = "aarch64")), allow(unused_variables))]
+pub fn sub4<T: Number, const N: usize>(lhs: [T; N], rhs: [T; N]) -> Option<[T; N]> {
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    if N == 4 {
+        return dispatch_f32::<T, _, _, _, _>((lhs, rhs), |(l, r): ([f32; 4], [f32; 4])| arch::sub4(l, r));
+    }
+    None
+}
+
+/// lane-wise `lhs * rhs` for `Vec4<f32>`, other types and lengths return `None` and use the scalar vector code
+#[cfg(feature = "simd")]
+#[cfg_attr(not(any(target_arch = "x86_64", target_arch = "aarch64")), allow(unused_variables))]
+pub fn mul4<T: Number, const N: usize>(lhs: [T; N], rhs: [T; N]) -> Option<[T; N]> {
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    if N == 4 {
+        return dispatch_f32::<T, _, _, _, _>((lhs, rhs), |(l, r): ([f32; 4], [f32; 4])| arch::mul4(l, r));
+    }
+    None
+}
+
+/// lane-wise `lhs / rhs` for `Vec4<f32>`, other types and lengths return `None` and use the scalar vector code
+#[cfg(feature = "simd")]
+#[cfg_attr(not(any(target_arch = "x86_64", target_arch = "aarch64")), allow(unused_variables))]
+pub fn div4<T: Number, const N: usize>(lhs: [T; N], rhs: [T; N]) -> Option<[T; N]> {
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    if N == 4 {
+        return dispatch_f32::<T, _, _, _, _>((lhs, rhs), |(l, r): ([f32; 4], [f32; 4])| arch::div4(l, r));
+    }
+    None
+}
+
+/// dot product of 4 component vectors, the products are vectorised for `f32` with the `simd` feature and summed in order
+pub fn dot4<T: Number>(lhs: [T; 4], rhs: [T; 4]) -> T {
+    #[cfg(all(feature = "simd", any(target_arch = "x86_64", target_arch = "aarch64")))]
+    if let Some(p) = dispatch_f32::<T, _, _, [T; 4], _>((lhs, rhs), |(l, r): ([f32; 4], [f32; 4])| arch::mul4(l, r)) {
+        return p[0] + p[1] + p[2] + p[3];
+    }
+    lhs[0] * rhs[0] + lhs[1] * rhs[1] + lhs[2] * rhs[2] + lhs[3] * rhs[3]
+}
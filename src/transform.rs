@@ -0,0 +1,218 @@
+use std::ops::Mul;
+use std::ops::MulAssign;
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::num::*;
+use crate::vec::*;
+use crate::mat::*;
+use crate::quat::*;
+
+/// affine transform stored as separate translation, rotation and scale (TRS), applied scale first then rotation then translation
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="hash", derive(Hash))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(C)]
+pub struct Transform<T> {
+    /// translation applied last
+    pub translation: Vec3<T>,
+    /// rotation applied after scale
+    pub rotation: Quat<T>,
+    /// per axis scale applied first
+    pub scale: Vec3<T>
+}
+
+impl<T> Transform<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    /// constructs an identity transform which has no translation, rotation or scale
+    pub fn identity() -> Self {
+        Transform {
+            translation: Vec3::zero(),
+            rotation: Quat::identity(),
+            scale: Vec3::one()
+        }
+    }
+
+    /// constructs a transform from `translation`, `rotation` and `scale`
+    pub fn new(translation: Vec3<T>, rotation: Quat<T>, scale: Vec3<T>) -> Self {
+        Transform {
+            translation,
+            rotation,
+            scale
+        }
+    }
+
+    /// constructs a transform with translation `t` only
+    pub fn from_translation(t: Vec3<T>) -> Self {
+        Transform {
+            translation: t,
+            ..Self::identity()
+        }
+    }
+
+    /// constructs a transform with rotation `r` only
+    pub fn from_rotation(r: Quat<T>) -> Self {
+        Transform {
+            rotation: r,
+            ..Self::identity()
+        }
+    }
+
+    /// constructs a transform with scale `s` only
+    pub fn from_scale(s: Vec3<T>) -> Self {
+        Transform {
+            scale: s,
+            ..Self::identity()
+        }
+    }
+
+    /// constructs a transform from the affine matrix `m`, shear is discarded and a reflection becomes a negative x scale
+    pub fn from_matrix(m: Mat34<T>) -> Self {
+        let (translation, rotation, scale) = m.decompose();
+        Transform {
+            translation,
+            rotation,
+            scale
+        }
+    }
+
+    /// returns a 3x4 affine matrix with the same translation, rotation and scale
+    pub fn get_matrix(self) -> Mat34<T> {
+        Mat34::from_trs(self.translation, self.rotation, self.scale)
+    }
+
+    /// returns the inverse transform, this is exact when the scale is uniform.
+    /// with non-uniform scale and rotation the inverse contains shear which cannot be represented in TRS form
+    pub fn inverse(self) -> Self {
+        let rotation = self.rotation.inverse();
+        let scale = Vec3::one() / self.scale;
+        Transform {
+            translation: scale * (rotation * -self.translation),
+            rotation,
+            scale
+        }
+    }
+
+    /// returns point `p` transformed by scale, rotation and translation
+    pub fn transform_point(self, p: Vec3<T>) -> Vec3<T> {
+        self.rotation * (self.scale * p) + self.translation
+    }
+
+    /// returns direction `v` transformed by scale and rotation, ignoring translation
+    pub fn transform_vector(self, v: Vec3<T>) -> Vec3<T> {
+        self.rotation * (self.scale * v)
+    }
+
+    /// returns the unit x axis in world space, ignoring scale
+    pub fn right(self) -> Vec3<T> {
+        self.rotation * Vec3::unit_x()
+    }
+
+    /// returns the unit y axis in world space, ignoring scale
+    pub fn up(self) -> Vec3<T> {
+        self.rotation * Vec3::unit_y()
+    }
+
+    /// returns the unit z axis in world space, ignoring scale
+    pub fn forward(self) -> Vec3<T> {
+        self.rotation * Vec3::unit_z()
+    }
+}
+
+/// composes transforms, `lhs * rhs` applies `rhs` first then `lhs`.
+/// scale is combined per axis so the result is exact unless `lhs` has non-uniform scale and `rhs` is rotated
+impl<T> Mul<Self> for Transform<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Transform {
+            translation: self.transform_point(other.translation),
+            rotation: self.rotation * other.rotation,
+            scale: self.scale * other.scale
+        }
+    }
+}
+
+impl<T> MulAssign<Self> for Transform<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+/// transforms point `other` by scale, rotation and translation
+impl<T> Mul<Vec3<T>> for Transform<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    type Output = Vec3<T>;
+    fn mul(self, other: Vec3<T>) -> Vec3<T> {
+        self.transform_point(other)
+    }
+}
+
+/// default to identity
+impl<T> Default for Transform<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// returns a transform interpolated between `e0` and `e1` by percentage `t`, translation and scale are lerped and rotation is nlerped
+impl<T> Lerp<T> for Transform<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn lerp(e0: Self, e1: Self, t: T) -> Self {
+        Transform {
+            translation: Vec3::lerp(e0.translation, e1.translation, t),
+            rotation: Quat::nlerp(e0.rotation, e1.rotation, t),
+            scale: Vec3::lerp(e0.scale, e1.scale, t)
+        }
+    }
+}
+
+/// returns a transform interpolated between `e0` and `e1` by percentage `t`, translation and scale are lerped and rotation is slerped
+impl<T> Slerp<T> for Transform<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn slerp(e0: Self, e1: Self, t: T) -> Self {
+        Transform {
+            translation: Vec3::lerp(e0.translation, e1.translation, t),
+            rotation: Quat::slerp(e0.rotation, e1.rotation, t),
+            scale: Vec3::lerp(e0.scale, e1.scale, t)
+        }
+    }
+}
+
+/// constructs from a tuple of translation, rotation and scale
+impl<T> From<(Vec3<T>, Quat<T>, Vec3<T>)> for Transform<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from(other: (Vec3<T>, Quat<T>, Vec3<T>)) -> Self {
+        Transform::new(other.0, other.1, other.2)
+    }
+}
+
+/// constructs from an affine 3x4 matrix without shear
+impl<T> From<Mat34<T>> for Transform<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from(other: Mat34<T>) -> Self {
+        Transform::from_matrix(other)
+    }
+}
+
+/// constructs from an affine 4x4 matrix without shear, the bottom row is ignored
+impl<T> From<Mat4<T>> for Transform<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from(other: Mat4<T>) -> Self {
+        Transform::from_matrix(Mat34::from(other))
+    }
+}
+
+/// constructs a 3x4 affine matrix from a transform
+impl<T> From<Transform<T>> for Mat34<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from(other: Transform<T>) -> Self {
+        other.get_matrix()
+    }
+}
+
+/// constructs a 4x4 affine matrix from a transform
+impl<T> From<Transform<T>> for Mat4<T> where T: Float + FloatOps<T> + NumberOps<T> + SignedNumberOps<T> {
+    fn from(other: Transform<T>) -> Self {
+        Mat4::from(other.get_matrix())
+    }
+}
+
+/// displays like [[1.0, 2.0, 3.0], [0.0, 0.0, 0.0, 1.0], [1.0, 1.0, 1.0]]
+impl<T> Display for Transform<T> where T: Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}, {}]", self.translation, self.rotation, self.scale)
+    }
+}
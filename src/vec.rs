@@ -11,6 +11,10 @@ use std::ops::DivAssign;
 use std::ops::Rem;
 use std::ops::RemAssign;
 use std::ops::Neg;
+use std::ops::Not;
+use std::ops::BitAnd;
+use std::ops::BitOr;
+use std::ops::BitXor;
 use std::ops::Deref;
 use std::ops::DerefMut;
 
@@ -20,6 +24,8 @@ use std::fmt::Display;
 use std::fmt::Formatter;
 
 use crate::num::*;
+#[cfg(any(feature = "lhs_scalar_vec_ops", feature = "casts"))]
+use crate::half::f16;
 
 //
 // Vec Traits
@@ -131,7 +137,7 @@ impl<T> Dot<T> for Vec3<T> where T: Number {
 
 impl<T> Dot<T> for Vec4<T> where T: Number {
     fn dot(a: Self, b: Self) -> T {
-        a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w
+        crate::simd::dot4([a.x, a.y, a.z, a.w], [b.x, b.y, b.z, b.w])
     }
 }
 
@@ -200,6 +206,50 @@ pub trait Nlerp<T: Float> {
     fn nlerp(e0: Self, e1: Self, t: T) -> Self;
 }
 
+/// trait for component wise comparison of vectors, returning a boolean vector mask of the same dimension
+pub trait VecCompare<T: Number>: VecN<T> {
+    /// boolean vector type used as a mask, ie. `Vec3<bool>` for `Vec3<T>`
+    type Mask: VecMask;
+    /// returns a mask with component wise `a < b`
+    fn less_than(a: Self, b: Self) -> Self::Mask;
+    /// returns a mask with component wise `a <= b`
+    fn less_equal(a: Self, b: Self) -> Self::Mask;
+    /// returns a mask with component wise `a > b`
+    fn greater_than(a: Self, b: Self) -> Self::Mask;
+    /// returns a mask with component wise `a >= b`
+    fn greater_equal(a: Self, b: Self) -> Self::Mask;
+    /// returns a mask with component wise `a == b`
+    fn equal(a: Self, b: Self) -> Self::Mask;
+    /// returns a mask with component wise `a != b`
+    fn not_equal(a: Self, b: Self) -> Self::Mask;
+    /// returns a vector selecting component wise from `a` where `mask` is true and from `b` where `mask` is false
+    fn select(mask: Self::Mask, a: Self, b: Self) -> Self;
+}
+
+/// trait for component wise floating point classification of vectors, returning a boolean vector mask
+pub trait VecFloatCompare<T: Float>: VecCompare<T> {
+    /// returns a mask which is true for components of `a` that are not a number (`nan`)
+    fn is_nan_mask(a: Self) -> Self::Mask;
+    /// returns a mask which is true for components of `a` that are `inf`
+    fn is_infinite_mask(a: Self) -> Self::Mask;
+    /// returns a mask which is true for components of `a` that are finite
+    fn is_finite_mask(a: Self) -> Self::Mask;
+}
+
+/// trait for boolean vector masks produced by component wise comparisons
+pub trait VecMask:
+    Copy + Not<Output=Self> +
+    BitAnd<Output=Self> + BitOr<Output=Self> + BitXor<Output=Self> {
+    /// returns a mask with all components set to `v`
+    fn splat(v: bool) -> Self;
+    /// returns true if all components of the mask `a` are true
+    fn all(a: Self) -> bool;
+    /// returns true if any component of the mask `a` is true
+    fn any(a: Self) -> bool;
+    /// returns true if no components of the mask `a` are true
+    fn none(a: Self) -> bool;
+}
+
 //
 // Macro Implementation
 //
@@ -904,6 +954,10 @@ macro_rules! vec_impl {
         impl<T> Add<Self> for $VecN<T> where T: Number {
             type Output = Self;
             fn add(self, other: Self) -> Self {
+                #[cfg(feature = "simd")]
+                if let Some([$($field),+]) = crate::simd::add4([$(self.$field),+], [$(other.$field),+]) {
+                    return Self { $($field),+ };
+                }
                 Self {
                     $($field: self.$field + other.$field,)+
                 }
@@ -943,6 +997,11 @@ macro_rules! vec_impl {
 
         impl<T> AddAssign<Self> for $VecN<T> where T: Number {
             fn add_assign(&mut self, other: Self) {
+                #[cfg(feature = "simd")]
+                if let Some([$($field),+]) = crate::simd::add4([$(self.$field),+], [$(other.$field),+]) {
+                    *self = Self { $($field),+ };
+                    return;
+                }
                 $(self.$field += other.$field;)+
             }
         }
@@ -963,6 +1022,10 @@ macro_rules! vec_impl {
         impl<T> Sub<Self> for $VecN<T> where T: Number {
             type Output = Self;
             fn sub(self, other: Self) -> Self {
+                #[cfg(feature = "simd")]
+                if let Some([$($field),+]) = crate::simd::sub4([$(self.$field),+], [$(other.$field),+]) {
+                    return Self { $($field),+ };
+                }
                 Self {
                     $($field: self.$field - other.$field,)+
                 }
@@ -1002,6 +1065,11 @@ macro_rules! vec_impl {
 
         impl<T> SubAssign<Self> for $VecN<T> where T: Number {
             fn sub_assign(&mut self, other: Self) {
+                #[cfg(feature = "simd")]
+                if let Some([$($field),+]) = crate::simd::sub4([$(self.$field),+], [$(other.$field),+]) {
+                    *self = Self { $($field),+ };
+                    return;
+                }
                 $(self.$field -= other.$field;)+
             }
         }
@@ -1022,6 +1090,10 @@ macro_rules! vec_impl {
         impl<T> Mul<Self> for $VecN<T> where T: Number {
             type Output = Self;
             fn mul(self, other: Self) -> Self {
+                #[cfg(feature = "simd")]
+                if let Some([$($field),+]) = crate::simd::mul4([$(self.$field),+], [$(other.$field),+]) {
+                    return Self { $($field),+ };
+                }
                 Self {
                     $($field: self.$field * other.$field,)+
                 }
@@ -1061,6 +1133,11 @@ macro_rules! vec_impl {
 
         impl<T> MulAssign<Self> for $VecN<T> where T: Number {
             fn mul_assign(&mut self, other: Self) {
+                #[cfg(feature = "simd")]
+                if let Some([$($field),+]) = crate::simd::mul4([$(self.$field),+], [$(other.$field),+]) {
+                    *self = Self { $($field),+ };
+                    return;
+                }
                 $(self.$field *= other.$field;)+
             }
         }
@@ -1081,6 +1158,10 @@ macro_rules! vec_impl {
         impl<T> Div<Self> for $VecN<T> where T: Number {
             type Output = Self;
             fn div(self, other: Self) -> Self {
+                #[cfg(feature = "simd")]
+                if let Some([$($field),+]) = crate::simd::div4([$(self.$field),+], [$(other.$field),+]) {
+                    return Self { $($field),+ };
+                }
                 Self {
                     $($field: self.$field / other.$field,)+
                 }
@@ -1120,6 +1201,11 @@ macro_rules! vec_impl {
 
         impl<T> DivAssign<Self> for $VecN<T> where T: Number {
             fn div_assign(&mut self, other: Self) {
+                #[cfg(feature = "simd")]
+                if let Some([$($field),+]) = crate::simd::div4([$(self.$field),+], [$(other.$field),+]) {
+                    *self = Self { $($field),+ };
+                    return;
+                }
                 $(self.$field /= other.$field;)+
             }
         }
@@ -1176,6 +1262,130 @@ macro_rules! vec_impl {
             }
         }
 
+        impl<T> VecCompare<T> for $VecN<T> where T: Number {
+            type Mask = $VecN<bool>;
+
+            fn less_than(a: Self, b: Self) -> $VecN<bool> {
+                $VecN {
+                    $($field: a.$field < b.$field,)+
+                }
+            }
+
+            fn less_equal(a: Self, b: Self) -> $VecN<bool> {
+                $VecN {
+                    $($field: a.$field <= b.$field,)+
+                }
+            }
+
+            fn greater_than(a: Self, b: Self) -> $VecN<bool> {
+                $VecN {
+                    $($field: a.$field > b.$field,)+
+                }
+            }
+
+            fn greater_equal(a: Self, b: Self) -> $VecN<bool> {
+                $VecN {
+                    $($field: a.$field >= b.$field,)+
+                }
+            }
+
+            fn equal(a: Self, b: Self) -> $VecN<bool> {
+                $VecN {
+                    $($field: a.$field == b.$field,)+
+                }
+            }
+
+            fn not_equal(a: Self, b: Self) -> $VecN<bool> {
+                $VecN {
+                    $($field: a.$field != b.$field,)+
+                }
+            }
+
+            fn select(mask: $VecN<bool>, a: Self, b: Self) -> Self {
+                Self {
+                    $($field: if mask.$field { a.$field } else { b.$field },)+
+                }
+            }
+        }
+
+        impl<T> VecFloatCompare<T> for $VecN<T> where T: Float + FloatOps<T> {
+            fn is_nan_mask(a: Self) -> $VecN<bool> {
+                $VecN {
+                    $($field: T::is_nan(a.$field) != T::zero(),)+
+                }
+            }
+
+            fn is_infinite_mask(a: Self) -> $VecN<bool> {
+                $VecN {
+                    $($field: T::is_infinite(a.$field) != T::zero(),)+
+                }
+            }
+
+            fn is_finite_mask(a: Self) -> $VecN<bool> {
+                $VecN {
+                    $($field: T::is_finite(a.$field) != T::zero(),)+
+                }
+            }
+        }
+
+        impl VecMask for $VecN<bool> {
+            fn splat(v: bool) -> Self {
+                $VecN {
+                    $($field: v,)+
+                }
+            }
+
+            fn all(a: Self) -> bool {
+                $(a.$field &&)+
+                true
+            }
+
+            fn any(a: Self) -> bool {
+                $(a.$field ||)+
+                false
+            }
+
+            fn none(a: Self) -> bool {
+                !Self::any(a)
+            }
+        }
+
+        impl Not for $VecN<bool> {
+            type Output = Self;
+            fn not(self) -> Self::Output {
+                Self {
+                    $($field: !self.$field,)+
+                }
+            }
+        }
+
+        impl BitAnd<Self> for $VecN<bool> {
+            type Output = Self;
+            fn bitand(self, other: Self) -> Self {
+                Self {
+                    $($field: self.$field & other.$field,)+
+                }
+            }
+        }
+
+        impl BitOr<Self> for $VecN<bool> {
+            type Output = Self;
+            fn bitor(self, other: Self) -> Self {
+                Self {
+                    $($field: self.$field | other.$field,)+
+                }
+            }
+        }
+
+        impl BitXor<Self> for $VecN<bool> {
+            type Output = Self;
+            fn bitxor(self, other: Self) -> Self {
+                Self {
+                    $($field: self.$field ^ other.$field,)+
+                }
+            }
+        }
+
         impl<T> Eq for $VecN<T> where T: Eq  {}
         impl<T> PartialEq for $VecN<T> where T: PartialEq  {
             fn eq(&self, other: &Self) -> bool {
@@ -1455,6 +1665,36 @@ macro_rules! vec_cast {
     }
 }
 
+/// macro to stamp out `vec_cast!` in both directions between every pair of the listed types
+macro_rules! vec_cast_all {
+    ($VecN:ident $fields:tt, $t:ident) => {};
+    ($VecN:ident $fields:tt, $t:ident, $($u:ident),+) => {
+        $(vec_cast!($VecN $fields, $t, $u);)+
+        vec_cast_all!($VecN $fields, $($u),+);
+    }
+}
+
+/// macro to stamp out casts in both directions between `f16` and a wider float type
+macro_rules! vec_cast_half {
+    ($VecN:ident { $($field:ident),+ }, $t:ident, $from:ident, $to:ident) => {
+        impl From<$VecN<f16>> for $VecN<$t> {
+            fn from(other: $VecN<f16>) -> $VecN<$t> {
+                $VecN {
+                    $($field: other.$field.$to(),)+
+                }
+            }
+        }
+
+        impl From<$VecN<$t>> for $VecN<f16> {
+            fn from(other: $VecN<$t>) -> $VecN<f16> {
+                $VecN {
+                    $($field: f16::$from(other.$field),)+
+                }
+            }
+        }
+    }
+}
+
 //
 // Macro Decl
 //
@@ -1499,6 +1739,69 @@ vec_scalar_lhs!(Vec3 { x, y, z }, u32);
 #[cfg(feature = "lhs_scalar_vec_ops")]
 vec_scalar_lhs!(Vec4 { x, y, z, w }, u32);
 
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec2 { x, y }, i8);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec3 { x, y, z }, i8);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec4 { x, y, z, w }, i8);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec2 { x, y }, u8);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec3 { x, y, z }, u8);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec4 { x, y, z, w }, u8);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec2 { x, y }, i16);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec3 { x, y, z }, i16);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec4 { x, y, z, w }, i16);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec2 { x, y }, u16);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec3 { x, y, z }, u16);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec4 { x, y, z, w }, u16);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec2 { x, y }, i64);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec3 { x, y, z }, i64);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec4 { x, y, z, w }, i64);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec2 { x, y }, u64);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec3 { x, y, z }, u64);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec4 { x, y, z, w }, u64);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec2 { x, y }, f16);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec3 { x, y, z }, f16);
+
+#[cfg(feature = "lhs_scalar_vec_ops")]
+vec_scalar_lhs!(Vec4 { x, y, z, w }, f16);
+
 #[cfg(feature = "short_hand_constructors")]
 vec_ctor!(Vec2 { x, y }, vec2f, splat2f, f32);
 
@@ -1535,56 +1838,101 @@ vec_ctor!(Vec3 { x, y, z }, vec3u, splat3u, u32);
 #[cfg(feature = "short_hand_constructors")]
 vec_ctor!(Vec4 { x, y, z, w }, vec4u, splat4u, u32);
 
-#[cfg(feature = "casts")]
-vec_cast!(Vec2 { x, y }, f64, i32);
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec2 { x, y }, vec2i8, splat2i8, i8);
 
-#[cfg(feature = "casts")]
-vec_cast!(Vec2 { x, y }, f64, u32);
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec3 { x, y, z }, vec3i8, splat3i8, i8);
 
-#[cfg(feature = "casts")]
-vec_cast!(Vec2 { x, y }, f32, f64);
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec4 { x, y, z, w }, vec4i8, splat4i8, i8);
 
-#[cfg(feature = "casts")]
-vec_cast!(Vec2 { x, y }, f32, i32);
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec2 { x, y }, vec2u8, splat2u8, u8);
 
-#[cfg(feature = "casts")]
-vec_cast!(Vec2 { x, y }, f32, u32);
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec3 { x, y, z }, vec3u8, splat3u8, u8);
 
-#[cfg(feature = "casts")]
-vec_cast!(Vec2 { x, y }, i32, u32);
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec4 { x, y, z, w }, vec4u8, splat4u8, u8);
 
-#[cfg(feature = "casts")]
-vec_cast!(Vec3 {x, y, z}, f64, i32);
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec2 { x, y }, vec2i16, splat2i16, i16);
 
-#[cfg(feature = "casts")]
-vec_cast!(Vec3 {x, y, z}, f64, u32);
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec3 { x, y, z }, vec3i16, splat3i16, i16);
 
-#[cfg(feature = "casts")]
-vec_cast!(Vec3 {x, y, z}, f32, f64);
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec4 { x, y, z, w }, vec4i16, splat4i16, i16);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec2 { x, y }, vec2u16, splat2u16, u16);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec3 { x, y, z }, vec3u16, splat3u16, u16);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec4 { x, y, z, w }, vec4u16, splat4u16, u16);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec2 { x, y }, vec2i64, splat2i64, i64);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec3 { x, y, z }, vec3i64, splat3i64, i64);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec4 { x, y, z, w }, vec4i64, splat4i64, i64);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec2 { x, y }, vec2u64, splat2u64, u64);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec3 { x, y, z }, vec3u64, splat3u64, u64);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec4 { x, y, z, w }, vec4u64, splat4u64, u64);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec2 { x, y }, vec2h, splat2h, f16);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec3 { x, y, z }, vec3h, splat3h, f16);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec4 { x, y, z, w }, vec4h, splat4h, f16);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec2 { x, y }, bvec2, splat2b, bool);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec3 { x, y, z }, bvec3, splat3b, bool);
+
+#[cfg(feature = "short_hand_constructors")]
+vec_ctor!(Vec4 { x, y, z, w }, bvec4, splat4b, bool);
 
 #[cfg(feature = "casts")]
-vec_cast!(Vec3 {x, y, z}, f32, i32);
+vec_cast_all!(Vec2 { x, y }, f32, f64, i8, u8, i16, u16, i32, u32, i64, u64);
 
 #[cfg(feature = "casts")]
-vec_cast!(Vec3 {x, y, z}, f32, u32);
+vec_cast_all!(Vec3 { x, y, z }, f32, f64, i8, u8, i16, u16, i32, u32, i64, u64);
 
 #[cfg(feature = "casts")]
-vec_cast!(Vec3 {x, y, z}, i32, u32);
+vec_cast_all!(Vec4 { x, y, z, w }, f32, f64, i8, u8, i16, u16, i32, u32, i64, u64);
 
 #[cfg(feature = "casts")]
-vec_cast!(Vec4 {x, y, z, w}, f64, i32);
+vec_cast_half!(Vec2 { x, y }, f32, from_f32, to_f32);
 
 #[cfg(feature = "casts")]
-vec_cast!(Vec4 {x, y, z, w}, f64, u32);
+vec_cast_half!(Vec3 { x, y, z }, f32, from_f32, to_f32);
 
 #[cfg(feature = "casts")]
-vec_cast!(Vec4 {x, y, z, w}, f32, f64);
+vec_cast_half!(Vec4 { x, y, z, w }, f32, from_f32, to_f32);
 
 #[cfg(feature = "casts")]
-vec_cast!(Vec4 {x, y, z, w}, f32, i32);
+vec_cast_half!(Vec2 { x, y }, f64, from_f64, to_f64);
 
 #[cfg(feature = "casts")]
-vec_cast!(Vec4 {x, y, z, w}, f32, u32);
+vec_cast_half!(Vec3 { x, y, z }, f64, from_f64, to_f64);
 
 #[cfg(feature = "casts")]
-vec_cast!(Vec4 {x, y, z, w}, i32, u32);
\ No newline at end of file
+vec_cast_half!(Vec4 { x, y, z, w }, f64, from_f64, to_f64);
\ No newline at end of file
@@ -1,3 +1,14 @@
+// the test data is generated with full precision literals and written in a plain assert style
+#![allow(
+    clippy::approx_constant,
+    clippy::bool_assert_comparison,
+    clippy::excessive_precision,
+    clippy::legacy_numeric_constants,
+    clippy::needless_range_loop,
+    clippy::unnecessary_cast,
+    clippy::useless_vec
+)]
+
 use maths_rs::prelude::*;
 
 #[test]
@@ -5388,7 +5399,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-3.040000, 4.030000),
             vec2f(11.680000, -3.760000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, true);
     }
     {
@@ -5407,7 +5418,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(2.442000, 4.030000),
             vec2f(17.162001, -3.760000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, false);
     }
     {
@@ -5426,7 +5437,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-21.227001, 4.030000),
             vec2f(-6.507000, -3.760000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, false);
     }
     {
@@ -5445,7 +5456,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-18.017000, -3.950000),
             vec2f(-9.607000, -1.030000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, true);
     }
     {
@@ -5464,7 +5475,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-18.889000, -3.950000),
             vec2f(-10.479000, -1.030000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, false);
     }
     {
@@ -5483,7 +5494,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(7.469000, -3.950000),
             vec2f(15.879000, -1.030000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, true);
     }
     {
@@ -5502,7 +5513,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(7.469000, -23.654001),
             vec2f(15.879000, -20.734001),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, false);
     }
     {
@@ -5519,7 +5530,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(7.035999, -18.804001),
             vec2f(8.535999, -17.584000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, true);
     }
     {
@@ -5536,7 +5547,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(7.035999, 6.696001),
             vec2f(8.535999, 7.916000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, true);
     }
     {
@@ -5553,7 +5564,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(7.035999, 1.336000),
             vec2f(8.535999, 2.556000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, true);
     }
     {
@@ -5571,7 +5582,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-2.884000, 17.401001),
             vec2f(5.666000, 5.071001),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, false);
     }
     {
@@ -5589,7 +5600,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-17.945999, 17.401001),
             vec2f(-9.396000, 5.071001),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, false);
     }
     {
@@ -5607,7 +5618,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-17.945999, 7.426000),
             vec2f(-9.396000, -4.904000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, true);
     }
     {
@@ -5625,7 +5636,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-24.586000, 2.676000),
             vec2f(-7.936001, 3.536000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, true);
     }
     {
@@ -5643,7 +5654,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-24.586000, -5.241000),
             vec2f(-7.936001, -4.381000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, false);
     }
     {
@@ -5661,7 +5672,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(8.985001, -5.241000),
             vec2f(25.635000, -4.381000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, false);
     }
     {
@@ -5679,7 +5690,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(4.230001, -5.241000),
             vec2f(20.880001, -4.381000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, true);
     }
     {
@@ -5698,7 +5709,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(7.246000, -5.201000),
             vec2f(8.316000, -7.891000),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, false);
     }
     {
@@ -5717,7 +5728,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(7.246000, -16.161001),
             vec2f(8.316000, -18.851002),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, true);
     }
     {
@@ -5736,7 +5747,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-6.689001, -16.161001),
             vec2f(-5.619000, -18.851002),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, false);
     }
     {
@@ -5753,7 +5764,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-9.189000, -18.011002),
             vec2f(6.041000, -17.561001),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, false);
     }
     {
@@ -5770,7 +5781,7 @@ fn convex_hull_vs_convex_hull_test() {
             vec2f(-24.174002, -10.285001),
             vec2f(-8.944000, -9.835001),
         ];
-        let overlap = convex_hull_vs_convex_hull(hull, hull2);
+        let overlap = convex_hull_vs_convex_hull(&hull, &hull2);
         assert_eq!(overlap, true);
     }
 }
@@ -6191,6 +6202,18 @@ fn test_serde() {
     assert_eq!("{\"m\":[1.0,0.0,0.0,0.0,0.0,1.0,0.0,0.0,0.0,0.0,1.0,0.0,0.0,0.0,0.0,1.0]}", serde_m);
     assert_eq!("{\"x\":1.0,\"y\":1.0,\"z\":1.0,\"w\":1.0}", serde_v);
     assert_eq!("{\"x\":0.0,\"y\":0.0,\"z\":0.0,\"w\":1.0}", serde_q);
+
+    let t = Transformf::new(vec3f(1.0, 2.0, 3.0), Quatf::identity(), vec3f(2.0, 2.0, 2.0));
+    let serde_t = serde_json::to_string(&t).unwrap();
+    assert_eq!("{\"translation\":{\"x\":1.0,\"y\":2.0,\"z\":3.0},\"rotation\":{\"x\":0.0,\"y\":0.0,\"z\":0.0,\"w\":1.0},\"scale\":{\"x\":2.0,\"y\":2.0,\"z\":2.0}}", serde_t);
+    assert_eq!(serde_json::from_str::<Transformf>(&serde_t).unwrap(), t);
+
+    let mn = Matrixf::<2, 3>::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let serde_mn = serde_json::to_string(&mn).unwrap();
+    assert_eq!("[1.0,2.0,3.0,4.0,5.0,6.0]", serde_mn);
+    assert_eq!(serde_json::from_str::<Matrixf<2, 3>>(&serde_mn).unwrap(), mn);
+    assert_eq!(serde_json::from_str::<Vectorf<3>>("[1.0,2.0,3.0]").unwrap(), Vectorf::new([1.0, 2.0, 3.0]));
+    assert!(serde_json::from_str::<Vectorf<3>>("[1.0,2.0]").is_err());
 }
 
 #[cfg(feature = "hash")]
@@ -6731,4 +6754,2134 @@ fn smooth_stop2_curve() {
     assert!(approx(smooth_stop2(1.0_f32, 0.0_f32, 1.0_f32, 1.0_f32), 1.0, eps));
     // "slow stop" means value > linear at t=0.5 (ease-out shape)
     assert!(smooth_stop2(0.5_f32, 0.0_f32, 1.0_f32, 1.0_f32) > 0.5);
-}
\ No newline at end of file
+}
+#[test]
+fn vec_compare_masks() {
+    let a = vec3f(1.0, 2.0, 3.0);
+    let b = vec3f(3.0, 2.0, 1.0);
+    assert_eq!(less_than(a, b), bvec3(true, false, false));
+    assert_eq!(less_equal(a, b), bvec3(true, true, false));
+    assert_eq!(greater_than(a, b), bvec3(false, false, true));
+    assert_eq!(greater_equal(a, b), bvec3(false, true, true));
+    assert_eq!(maths_rs::equal(a, b), bvec3(false, true, false));
+    assert_eq!(not_equal(a, b), bvec3(true, false, true));
+
+    let a = vec4i(-1, 0, 5, 7);
+    let b = vec4i(0, 0, 0, 0);
+    assert_eq!(less_than(a, b), bvec4(true, false, false, false));
+    assert_eq!(not(less_than(a, b)), greater_equal(a, b));
+
+    let m = bvec2(true, false);
+    assert!(any(m));
+    assert!(!all(m));
+    assert!(all(m | !m));
+    assert!(BVec2::none(m & !m));
+    assert_eq!(m ^ BVec2::splat(true), bvec2(false, true));
+}
+
+#[test]
+fn vec_select() {
+    let a = vec3f(1.0, 2.0, 3.0);
+    let b = vec3f(3.0, 2.0, 1.0);
+    assert_eq!(select(less_than(a, b), a, b), min(a, b));
+    assert_eq!(select(greater_than(a, b), a, b), max(a, b));
+    assert_eq!(select(splat3b(true), a, b), a);
+    assert_eq!(select(splat3b(false), a, b), b);
+
+    let v = vec4f(1.0, f32::NAN, f32::INFINITY, -2.0);
+    assert_eq!(Vec4f::is_nan_mask(v), bvec4(false, true, false, false));
+    assert_eq!(Vec4f::is_infinite_mask(v), bvec4(false, false, true, false));
+    assert_eq!(Vec4f::is_finite_mask(v), bvec4(true, false, false, true));
+    assert_eq!(select(Vec4f::is_finite_mask(v), v, Vec4f::zero()), vec4f(1.0, 0.0, 0.0, -2.0));
+}
+
+#[test]
+fn mat4_mul_kernels() {
+    // results must be bit identical with or without the simd feature
+    let a = Mat4f::from_translation(vec3f(1.0, -2.0, 3.5)) * Mat4f::from_z_rotation(0.7) * Mat4f::from_scale(vec3f(0.3, 1.7, 2.1));
+    let b = Mat4f::create_perspective_projection_lh_yup(1.2, 16.0 / 9.0, 0.1, 100.0) * Mat4f::from_x_rotation(-0.4);
+    let ab = a * b;
+    for r in 0..4 {
+        for c in 0..4 {
+            let row = a.get_row(r);
+            let col = b.get_column(c);
+            assert_eq!(ab.at(r, c), row.x * col.x + row.y * col.y + row.z * col.z + row.w * col.w);
+        }
+    }
+
+    let v = vec4f(0.25, -8.0, 3.0, 1.0);
+    let av = a * v;
+    for r in 0..4 {
+        let row = a.get_row(r);
+        assert_eq!(av[r as usize], row.x * v.x + row.y * v.y + row.z * v.z + row.w * v.w);
+    }
+}
+
+#[test]
+fn vec4_lane_kernels() {
+    // results must be bit identical with or without the simd feature
+    let a = vec4f(0.1, -2.5, 3.3, 1e-3);
+    let b = vec4f(7.0, 0.3, -1.1, 3.0);
+    assert_eq!(a + b, vec4f(a.x + b.x, a.y + b.y, a.z + b.z, a.w + b.w));
+    assert_eq!(a - b, vec4f(a.x - b.x, a.y - b.y, a.z - b.z, a.w - b.w));
+    assert_eq!(a * b, vec4f(a.x * b.x, a.y * b.y, a.z * b.z, a.w * b.w));
+    assert_eq!(a / b, vec4f(a.x / b.x, a.y / b.y, a.z / b.z, a.w / b.w));
+    assert_eq!(dot(a, b), a.x * b.x + a.y * b.y + a.z * b.z + a.w * b.w);
+    let mut c = a;
+    c += b;
+    c *= b;
+    c -= a;
+    c /= b;
+    assert_eq!(c, vec4f(((a.x + b.x) * b.x - a.x) / b.x, ((a.y + b.y) * b.y - a.y) / b.y, ((a.z + b.z) * b.z - a.z) / b.z, ((a.w + b.w) * b.w - a.w) / b.w));
+
+    // other element types and lengths keep the scalar code
+    assert_eq!(vec4d(1.0, 2.0, 3.0, 4.0) * vec4d(2.0, 2.0, 2.0, 2.0), vec4d(2.0, 4.0, 6.0, 8.0));
+    assert_eq!(vec4i(1, 2, 3, 4) - vec4i(4, 3, 2, 1), vec4i(-3, -1, 1, 3));
+    assert_eq!(vec3f(1.0, 2.0, 3.0) + vec3f(1.0, 1.0, 1.0), vec3f(2.0, 3.0, 4.0));
+}
+
+#[test]
+fn quat_mul_kernel() {
+    let a = Quatf::from_euler_angles(0.3, -1.2, 2.4);
+    let b = Quatf::from_axis_angle(normalize(vec3f(1.0, 2.0, -0.5)), 0.9);
+    let q = a * b;
+    assert_eq!(q.x, a.w * b.x + a.x * b.w + a.y * b.z - a.z * b.y);
+    assert_eq!(q.y, a.w * b.y - a.x * b.z + a.y * b.w + a.z * b.x);
+    assert_eq!(q.z, a.w * b.z + a.x * b.y - a.y * b.x + a.z * b.w);
+    assert_eq!(q.w, a.w * b.w - a.x * b.x - a.y * b.y - a.z * b.z);
+}
+
+#[test]
+fn integer_vec_types() {
+    // texels
+    let texel = vec4u8(255, 128, 0, 255);
+    assert_eq!(Vec4f::from(texel) / 255.0, vec4f(1.0, 128.0 / 255.0, 0.0, 1.0));
+    assert_eq!(Vec4u8::from(vec4f(255.0, 128.0, 0.0, 255.0)), texel);
+    assert_eq!(Vec4u16::from(texel), vec4u16(255, 128, 0, 255));
+    assert_eq!(2u8 * vec2u8(3, 4), splat2u8(6) + vec2u8(0, 2));
+
+    // voxel coordinates
+    let voxel = vec3i16(-4, 8, 12);
+    assert_eq!(Vec3i::from(voxel), vec3i(-4, 8, 12));
+    assert_eq!(voxel / 4i16, vec3i16(-1, 2, 3));
+    assert_eq!(Vec3i8::from(voxel), vec3i8(-4, 8, 12));
+    assert_eq!(Vec3u16::from(vec3u8(1, 2, 3)), vec3u16(1, 2, 3));
+
+    // large world positions
+    let pos = vec3i64(5_000_000_000, -3, 7);
+    assert_eq!(Vec3d::from(pos), vec3d(5_000_000_000.0, -3.0, 7.0));
+    assert_eq!(Vec3i64::from(vec3u64(1, 2, 3)) - 1i64 * splat3i64(1), vec3i64(0, 1, 2));
+    assert_eq!(Vec2u64::from(vec2i8(1, 2)), vec2u64(1, 2));
+    assert_eq!(Vec4i64::from(vec4u(u32::MAX, 0, 1, 2)), vec4i64(u32::MAX as i64, 0, 1, 2));
+
+    // lossy casts saturate float to int and truncate / wrap int to int
+    assert_eq!(Vec2u8::from(vec2f(300.0, -1.0)), vec2u8(255, 0));
+    assert_eq!(Vec2u8::from(vec2i(256, 257)), vec2u8(0, 1));
+}
+
+#[test]
+fn half_float() {
+    // exact values
+    assert_eq!(f16::from_f32(1.0).to_bits(), 0x3c00);
+    assert_eq!(f16::from_f32(-2.0).to_bits(), 0xc000);
+    assert_eq!(f16::from_f32(65504.0), f16::MAX);
+    assert_eq!(f16::from_f32(0.000061035156), f16::MIN_POSITIVE);
+    assert_eq!(f16::from_f32(-0.0).to_bits(), 0x8000);
+    assert_eq!(f16::from_f32(0.0), f16::from_f32(-0.0));
+
+    // rounding to nearest even, overflow and underflow
+    assert_eq!(f16::from_f32(1.0 + 1.0 / 2048.0).to_bits(), 0x3c00);
+    assert_eq!(f16::from_f32(1.0 + 3.0 / 2048.0).to_bits(), 0x3c02);
+    assert_eq!(f16::from_f32(65519.0), f16::MAX);
+    assert_eq!(f16::from_f32(65520.0), f16::INFINITY);
+    assert_eq!(f16::from_f32(1e10), f16::INFINITY);
+    assert_eq!(f16::from_f32(-1e10), f16::NEG_INFINITY);
+    assert_eq!(f16::from_f32(5.9604645e-8).to_bits(), 0x0001);
+    assert_eq!(f16::from_f32(2.9802322e-8).to_bits(), 0x0000);
+    assert_eq!(f16::from_f32(2.9802326e-8).to_bits(), 0x0001);
+    assert!(f16::from_f32(f32::NAN).is_nan());
+    assert!(f16::NAN != f16::NAN);
+
+    // every non-nan half round trips through f32
+    for bits in 0..=u16::MAX {
+        let h = f16::from_bits(bits);
+        if !h.is_nan() {
+            assert_eq!(f16::from_f32(h.to_f32()).to_bits(), bits);
+            assert_eq!(f16::from_f64(h.to_f64()).to_bits(), bits);
+        }
+    }
+
+    // f64 rounds once, via f32 the tail below f32 precision is lost and this would tie to even
+    assert_eq!(f16::from_f64(1.0 + 1.0 / 2048.0 + 1.0 / 1099511627776.0).to_bits(), 0x3c01);
+    assert_eq!(f16::from_f64(1.0 + 1.0 / 2048.0).to_bits(), 0x3c00);
+    assert_eq!(f16::from_f64(65520.0), f16::INFINITY);
+    assert_eq!(f16::from_f64(1e300), f16::INFINITY);
+    assert_eq!(f16::from_f64(1.0 / 33554432.0).to_bits(), 0x0000);
+    assert_eq!(f16::from_f64(1.0 / 33554432.0 + 1e-20).to_bits(), 0x0001);
+    assert_eq!(f16::from_f64(1e-300).to_bits(), 0x0000);
+    assert_eq!(f16::from_f64(-0.0).to_bits(), 0x8000);
+    assert!(f16::from_f64(f64::NAN).is_nan());
+    for bits in (0..u32::MAX).step_by(65521) {
+        let v = f32::from_bits(bits);
+        if !v.is_nan() {
+            assert_eq!(f16::from_f64(v as f64).to_bits(), f16::from_f32(v).to_bits());
+        }
+    }
+
+    // number traits
+    let a = f16::from_f32(1.5);
+    let b = f16::from_f32(0.25);
+    assert_eq!(a + b, f16::from_f32(1.75));
+    assert_eq!(a * b, f16::from_f32(0.375));
+    assert_eq!(-a, f16::from_f32(-1.5));
+    assert_eq!(abs(-a), a);
+    assert_eq!(min(a, b), b);
+    assert_eq!(f16::minus_one(), f16::NEG_ONE);
+    assert_eq!(<f16 as Cast<f16>>::from_i32(7).as_i32(), 7);
+    assert!(a > b);
+}
+
+#[test]
+fn half_float_vec() {
+    let v = vec3h(f16::ONE, f16::from_f32(2.0), f16::from_f32(-0.5));
+    assert_eq!(Vec3f::from(v), vec3f(1.0, 2.0, -0.5));
+    assert_eq!(Vec3h::from(vec3f(1.0, 2.0, -0.5)), v);
+    assert_eq!(Vec3d::from(v), vec3d(1.0, 2.0, -0.5));
+    assert_eq!(v * f16::two(), vec3h(f16::two(), f16::four(), f16::NEG_ONE));
+    assert_eq!(f16::two() * v, v + v);
+    assert_eq!(std::mem::size_of::<Vec4h>(), 8);
+
+    let src = vec![vec4f(0.1, 0.2, 0.3, 1.0), vec4f(-100.0, 0.0, 1024.0, 0.5)];
+    let half = vec4f_to_vec4h(&src);
+    let back = vec4h_to_vec4f(&half);
+    for (a, b) in src.iter().zip(&back) {
+        assert!(approx(*a, *b, 0.001));
+    }
+
+    let mut dst = vec![Vec4h::zero(); 2];
+    vec4f_to_vec4h_slice(&src, &mut dst);
+    assert_eq!(dst, half);
+    let mut dstf = vec![Vec4f::zero(); 2];
+    vec4h_to_vec4f_slice(&dst, &mut dstf);
+    assert_eq!(dstf, back);
+}
+
+#[test]
+fn fixed_point_scalar() {
+    let fx = Fixed32::from_f32;
+    let eps = fx(0.0005);
+
+    // arithmetic
+    assert_eq!(fx(1.5) + fx(2.25), fx(3.75));
+    assert_eq!(fx(1.5) * fx(-2.0), fx(-3.0));
+    assert_eq!(fx(3.0) / fx(4.0), fx(0.75));
+    assert_eq!(fx(1.0) / Fixed32::zero(), Fixed32::MAX);
+    assert_eq!(Fixed32::MAX + Fixed32::ONE, Fixed32::MAX);
+    assert_eq!(Fixed32::floor(fx(-1.25)), fx(-2.0));
+    assert_eq!(Fixed32::ceil(fx(-1.25)), fx(-1.0));
+    assert_eq!(Fixed32::round(fx(2.5)), fx(3.0));
+    assert_eq!(Fixed32::trunc(fx(-1.75)), fx(-1.0));
+    assert_eq!(Fixed32::frac(fx(-1.75)), fx(-0.75));
+
+    // transcendental functions against f64
+    let mut x: f64 = -10.0;
+    while x < 10.0 {
+        let v = fx(x as f32);
+        assert!(approx(Fixed32::sin(v), fx(f64::sin(v.to_f64()) as f32), eps));
+        assert!(approx(Fixed32::cos(v), fx(f64::cos(v.to_f64()) as f32), eps));
+        assert!(approx(Fixed32::atan(v), fx(f64::atan(v.to_f64()) as f32), eps));
+        assert!(approx(Fixed32::atan2(v, fx(0.7)), fx(f64::atan2(v.to_f64(), 0.7) as f32), eps));
+        assert!(approx(Fixed32::atan2(fx(-0.3), v), fx(f64::atan2(-0.3, v.to_f64()) as f32), eps));
+        if x > 0.0 {
+            assert!(approx(Fixed32::sqrt(v), fx(f64::sqrt(v.to_f64()) as f32), eps));
+            assert!(approx(Fixed32::log2(v), fx(f64::log2(v.to_f64()) as f32), eps));
+        }
+        if x.abs() < 5.0 {
+            assert!(approx(Fixed32::exp(v), fx(f64::exp(v.to_f64()) as f32), fx(0.005)));
+        }
+        x += 0.173;
+    }
+    assert!(approx(Fixed32::powf(fx(2.0), fx(0.5)), fx(std::f32::consts::SQRT_2), eps));
+    assert_eq!(Fixed32::powi(fx(1.5), 5), fx(7.59375));
+    assert_eq!(Fixed32::powi(fx(2.0), -3), fx(0.125));
+    assert_eq!(Fixed32::powi(fx(-2.0), 3), fx(-8.0));
+    assert_eq!(Fixed32::powi(fx(3.0), 0), Fixed32::one());
+    assert_eq!(Fixed32::powi(Fixed32::one(), i32::MAX), Fixed32::one());
+    // hyperbolics saturate rather than overflow where exp reaches the top of the q32 range
+    for i in 20..=30 {
+        assert_eq!(Fixed32::cosh(Fixed32::from_int(i)), Fixed32::MAX);
+        assert_eq!(Fixed32::cosh(Fixed32::from_int(-i)), Fixed32::MAX);
+        assert_eq!(Fixed32::sinh(Fixed32::from_int(i)), Fixed32::MAX);
+    }
+    assert!(approx(Fixed32::cosh(fx(21.7)), Fixed32::MAX, eps));
+    assert!(approx(Fixed32::cosh(fx(2.0)), fx(2.0f32.cosh()), eps));
+    assert!(approx(Fixed32::asin(fx(0.5)), fx(std::f32::consts::FRAC_PI_6), eps));
+    assert!(approx(Fixed32::acos(fx(0.5)), fx(std::f32::consts::FRAC_PI_3), eps));
+
+    // q32.32 has much higher precision
+    let v = Fixed64::from_f64(0.9);
+    assert!(approx(Fixed64::sin(v), Fixed64::from_f64(0.9f64.sin()), Fixed64::from_f64(1e-8)));
+    assert!(approx(Fixed64::sqrt(v), Fixed64::from_f64(0.9f64.sqrt()), Fixed64::from_f64(1e-8)));
+    assert_eq!(Fixed64::from_int(1 << 20) * Fixed64::from_int(4), Fixed64::from_int(1 << 22));
+
+    // results are defined by raw integer values
+    assert_eq!(Fixed32::sin(Fixed32::one()).to_raw(), 55147);
+    assert_eq!(Fixed32::sqrt(Fixed32::two()).to_raw(), 92682);
+}
+
+#[test]
+fn fixed_point_generic() {
+    let fx = Fixed32::from_f32;
+    let v3 = |x: f32, y: f32, z: f32| Vec3::new(fx(x), fx(y), fx(z));
+    let eps = fx(0.001);
+
+    // the default machine epsilon is the smallest step above 1
+    assert_eq!(<Fixed32 as Float>::epsilon(), Fixed32::EPSILON);
+    assert_eq!(<Fixed64 as Float>::epsilon(), Fixed64::EPSILON);
+    assert_eq!(<f32 as Float>::epsilon(), f32::EPSILON);
+
+    // vector
+    let n = normalize(v3(3.0, 0.0, 4.0));
+    assert!(approx(n, v3(0.6, 0.0, 0.8), eps));
+    assert!(approx(length(v3(3.0, 0.0, 4.0)), fx(5.0), eps));
+    assert!(approx(cross(v3(1.0, 0.0, 0.0), v3(0.0, 1.0, 0.0)), v3(0.0, 0.0, 1.0), eps));
+
+    // matrix
+    let m = Mat4::from_translation(v3(1.0, 2.0, 3.0)) * Mat4::from_y_rotation(fx(0.5)) * Mat4::from_scale(v3(2.0, 2.0, 2.0));
+    let p = v3(0.5, -1.0, 2.0);
+    let tp = m * p;
+    assert!(approx(m.inverse() * tp, p, fx(0.005)));
+
+    // quaternion
+    let q = Quat::from_euler_angles(fx(0.0), fx(std::f32::consts::FRAC_PI_2), fx(0.0));
+    assert!(approx(q * v3(1.0, 0.0, 0.0), v3(0.0, 0.0, -1.0), eps));
+    let s = Quat::slerp(Quat::identity(), q, fx(0.5));
+    assert!(approx(Quat::to_euler_angles(s).1, fx(std::f32::consts::FRAC_PI_4), eps));
+
+    // intersection
+    let hit = ray_vs_sphere(v3(-10.0, 0.0, 0.0), v3(1.0, 0.0, 0.0), v3(0.0, 0.0, 0.0), fx(2.0));
+    assert!(approx(hit.unwrap(), v3(-2.0, 0.0, 0.0), eps));
+    let hit = ray_vs_aabb(v3(-10.0, 0.5, 0.5), v3(1.0, 0.0, 0.0), v3(-1.0, -1.0, -1.0), v3(1.0, 1.0, 1.0));
+    assert!(approx(hit.unwrap(), v3(-1.0, 0.5, 0.5), eps));
+    let hit = ray_vs_triangle(v3(0.2, 0.2, 5.0), v3(0.0, 0.0, -1.0), v3(0.0, 0.0, 0.0), v3(1.0, 0.0, 0.0), v3(0.0, 1.0, 0.0));
+    assert!(approx(hit.unwrap(), v3(0.2, 0.2, 0.0), eps));
+    assert!(aabb_vs_aabb(v3(0.0, 0.0, 0.0), v3(1.0, 1.0, 1.0), v3(0.5, 0.5, 0.5), v3(2.0, 2.0, 2.0)));
+    assert!(point_inside_triangle(v3(0.2, 0.2, 0.0), v3(0.0, 0.0, 0.0), v3(1.0, 0.0, 0.0), v3(0.0, 1.0, 0.0)));
+}
+
+#[test]
+fn dual_quat() {
+    let eps = 0.0001;
+    let r = Quatf::from_euler_angles(0.3, -0.7, 1.1);
+    let t = vec3f(1.0, -2.0, 3.0);
+    let dq = DualQuatf::from_rotation_translation(r, t);
+    assert!(approx(dq.get_translation(), t, eps));
+
+    // transform matches rotate then translate
+    let p = vec3f(0.5, 4.0, -1.5);
+    assert!(approx(dq * p, r * p + t, eps));
+    assert!(approx(dq.transform_vector(p), r * p, eps));
+
+    // matrix round trip
+    let m = Mat34f::from(dq);
+    assert!(approx(m * p, dq * p, eps));
+    let dq2 = DualQuatf::from(m);
+    assert!(approx(dq2 * p, dq * p, eps));
+    assert!(approx(Mat4f::from(dq) * p, dq * p, eps));
+
+    // composition and inverse
+    let dqb = DualQuatf::from_rotation_translation(Quatf::from_axis_angle(Vec3f::unit_y(), 0.8), vec3f(-3.0, 0.0, 1.0));
+    assert!(approx((dq * dqb) * p, dq * (dqb * p), eps));
+    assert!(approx(dq.inverse() * (dq * p), p, eps));
+    assert!(approx(dq.conjugate() * (dq * p), p, eps));
+
+    // normalize
+    let n = DualQuatf::new(dq.real * 3.0, dq.dual * 3.0).normalize();
+    assert!(approx(n * p, dq * p, eps));
+}
+
+#[test]
+fn dual_quat_blend() {
+    let eps = 0.0001;
+    let a = DualQuatf::from_rotation_translation(Quatf::identity(), vec3f(0.0, 0.0, 0.0));
+    let b = DualQuatf::from_rotation_translation(Quatf::from_axis_angle(Vec3f::unit_z(), 1.0), vec3f(2.0, 0.0, 0.0));
+
+    // end points
+    let p = vec3f(1.0, 1.0, 0.0);
+    assert!(approx(DualQuatf::sclerp(a, b, 0.0) * p, a * p, eps));
+    assert!(approx(DualQuatf::sclerp(a, b, 1.0) * p, b * p, eps));
+    assert!(approx(slerp(a, b, 1.0) * p, b * p, eps));
+
+    // half way is the square root of the screw motion
+    let h = DualQuatf::sclerp(a, b, 0.5);
+    assert!(approx((h * h) * p, b * p, eps));
+    assert!(approx(Quatf::to_euler_angles(h.real).2, 0.5, eps));
+
+    // pure translation
+    let c = DualQuatf::from_translation(vec3f(4.0, 2.0, 0.0));
+    assert!(approx(DualQuatf::sclerp(a, c, 0.25).get_translation(), vec3f(1.0, 0.5, 0.0), eps));
+
+    // dlb with equal weights of antipodal representations of the same transform
+    let d = DualQuatf::dlb(&[b, -b], &[0.5, 0.5]);
+    assert!(approx(d * p, b * p, eps));
+
+    // dlb preserves rigidity where linear matrix blending would shrink
+    let e = DualQuatf::from_rotation(Quatf::from_axis_angle(Vec3f::unit_z(), std::f32::consts::PI * 0.9));
+    let d = DualQuatf::dlb(&[a, e], &[0.5, 0.5]);
+    let q = vec3f(1.0, 0.0, 0.0);
+    assert!(approx(length(d * q), 1.0, eps));
+    assert!(approx(nlerp(a, e, 0.5) * q, d * q, eps));
+}
+
+#[test]
+fn transform() {
+    let eps = 0.0001;
+    let r = Quatf::from_euler_angles(0.3, -0.7, 1.1);
+    let t = Transformf::new(vec3f(1.0, -2.0, 3.0), r, vec3f(2.0, 3.0, 0.5));
+    let p = vec3f(0.5, 4.0, -1.5);
+
+    // matches the equivalent matrix
+    let m = Mat4f::from(t);
+    let expected = Mat4f::from_translation(t.translation) * Mat4f::from(r) * Mat4f::from_scale(t.scale);
+    assert!(approx(m * p, expected * p, eps));
+    assert!(approx(t * p, Mat34f::from(t) * p, eps));
+    assert!(approx(t.transform_vector(p), Mat3f::from(Mat34f::from(t)) * p, eps));
+
+    // round trip through matrices
+    let t2 = Transformf::from(m);
+    assert!(approx(t2.translation, t.translation, eps));
+    assert!(approx(t2.scale, t.scale, eps));
+    assert!(approx(t2 * p, t * p, eps));
+    let t3 = Transformf::from(Mat34f::from(t));
+    assert!(approx(t3 * p, t * p, eps));
+
+    // composition applies rhs first
+    let a = Transformf::new(vec3f(0.0, 5.0, 0.0), Quatf::from_axis_angle(Vec3f::unit_y(), 0.5), splat3f(2.0));
+    let ab = a * t;
+    assert!(approx(ab * p, a * (t * p), eps));
+    assert!(approx(Mat4f::from(ab) * p, (Mat4f::from(a) * Mat4f::from(t)) * p, eps));
+    let mut c = a;
+    c *= t;
+    assert_eq!(c, ab);
+
+    // inverse with uniform scale
+    let inv = a.inverse();
+    assert!(approx(inv * (a * p), p, eps));
+    assert!(approx((a * inv) * p, p, eps));
+    assert_eq!(Transformf::default(), Transformf::identity());
+    assert!(approx(Transformf::identity() * p, p, eps));
+
+    // reflection is recovered as negative scale
+    let f = Transformf::from(Mat34f::from_scale(vec3f(-2.0, 1.0, 1.0)));
+    assert!(approx(f * p, vec3f(-1.0, 4.0, -1.5), eps));
+}
+
+#[test]
+fn transform_interpolate() {
+    let eps = 0.0001;
+    let a = Transformf::from_translation(vec3f(0.0, 0.0, 0.0));
+    let b = Transformf::new(vec3f(2.0, 4.0, 0.0), Quatf::from_axis_angle(Vec3f::unit_z(), 1.0), splat3f(3.0));
+
+    // end points
+    let p = vec3f(1.0, 0.0, 0.0);
+    assert!(approx(slerp(a, b, 0.0) * p, a * p, eps));
+    assert!(approx(slerp(a, b, 1.0) * p, b * p, eps));
+    assert!(approx(lerp(a, b, 1.0) * p, b * p, eps));
+
+    // half way
+    let h = slerp(a, b, 0.5);
+    assert!(approx(h.translation, vec3f(1.0, 2.0, 0.0), eps));
+    assert!(approx(h.scale, splat3f(2.0), eps));
+    assert!(approx(Quatf::to_euler_angles(h.rotation).2, 0.5, eps));
+
+    // lerp keeps rotation normalised
+    let l = lerp(a, b, 0.5);
+    assert!(approx(Quatf::mag(l.rotation), 1.0, eps));
+    assert!(approx(l.rotation * p, h.rotation * p, eps));
+}
+
+#[test]
+fn mat_decompose() {
+    let eps = 0.0001;
+    let r = Quatf::from_euler_angles(0.4, 1.2, -0.6);
+    let t = vec3f(3.0, -1.0, 8.0);
+    let p = vec3f(1.0, 2.0, 3.0);
+
+    // non-uniform scale round trips
+    let s = vec3f(1.0, 2.0, 0.25);
+    let m = Mat4f::from_trs(t, r, s);
+    assert!(Mat4f::approx(m, Mat4f::from_translation(t) * Mat4f::from(r) * Mat4f::from_scale(s), eps));
+    let (dt, dr, ds) = m.decompose();
+    assert!(approx(dt, t, eps));
+    assert!(approx(ds, s, eps));
+    assert!(approx(dr * p, r * p, eps));
+    assert!(Mat4f::approx(Mat4f::from_trs(dt, dr, ds), m, eps));
+
+    // reflection is returned as negative x scale
+    for s in [vec3f(-2.0, 1.0, 3.0), vec3f(2.0, -1.0, 3.0), vec3f(2.0, 1.0, -3.0), vec3f(-1.0, -1.0, -1.0)] {
+        let m = Mat34f::from_trs(t, r, s);
+        let (dt, dr, ds) = m.decompose();
+        assert!(ds.x < 0.0);
+        assert!(ds.y > 0.0 && ds.z > 0.0);
+        assert!(approx(abs(ds), abs(s), eps));
+        assert!(approx(dt, t, eps));
+        assert!(approx(Quatf::mag(dr), 1.0, eps));
+        assert!(Mat34f::approx(Mat34f::from_trs(dt, dr, ds), m, eps));
+    }
+
+    // zero scaled axes still produce a valid rotation
+    let m = Mat34f::from_trs(t, r, vec3f(2.0, 0.0, 3.0));
+    let (_, dr, ds) = m.decompose();
+    assert!(approx(ds, vec3f(2.0, 0.0, 3.0), eps));
+    assert!(approx(dr * p, r * p, eps));
+    assert!(Mat34f::approx(Mat34f::from_trs(t, dr, ds), m, eps));
+
+    let m = Mat34f::from_trs(t, r, vec3f(0.0, 0.0, 3.0));
+    let (_, dr, ds) = m.decompose();
+    assert!(approx(Quatf::mag(dr), 1.0, eps));
+    assert!(Mat34f::approx(Mat34f::from_trs(t, dr, ds), m, eps));
+
+    let (_, dr, ds) = Mat34f::from_scale(Vec3f::zero()).decompose();
+    assert_eq!(ds, Vec3f::zero());
+    assert!(approx(Quatf::mag(dr), 1.0, eps));
+
+    // identity
+    let (dt, dr, ds) = Mat4d::identity().decompose();
+    assert_eq!(dt, Vec3d::zero());
+    assert_eq!(ds, Vec3d::one());
+    assert!(approx(dr.w, 1.0, 0.0001));
+}
+
+#[test]
+fn look_at_view_matrices() {
+    let eps = 0.001;
+    let vp = vec2f(1280.0, 720.0);
+    let eye = vec3f(3.0, 4.0, -8.0);
+    let target = vec3f(-1.0, 0.5, 2.0);
+    let up = Vec3f::unit_y();
+    let dist = length(target - eye);
+
+    // left handed, view looks along -z
+    let view = Mat4f::look_at_lh(eye, target, up);
+    assert!(approx(view * eye, Vec3f::zero(), eps));
+    assert!(approx(view * target, vec3f(0.0, 0.0, -dist), eps));
+    let proj = Mat4f::create_perspective_projection_lh_yup(f32::pi() / 3.0, vp.x / vp.y, 0.1, 100.0);
+    let sc = project_to_sc(target, proj * view, vp);
+    assert!(approx(sc.xy(), vp * 0.5, eps));
+    assert!(sc.z > 0.0 && sc.z < 1.0);
+
+    // right of the camera in a left handed world is cross(up, forward)
+    let right = cross(up, target - eye);
+    assert!((proj * view * Vec4f::from((target, 1.0))).w > 0.0);
+    assert!(project_to_sc(target + right, proj * view, vp).x > vp.x * 0.5);
+    assert!(project_to_sc(target + up, proj * view, vp).y > vp.y * 0.5);
+
+    // right handed, paired with the same projection as the view space matches
+    let view = Mat4f::look_at_rh(eye, target, up);
+    assert!(approx(view * eye, Vec3f::zero(), eps));
+    assert!(approx(view * target, vec3f(0.0, 0.0, -dist), eps));
+    assert!(approx(view * (target + cross(target - eye, up)), vec3f(mag(cross(target - eye, up)), 0.0, -dist), eps));
+    let sc = project_to_sc(target, proj * view, vp);
+    assert!(approx(sc.xy(), vp * 0.5, eps));
+    assert!(sc.z > 0.0 && sc.z < 1.0);
+
+    // right of the camera in a right handed world is cross(forward, up), and points in front have positive clip w
+    let right = cross(target - eye, up);
+    assert!((proj * view * Vec4f::from((target, 1.0))).w > 0.0);
+    assert!(project_to_sc(target + right, proj * view, vp).x > vp.x * 0.5);
+    assert!(project_to_sc(target + up, proj * view, vp).y > vp.y * 0.5);
+
+    // look_to and mat34 variants match
+    let dir = target - eye;
+    assert!(Mat4f::approx(Mat4f::look_to_lh(eye, dir, up), Mat4f::look_at_lh(eye, target, up), eps));
+    assert!(Mat4f::approx(Mat4f::look_to_rh(eye, dir, up), Mat4f::look_at_rh(eye, target, up), eps));
+    assert!(Mat4f::approx(Mat4f::from(Mat34f::look_at_lh(eye, target, up)), Mat4f::look_at_lh(eye, target, up), eps));
+    assert!(Mat4f::approx(Mat4f::from(Mat34f::look_to_rh(eye, dir, up)), Mat4f::look_to_rh(eye, dir, up), eps));
+
+    // views are orthonormal, rh is a rotation and lh contains a reflection
+    let r = Mat3f::from(Mat34f::look_at_rh(eye, target, up));
+    assert!(approx(r.determinant(), 1.0, eps));
+    let l = Mat3f::from(Mat34f::look_at_lh(eye, target, up));
+    assert!(approx(l.determinant(), -1.0, eps));
+
+    // up parallel to the view direction still produces a valid view
+    let view = Mat34f::look_at_rh(vec3f(0.0, 10.0, 0.0), Vec3f::zero(), up);
+    assert!(approx(view * Vec3f::zero(), vec3f(0.0, 0.0, -10.0), eps));
+    assert!(approx(Mat3f::from(view).determinant(), 1.0, eps));
+}
+
+#[test]
+fn perspective_projection_depth_range() {
+    let eps = 0.0001;
+    let fov = f32::pi() / 2.0;
+    let aspect = 16.0 / 9.0;
+    let near = 0.5;
+    let far = 50.0;
+
+    // -1 to 1 matches the original lh projection
+    let lh = Mat4f::create_perspective_projection_lh_yup(fov, aspect, near, far);
+    let lh_no = Mat4f::create_perspective_projection_lh_yup_depth(fov, aspect, near, far, DepthRange::NegOneToOne);
+    assert!(Mat4f::approx(lh, lh_no, eps));
+
+    // near and far land on the requested depth range for both handedness
+    let ranges = [DepthRange::NegOneToOne, DepthRange::ZeroToOne, DepthRange::Reversed];
+    for depth in ranges {
+        for proj in [
+            Mat4f::create_perspective_projection_lh_yup_depth(fov, aspect, near, far, depth),
+            Mat4f::create_perspective_projection_rh_yup_depth(fov, aspect, near, far, depth)
+        ] {
+            assert!(approx(project_to_ndc(vec3f(0.0, 0.0, -near), proj).z, depth.near(), eps));
+            assert!(approx(project_to_ndc(vec3f(0.0, 0.0, -far), proj).z, depth.far(), eps));
+            assert!(approx(project_to_ndc(vec3f(0.0, 0.0, -10.0), proj).xy(), Vec2f::zero(), eps));
+        }
+
+        // infinite far plane approaches the far depth
+        for proj in [
+            Mat4f::create_perspective_projection_infinite_lh_yup(fov, aspect, near, depth),
+            Mat4f::create_perspective_projection_infinite_rh_yup(fov, aspect, near, depth)
+        ] {
+            assert!(approx(project_to_ndc(vec3f(0.0, 0.0, -near), proj).z, depth.near(), eps));
+            assert!(approx(project_to_ndc(vec3f(0.0, 0.0, -1000000.0), proj).z, depth.far(), 0.001));
+        }
+    }
+
+    // reversed-z keeps far away depths distinguishable in f32
+    let proj = Mat4f::create_perspective_projection_infinite_lh_yup(fov, aspect, 0.1, DepthRange::Reversed);
+    let z0 = project_to_ndc(vec3f(0.0, 0.0, -10000.0), proj).z;
+    let z1 = project_to_ndc(vec3f(0.0, 0.0, -10001.0), proj).z;
+    assert!(z1 < z0);
+    let z = project_to_ndc(vec3f(0.0, 0.0, -1.0), proj).z;
+    assert!(approx(z, 0.1, eps));
+}
+
+#[test]
+fn frustum_depth_range() {
+    let eps = 0.001;
+    let fov = f32::pi() / 2.0;
+    let near = 1.0;
+    let far = 10.0;
+    let vp = vec2f(1280.0, 720.0);
+    for depth in [DepthRange::NegOneToOne, DepthRange::ZeroToOne, DepthRange::Reversed] {
+        for proj in [
+            Mat4f::create_perspective_projection_lh_yup_depth(fov, 1.0, near, far, depth),
+            Mat4f::create_perspective_projection_rh_yup_depth(fov, 1.0, near, far, depth)
+        ] {
+            // corners lie on the near and far planes
+            let corners = proj.get_frustum_corners_depth(depth);
+            for c in &corners[0..4] {
+                assert!(approx(c.z, -near, eps));
+                assert!(approx(abs(c.x), near, eps));
+            }
+            for c in &corners[4..8] {
+                assert!(approx(c.z, -far, eps));
+                assert!(approx(abs(c.y), far, eps));
+            }
+
+            // planes clip exactly at near and far
+            let planes = proj.get_frustum_planes_depth(depth);
+            assert!(point_inside_frustum(vec3f(0.0, 0.0, -near * 1.01), &planes));
+            assert!(!point_inside_frustum(vec3f(0.0, 0.0, -near * 0.99), &planes));
+            assert!(point_inside_frustum(vec3f(0.0, 0.0, -far * 0.99), &planes));
+            assert!(!point_inside_frustum(vec3f(0.0, 0.0, -far * 1.01), &planes));
+            assert!(point_inside_frustum(vec3f(4.9, 0.0, -5.0), &planes));
+            assert!(!point_inside_frustum(vec3f(5.1, 0.0, -5.0), &planes));
+            assert!(!point_inside_frustum(vec3f(0.0, -5.1, -5.0), &planes));
+
+            // screen space round trip with 0-1 window depth
+            let p = vec3f(1.0, -2.0, -7.0);
+            let sc = project_to_sc_depth(p, proj, vp, depth);
+            assert!(sc.z >= 0.0 && sc.z <= 1.0);
+            assert!(approx(unproject_sc_depth(sc, proj, vp, depth), p, eps));
+        }
+
+        // infinite far plane has no far clip
+        let proj = Mat4f::create_perspective_projection_infinite_lh_yup(fov, 1.0, near, depth);
+        let planes = proj.get_frustum_planes_depth(depth);
+        assert_eq!(planes[5], Vec4f::zero());
+        assert!(point_inside_frustum(vec3f(0.0, 0.0, -100000.0), &planes));
+        assert!(!point_inside_frustum(vec3f(0.0, 0.0, -near * 0.99), &planes));
+        assert!(sphere_vs_frustum(vec3f(0.0, 0.0, -1000.0), 1.0, &planes));
+        assert!(aabb_vs_frustum(vec3f(0.0, 0.0, -1000.0), splat3f(1.0), &planes));
+        let corners = proj.get_frustum_corners_depth(depth);
+        assert!(approx(corners[0].z, -near, eps));
+    }
+
+    // the original planes and corners without a depth range are unchanged, ordered left, top, right, bottom, near, far
+    let proj = Mat4f::create_perspective_projection_rh_yup(fov, 1.0, near, far);
+    let k = 0.5773503;
+    let expected = [
+        vec4f(-k, 0.0, -k, 0.0),
+        vec4f(0.0, k, -k, 0.0),
+        vec4f(k, 0.0, -k, 0.0),
+        vec4f(0.0, -k, -k, 0.0),
+        vec4f(0.0, 0.0, 1.0, 1.818182),
+        vec4f(0.0, 0.0, -1.0, -1.0)
+    ];
+    let planes = proj.get_frustum_planes();
+    for i in 0..6 {
+        assert!(approx(planes[i], expected[i], eps));
+    }
+    let corners = proj.get_frustum_corners();
+    assert!(approx(corners[0], vec3f(1.818182, -1.818182, -1.818182), eps));
+    assert!(approx(corners[7], vec3f(-1.0, 1.0, -1.0), eps));
+    let planes = Mat4f::create_perspective_projection_lh_yup(fov, 1.0, near, far).get_frustum_planes();
+    assert!(approx(planes[0], vec4f(-k, 0.0, k, 0.0), eps));
+    assert!(approx(planes[5], vec4f(0.0, 0.0, -1.0, -10.0), eps));
+}
+
+#[test]
+fn quat_extended() {
+    let eps = 0.0001;
+    let qv = |q: Quatf| vec4f(q.x, q.y, q.z, q.w);
+
+    // from_to
+    let a = vec3f(1.0, 2.0, -0.5);
+    let b = vec3f(-3.0, 0.5, 2.0);
+    let q = Quatf::from_to(a, b);
+    assert!(approx(q * normalize(a), normalize(b), eps));
+    assert!(approx(Quatf::mag(q), 1.0, eps));
+    assert!(approx(Quatf::from_to(a, a).w, 1.0, eps));
+
+    // antiparallel vectors rotate 180 degrees about a perpendicular axis
+    for v in [Vec3f::unit_x(), Vec3f::unit_y(), Vec3f::unit_z(), vec3f(1.0, -1.0, 0.5)] {
+        let q = Quatf::from_to(v, -v);
+        assert!(approx(q * normalize(v), -normalize(v), eps));
+        assert!(approx(Quatf::mag(q), 1.0, eps));
+        let (axis, angle) = q.to_axis_angle();
+        assert!(approx(dot(axis, v), 0.0, eps));
+        assert!(approx(angle, f32::pi(), eps));
+    }
+
+    // scaled and nearly antiparallel non axis vectors where rounding keeps the cross product above zero
+    let pairs = [
+        (vec3f(1.0, 2.0, 3.0), vec3f(-2.0, -4.0, -6.0)),
+        (vec3f(0.3, -0.7, 0.2), vec3f(-3.0, 7.0, -2.0)),
+        (vec3f(-5.0, 1.0, 9.0), vec3f(5.0, -1.0, -9.0) * 0.1),
+        (vec3f(0.1, 0.2, 0.3), vec3f(-0.1, -0.2, -0.3) + vec3f(1e-7, 0.0, 0.0))
+    ];
+    for (a, b) in pairs {
+        let q = Quatf::from_to(a, b);
+        assert!(approx(q * normalize(a), normalize(b), 0.001));
+        assert!(approx(Quatf::mag(q), 1.0, eps));
+        assert!(approx(q.to_axis_angle().1, f32::pi(), 0.001));
+    }
+
+    // look rotation
+    let forward = vec3f(1.0, 0.5, -1.0);
+    let q = Quatf::look_rotation(forward, Vec3f::unit_y());
+    assert!(approx(q * Vec3f::unit_z(), normalize(forward), eps));
+    assert!(approx(dot(q * Vec3f::unit_x(), Vec3f::unit_y()), 0.0, eps));
+    assert!((q * Vec3f::unit_y()).y > 0.0);
+    assert!(approx(Quatf::look_rotation(Vec3f::unit_z(), Vec3f::unit_y()).w, 1.0, eps));
+    let q = Quatf::look_rotation(Vec3f::unit_y(), Vec3f::unit_y());
+    assert!(approx(q * Vec3f::unit_z(), Vec3f::unit_y(), eps));
+
+    // axis angle round trip
+    let axis = normalize(vec3f(0.3, -0.8, 0.2));
+    let q = Quatf::from_axis_angle(axis, 2.0);
+    let (qa, qt) = q.to_axis_angle();
+    assert!(approx(qa, axis, eps));
+    assert!(approx(qt, 2.0, eps));
+    let (qa, qt) = (-q).to_axis_angle();
+    assert!(approx(qa, axis, eps));
+    assert!(approx(qt, 2.0, eps));
+    let (qa, qt) = Quatf::from_axis_angle(axis, 5.0).to_axis_angle();
+    assert!(approx(qa, -axis, eps));
+    assert!(approx(qt, f32::two_pi() - 5.0, eps));
+    assert_eq!(Quatf::identity().to_axis_angle(), (Vec3f::unit_x(), 0.0));
+
+    // angle between
+    let q0 = Quatf::from_axis_angle(axis, 0.5);
+    let q1 = Quatf::from_axis_angle(axis, 1.75);
+    assert!(approx(Quatf::angle_between(q0, q1), 1.25, eps));
+    assert!(approx(Quatf::angle_between(q0, -q1), 1.25, eps));
+    assert!(approx(Quatf::angle_between(q0, q0), 0.0, 0.001));
+
+    // exp and log
+    let l = q.log();
+    assert!(approx(l.w, 0.0, eps));
+    assert!(approx(vec3f(l.x, l.y, l.z), axis, eps));
+    let e = l.exp();
+    assert!(approx(qv(e), qv(q), eps));
+    let g = Quatf::new(0.1, 0.2, -0.3, 1.5);
+    assert!(approx(qv(g.log().exp()), qv(g), eps));
+    assert!(approx(qv(Quatf::identity().log()), qv(Quatf::new(0.0, 0.0, 0.0, 0.0)), eps));
+    assert!(approx(qv(Quatf::new(0.0, 0.0, 0.0, 0.0).exp()), qv(Quatf::identity()), eps));
+
+    // pow scales the angle
+    let p = q.pow(0.5);
+    assert!(approx(qv(p * p), qv(q), eps));
+    assert!(approx(qv(q.pow(0.25)), qv(Quatf::from_axis_angle(axis, 0.5)), eps));
+    assert!(approx(qv(q.pow(0.0)), qv(Quatf::identity()), eps));
+    assert!(approx(qv(q.pow(1.0)), qv(q), eps));
+    assert!(approx(qv(q.pow(0.3)), qv(slerp(Quatf::identity(), q, 0.3)), eps));
+}
+
+#[test]
+fn euler_orders() {
+    let eps = 0.0001;
+    let qv = |q: Quatd| vec4d(q.x, q.y, q.z, q.w);
+    let orders = [
+        EulerOrder::XYZ, EulerOrder::XZY, EulerOrder::YXZ, EulerOrder::YZX, EulerOrder::ZXY, EulerOrder::ZYX,
+        EulerOrder::XYX, EulerOrder::XZX, EulerOrder::YXY, EulerOrder::YZY, EulerOrder::ZXZ, EulerOrder::ZYZ
+    ];
+
+    // xyz matches the original euler angle functions
+    let q = Quatd::from_euler(EulerOrder::XYZ, 0.3, -0.6, 1.2);
+    assert!(approx(qv(q), qv(Quatd::from_euler_angles(0.3, -0.6, 1.2)), eps));
+    let (a, b, c) = Quatd::from_euler_angles(0.3, -0.6, 1.2).to_euler(EulerOrder::XYZ);
+    assert!(approx(vec3d(a, b, c), Vec3d::from(Quatd::from_euler_angles(0.3, -0.6, 1.2).to_euler_angles()), eps));
+
+    let p = vec3d(0.4, -1.3, 2.2);
+    for order in orders {
+        let (i, j, _) = order.axes();
+        let unit = |n: usize| {
+            let mut v = Vec3d::zero();
+            v[n] = 1.0;
+            v
+        };
+
+        // rotations are applied about the fixed axes in sequence
+        let q = Quatd::from_euler(order, 0.5, 0.7, -0.9);
+        let expected = Quatd::from_axis_angle(unit(order.last_axis()), -0.9) * (Quatd::from_axis_angle(unit(j), 0.7) * (Quatd::from_axis_angle(unit(i), 0.5) * p));
+        assert!(approx(q * p, expected, eps));
+        assert!(approx(Mat3d::from_euler(order, 0.5, 0.7, -0.9) * p, expected, eps));
+        assert!(approx(Mat4d::from_euler(order, 0.5, 0.7, -0.9) * p, expected, eps));
+
+        // round trip angles within range
+        let b_values = if order.is_proper() { [0.2, 1.5, 2.9] } else { [-1.4, 0.1, 1.2] };
+        for a in [-3.0, -1.0, 0.25, 2.5] {
+            for b in b_values {
+                for c in [-2.0, 0.0, 1.7, 3.1] {
+                    let q = Quatd::from_euler(order, a, b, c);
+                    let (ea, eb, ec) = q.to_euler(order);
+                    assert!(approx(vec3d(ea, eb, ec), vec3d(a, b, c), eps), "{:?} {} {} {}", order, a, b, c);
+                    let (ma, mb, mc) = Mat4d::from(q).to_euler(order);
+                    assert!(approx(vec3d(ma, mb, mc), vec3d(a, b, c), eps));
+                }
+            }
+        }
+
+        // gimbal lock produces angles which reconstruct the same rotation
+        let locked = if order.is_proper() { [0.0, std::f64::consts::PI] } else { [std::f64::consts::FRAC_PI_2, -std::f64::consts::FRAC_PI_2] };
+        for b in locked {
+            let m = Mat3d::from_euler(order, 0.8, b, -0.3);
+            let (ea, eb, ec) = m.to_euler(order);
+            assert!(approx(eb, b, eps));
+            assert_eq!(ec, 0.0);
+            assert!(approx(Mat3d::from_euler(order, ea, eb, ec) * p, m * p, eps));
+        }
+    }
+
+    // f32 near gimbal lock stays stable
+    let q = Quatf::from_euler(EulerOrder::ZYX, 0.4, f32::pi() * 0.5 - 0.00001, 0.2);
+    let (a, b, c) = q.to_euler(EulerOrder::ZYX);
+    let r = Quatf::from_euler(EulerOrder::ZYX, a, b, c);
+    assert!(approx(r * vec3f(1.0, 2.0, 3.0), q * vec3f(1.0, 2.0, 3.0), 0.001));
+}
+
+#[test]
+fn const_generic_matrix() {
+    // 2x3 * 3x2 = 2x2
+    let a = Matrixf::<2, 3>::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let b = a.transpose();
+    assert_eq!(b, Matrixf::<3, 2>::new([[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]));
+    assert_eq!(a * b, Matrixf::<2, 2>::new([[14.0, 32.0], [32.0, 77.0]]));
+    assert_eq!(a * Vectorf::new([1.0, 0.0, -1.0]), Vectorf::new([-2.0, -2.0]));
+    assert_eq!(a.get_column(2), Vectorf::new([3.0, 6.0]));
+    assert_eq!(a.get_row(1), Vectorf::new([4.0, 5.0, 6.0]));
+    assert_eq!(a[(1, 2)], 6.0);
+    assert_eq!(a.as_slice(), &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(a * 2.0 - a, a);
+    assert_eq!(format!("{}", a), "[1, 2, 3]\n[4, 5, 6]");
+
+    // 6x6 inverse and determinant
+    let mut m = Matrixd::<6, 6>::identity();
+    for r in 0..6 {
+        for c in 0..6 {
+            m.m[r][c] += 1.0 / (1.0 + r as f64 + c as f64) + if r == (c + 1) % 6 { 2.0 } else { 0.0 };
+        }
+    }
+    let inv = m.inverse();
+    assert!(Matrixd::approx(m * inv, Matrixd::identity(), 0.00001));
+    assert!(Matrixd::approx(inv * m, Matrixd::identity(), 0.00001));
+    assert!(approx(m.determinant() * inv.determinant(), 1.0, 0.00001));
+
+    // row swaps change the sign of the determinant
+    let p = Matrixd::<3, 3>::new([[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 2.0]]);
+    assert_eq!(p.determinant(), -2.0);
+    assert_eq!(Matrixd::<3, 3>::new([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 1.0]]).determinant(), 0.0);
+
+    // agrees with the fixed size types
+    let m4 = Mat4d::from_translation(vec3d(1.0, 2.0, 3.0)) * Mat4d::from_z_rotation(0.3) * Mat4d::from_scale(vec3d(2.0, 3.0, 4.0));
+    let mn = Matrixd::<4, 4>::from(m4);
+    assert!(approx(mn.determinant(), m4.determinant(), 0.00001));
+    assert!(Mat4d::approx(Mat4d::from(mn.inverse()), m4.inverse(), 0.00001));
+    assert_eq!(Mat4d::from(mn.transpose()), m4.transpose());
+    assert_eq!(Mat4d::from(mn * mn), m4 * m4);
+
+    let m34 = Mat34f::from(Mat4f::from_x_rotation(0.5));
+    let mn34 = Matrixf::<3, 4>::from(m34);
+    assert_eq!(mn34.at(1, 2), m34.at(1, 2));
+    assert_eq!(Mat34f::from(mn34), m34);
+    assert_eq!(Mat43f::from(mn34.transpose()), m34.transpose());
+    assert_eq!(Mat2f::from(Matrixf::<2, 2>::from(Mat2f::from_z_rotation(0.1))), Mat2f::from_z_rotation(0.1));
+    assert_eq!(Mat3f::from(Matrixf::<3, 3>::from(Mat3f::from_y_rotation(0.2))), Mat3f::from_y_rotation(0.2));
+
+    let v3 = vec3f(1.0, 2.0, 3.0);
+    let vn = Vectorf::<3>::from(v3);
+    assert_eq!(Vec3f::from(vn), v3);
+    assert_eq!(Vec2f::from(Vectorf::<2>::from(vec2f(4.0, 5.0))), vec2f(4.0, 5.0));
+    assert_eq!(Vec4f::from(Vectorf::<4>::from(vec4f(4.0, 5.0, 6.0, 7.0))), vec4f(4.0, 5.0, 6.0, 7.0));
+    let m3 = Mat3f::from_x_rotation(0.7);
+    assert!(approx(Vec3f::from(Matrixf::<3, 3>::from(m3) * vn), m3 * v3, 0.0001));
+
+    // vector ops
+    let v = Vectorf::<5>::new([1.0, 2.0, 3.0, 4.0, 5.0]);
+    assert_eq!(Vectorf::dot(v, Vectorf::one()), 15.0);
+    assert_eq!(v.mag2(), 55.0);
+    assert!(approx(v.normalize().mag(), 1.0, 0.0001));
+    assert_eq!(-v + v, Vectorf::zero());
+}
+
+#[test]
+fn mat3_eigen_svd_polar() {
+    let eps = 0.0001;
+
+    // known eigen values
+    let m = Mat3d::new(
+        2.0, 1.0, 0.0,
+        1.0, 2.0, 0.0,
+        0.0, 0.0, 5.0
+    );
+    let (values, vectors) = m.eigen_symmetric();
+    assert!(approx(values, vec3d(5.0, 3.0, 1.0), eps));
+    assert!(approx(vectors.determinant(), 1.0, eps));
+    for i in 0..3 {
+        let v = vectors.get_column(i);
+        assert!(approx(m * v, v * values[i as usize], eps));
+    }
+    assert!(Mat3d::approx(vectors * Mat3d::from_scale(values) * vectors.transpose(), m, eps));
+
+    // rotated inertia tensor of a box recovers the principal axes
+    let r = Mat3d::from(Quatd::from_euler_angles(0.3, -0.7, 1.1));
+    let inertia = r * Mat3d::from_scale(vec3d(4.0, 9.0, 1.0)) * r.transpose();
+    let (values, vectors) = inertia.eigen_symmetric();
+    assert!(approx(values, vec3d(9.0, 4.0, 1.0), eps));
+    assert!(approx(abs(dot(vectors.get_column(0), r.get_column(1))), 1.0, eps));
+    assert!(approx(abs(dot(vectors.get_column(2), r.get_column(2))), 1.0, eps));
+
+    // repeated eigen values and diagonal input
+    let (values, vectors) = Mat3f::from_scale(vec3f(2.0, 7.0, 2.0)).eigen_symmetric();
+    assert!(approx(values, vec3f(7.0, 2.0, 2.0), 0.0001));
+    assert!(approx(abs(vectors.get_column(0).y), 1.0, 0.0001));
+
+    // svd of known matrix
+    let m = Mat3d::new(
+        3.0, 0.0, 0.0,
+        0.0, 0.0, -2.0,
+        0.0, 1.0, 0.0
+    );
+    let (u, s, v) = m.svd();
+    assert!(approx(s, vec3d(3.0, 2.0, 1.0), eps));
+    assert!(Mat3d::approx(u * Mat3d::from_scale(s) * v.transpose(), m, eps));
+
+    // svd of general matrix
+    let m = Mat3d::new(
+        1.0, 2.0, -1.0,
+        0.5, -3.0, 4.0,
+        2.0, 0.25, 1.5
+    );
+    let (u, s, v) = m.svd();
+    assert!(Mat3d::approx(u * Mat3d::from_scale(s) * v.transpose(), m, eps));
+    assert!(Mat3d::approx(u * u.transpose(), Mat3d::identity(), eps));
+    assert!(Mat3d::approx(v * v.transpose(), Mat3d::identity(), eps));
+    assert!(approx(u.determinant(), 1.0, eps) && approx(v.determinant(), 1.0, eps));
+    assert!(s.x >= s.y && s.y >= abs(s.z));
+    assert!(approx(s.x * s.y * s.z, m.determinant(), eps));
+
+    // reflection gives a negative smallest singular value
+    let (u, s, v) = Mat3f::from_scale(vec3f(-1.0, 2.0, 3.0)).svd();
+    assert!(approx(s, vec3f(3.0, 2.0, -1.0), 0.0001));
+    assert!(Mat3f::approx(u * Mat3f::from_scale(s) * v.transpose(), Mat3f::from_scale(vec3f(-1.0, 2.0, 3.0)), 0.0001));
+
+    // rank deficient
+    let m = Mat3d::new(
+        1.0, 2.0, 3.0,
+        2.0, 4.0, 6.0,
+        0.0, 0.0, 0.0
+    );
+    let (u, s, v) = m.svd();
+    assert!(approx(s.y, 0.0, eps) && approx(s.z, 0.0, eps));
+    assert!(Mat3d::approx(u * Mat3d::from_scale(s) * v.transpose(), m, eps));
+    assert!(approx(u.determinant(), 1.0, eps));
+    let (u, s, _) = Mat3d::zero().svd();
+    assert_eq!(s, Vec3d::zero());
+    assert!(approx(u.determinant(), 1.0, eps));
+
+    // polar decomposition of rotation * stretch
+    let stretch = Mat3d::new(
+        2.0, 0.5, 0.0,
+        0.5, 1.0, 0.25,
+        0.0, 0.25, 3.0
+    );
+    let (rot, st) = (r * stretch).polar();
+    assert!(Mat3d::approx(rot, r, eps));
+    assert!(Mat3d::approx(st, stretch, eps));
+    assert!(Mat3d::approx(rot * st, r * stretch, eps));
+
+    // polar of a reflection keeps a proper rotation
+    let m = r * Mat3d::from_scale(vec3d(1.0, -2.0, 1.5));
+    let (rot, st) = m.polar();
+    assert!(approx(rot.determinant(), 1.0, eps));
+    assert!(Mat3d::approx(st, st.transpose(), eps));
+    assert!(Mat3d::approx(rot * st, m, eps));
+}
+
+#[test]
+fn linear_solvers() {
+    let eps = 0.00001;
+
+    // lu with pivoting
+    let a = Mat3d::new(
+        0.0, 2.0, 1.0,
+        1.0, 1.0, 0.0,
+        3.0, 0.0, 4.0
+    );
+    let b = vec3d(1.0, 2.0, 3.0);
+    let (l, u, p) = a.lu().unwrap();
+    assert!(Mat3d::approx(p * a, l * u, eps));
+    for r in 0..3 {
+        assert_eq!(l.at(r, r), 1.0);
+        for c in r+1..3 {
+            assert_eq!(l.at(r, c), 0.0);
+            assert_eq!(u.at(c, r), 0.0);
+        }
+    }
+    let x = a.solve(b).unwrap();
+    assert!(approx(a * x, b, eps));
+    assert!(approx(x, a.inverse() * b, eps));
+
+    // qr
+    let (q, r) = a.qr().unwrap();
+    assert!(Mat3d::approx(q * r, a, eps));
+    assert!(Mat3d::approx(q * q.transpose(), Mat3d::identity(), eps));
+    assert!(r.at(0, 0) > 0.0 && r.at(1, 1) > 0.0 && r.at(2, 2) > 0.0);
+    assert!(r.at(1, 0) == 0.0 && r.at(2, 0) == 0.0 && r.at(2, 1) == 0.0);
+    assert!(approx(a.solve_qr(b).unwrap(), x, eps));
+
+    // cholesky on symmetric positive definite
+    let spd = Mat3d::new(
+        4.0, 12.0, -16.0,
+        12.0, 37.0, -43.0,
+        -16.0, -43.0, 98.0
+    );
+    let l = spd.cholesky().unwrap();
+    assert!(Mat3d::approx(l, Mat3d::new(2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0), eps));
+    assert!(approx(spd * spd.solve_cholesky(b).unwrap(), b, eps));
+
+    // singular and non positive definite input
+    let singular = Mat3d::new(
+        1.0, 2.0, 3.0,
+        4.0, 5.0, 6.0,
+        7.0, 8.0, 9.0
+    );
+    assert!(singular.lu().is_none());
+    assert!(singular.solve(b).is_none());
+    assert!(singular.qr().is_none());
+    assert!(singular.solve_qr(b).is_none());
+    assert!(Mat3d::zero().solve(b).is_none());
+    assert!(Mat2f::new(1.0, 2.0, 2.0, 4.0).solve(vec2f(1.0, 1.0)).is_none());
+    assert!(a.cholesky().is_none()); // not symmetric
+    assert!(Mat2d::new(1.0, 2.0, 2.0, 1.0).cholesky().is_none()); // indefinite
+    assert!(Mat2d::new(1.0, 2.0, 2.0, 1.0).solve_cholesky(vec2d(1.0, 1.0)).is_none());
+
+    // ill conditioned but not singular still solves
+    let m = Mat2d::new(1.0, 1.0, 1.0, 1.0 + 1e-9);
+    let x = m.solve(vec2d(2.0, 2.0 + 1e-9)).unwrap();
+    assert!(approx(x, vec2d(1.0, 1.0), 0.0001));
+
+    // mat2 and mat4 in f32
+    let m2 = Mat2f::new(3.0, 1.0, 1.0, 2.0);
+    assert!(approx(m2 * m2.solve(vec2f(9.0, 8.0)).unwrap(), vec2f(9.0, 8.0), 0.0001));
+    assert!(approx(m2 * m2.solve_cholesky(vec2f(9.0, 8.0)).unwrap(), vec2f(9.0, 8.0), 0.0001));
+    let m4 = Mat4f::from_translation(vec3f(1.0, -2.0, 3.0)) * Mat4f::from_y_rotation(0.4) * Mat4f::from_scale(vec3f(2.0, 0.5, 1.0));
+    let b4 = vec4f(1.0, 2.0, 3.0, 1.0);
+    assert!(approx(m4 * m4.solve(b4).unwrap(), b4, 0.0001));
+    assert!(approx(m4 * m4.solve_qr(b4).unwrap(), b4, 0.0001));
+    let spd4 = m4.transpose() * m4;
+    let l4 = spd4.cholesky().unwrap();
+    assert!(Mat4f::approx(l4 * l4.transpose(), spd4, 0.0001));
+    assert!(approx(spd4 * spd4.solve_cholesky(b4).unwrap(), b4, 0.001));
+
+    // const generic sizes
+    let mut m6 = Matrixd::<6, 6>::identity() * 4.0;
+    for i in 0..5 {
+        m6.m[i][i + 1] = -1.0;
+        m6.m[i + 1][i] = -1.0;
+    }
+    let b6 = Vectord::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    for x in [m6.solve(b6), m6.solve_qr(b6), m6.solve_cholesky(b6)] {
+        assert!(Vectord::approx(m6 * x.unwrap(), b6, eps));
+    }
+
+    assert_eq!(f32::epsilon(), f32::EPSILON);
+    assert_eq!(f64::epsilon(), f64::EPSILON);
+}
+
+#[test]
+fn primitives() {
+    // aabb construction and bounds
+    let aabb = Aabb::new(vec3f(-1.0, 0.0, 2.0), vec3f(3.0, 2.0, 4.0));
+    assert_eq!(aabb.center(), vec3f(1.0, 1.0, 3.0));
+    assert_eq!(aabb.extents(), vec3f(2.0, 1.0, 1.0));
+    assert_eq!(aabb.size(), vec3f(4.0, 2.0, 2.0));
+    assert_eq!(Aabb::from_center_extents(aabb.center(), aabb.extents()), aabb);
+    assert_eq!(Aabb::from_points(&aabb.corners()), aabb);
+    assert!(Aabb::<f32>::empty().is_empty());
+    assert!(Aabb::<f32, Vec3f>::from_points(&[]).is_empty());
+    assert_eq!(Aabb::<f32>::empty().merge(&aabb), aabb);
+    let merged = aabb.merge(&Aabb::new(vec3f(0.0, -5.0, 0.0), vec3f(1.0, 1.0, 1.0)));
+    assert_eq!(merged, Aabb::new(vec3f(-1.0, -5.0, 0.0), vec3f(3.0, 2.0, 4.0)));
+    assert_eq!(aabb.expand(vec3f(5.0, 1.0, 3.0)).max, vec3f(5.0, 2.0, 4.0));
+    assert_eq!(aabb.grow(1.0).min, vec3f(-2.0, -1.0, 1.0));
+    assert!(merged.contains_aabb(&aabb));
+    assert!(!aabb.contains_aabb(&merged));
+
+    // aabb wrappers match the free functions
+    let p = vec3f(5.0, -1.0, 3.5);
+    assert_eq!(aabb.contains_point(p), point_inside_aabb(p, aabb.min, aabb.max));
+    assert!(aabb.contains_point(aabb.center()));
+    assert_eq!(aabb.closest_point(p), closest_point_on_aabb(p, aabb.min, aabb.max));
+    assert_eq!(aabb.distance(p), point_aabb_distance(p, aabb.min, aabb.max));
+
+    // 2D aabb
+    let aabb2 = Aabb::new(vec2f(0.0, 0.0), vec2f(2.0, 1.0));
+    assert_eq!(aabb2.corners(), [vec2f(0.0, 0.0), vec2f(2.0, 0.0), vec2f(2.0, 1.0), vec2f(0.0, 1.0)]);
+    assert!(aabb2.intersects_sphere(&Sphere::new(vec2f(3.0, 0.5), 1.5)));
+    assert!(!aabb2.intersects_sphere(&Sphere::new(vec2f(3.0, 0.5), 0.5)));
+    assert!(aabb2.intersects_aabb(&Aabb::new(vec2f(1.0, 0.5), vec2f(4.0, 4.0))));
+    assert!(!aabb2.intersects_aabb(&Aabb::new(vec2f(3.0, 0.5), vec2f(4.0, 4.0))));
+
+    // sphere
+    let sphere = Sphere::new(vec3f(0.0, 1.0, 0.0), 2.0);
+    assert_eq!(sphere.aabb(), Aabb::new(vec3f(-2.0, -1.0, -2.0), vec3f(2.0, 3.0, 2.0)));
+    assert_eq!(sphere.closest_point(p), closest_point_on_sphere(p, sphere.center, sphere.radius));
+    assert_eq!(sphere.distance(p), point_sphere_distance(p, sphere.center, sphere.radius));
+    assert!(sphere.contains_point(vec3f(1.0, 1.0, 1.0)));
+    assert_eq!(sphere.intersects_aabb(&aabb), aabb_vs_sphere(aabb.min, aabb.max, sphere.center, sphere.radius));
+    assert_eq!(aabb.intersects_sphere(&sphere), sphere.intersects_aabb(&aabb));
+    assert!(sphere.intersects_sphere(&Sphere::new(vec3f(3.0, 1.0, 0.0), 1.5)));
+    assert!(!sphere.intersects_sphere(&Sphere::new(vec3f(3.0, 1.0, 0.0), 0.5)));
+    let merged = sphere.merge(&Sphere::new(vec3f(6.0, 1.0, 0.0), 1.0));
+    assert!(approx(merged.center, vec3f(2.5, 1.0, 0.0), 0.0001));
+    assert!(approx(merged.radius, 4.5, 0.0001));
+    assert_eq!(sphere.merge(&Sphere::new(vec3f(0.5, 1.0, 0.0), 0.5)), sphere);
+    assert!(approx(sphere.expand(vec3f(0.0, 5.0, 0.0)).radius, 3.0, 0.0001));
+    let plane = Plane::from_normal_and_point(vec3f(0.0, 2.0, 0.0), vec3f(0.0, 1.0, 0.0));
+    assert_eq!(sphere.classify_plane(&plane), Classification::Intersects);
+    assert_eq!(Sphere::new(vec3f(0.0, 5.0, 0.0), 1.0).classify_plane(&plane), Classification::Infront);
+
+    // capsule
+    let capsule = Capsule::new(vec3f(0.0, 0.0, 0.0), vec3f(0.0, 4.0, 0.0), 1.0);
+    assert_eq!(capsule.center(), vec3f(0.0, 2.0, 0.0));
+    assert_eq!(capsule.aabb(), Aabb::new(vec3f(-1.0, -1.0, -1.0), vec3f(1.0, 5.0, 1.0)));
+    assert!(capsule.contains_point(vec3f(0.5, 3.0, 0.0)));
+    assert!(!capsule.contains_point(vec3f(1.5, 3.0, 0.0)));
+    assert!(approx(capsule.closest_point(vec3f(3.0, 2.0, 0.0)), vec3f(1.0, 2.0, 0.0), 0.0001));
+    assert!(approx(capsule.distance(vec3f(3.0, 2.0, 0.0)), 2.0, 0.0001));
+    assert!(capsule.intersects_sphere(&Sphere::new(vec3f(2.0, 2.0, 0.0), 1.5)));
+    assert!(capsule.intersects_capsule(&Capsule::new(vec3f(-2.0, 1.0, 0.0), vec3f(2.0, 1.0, 0.0), 0.5)));
+    assert!(!capsule.intersects_capsule(&Capsule::new(vec3f(-2.0, 1.0, 3.0), vec3f(2.0, 1.0, 3.0), 0.5)));
+    assert_eq!(capsule.classify_plane(&plane), Classification::Intersects);
+
+    // plane
+    assert_eq!(plane.point(), vec3f(0.0, 2.0, 0.0));
+    assert_eq!(plane.distance(vec3f(3.0, 5.0, 1.0)), 3.0);
+    assert_eq!(plane.distance(vec3f(3.0, 0.0, 1.0)), -2.0);
+    assert_eq!(plane.closest_point(vec3f(3.0, 5.0, 1.0)), vec3f(3.0, 2.0, 1.0));
+    assert_eq!(plane.classify_point(vec3f(0.0, 1.0, 0.0)), Classification::Behind);
+    assert_eq!(Plane::from(Vec4f::from(plane)), plane);
+    let tri_plane = Plane::from_points(vec3f(0.0, 2.0, 0.0), vec3f(0.0, 2.0, 1.0), vec3f(1.0, 2.0, 0.0));
+    assert!(approx(tri_plane.normal, vec3f(0.0, 1.0, 0.0), 0.0001));
+    assert!(approx(tri_plane.distance, -2.0, 0.0001));
+
+    // ray
+    let ray = Ray::from_points(vec3f(-10.0, 1.0, 3.0), vec3f(0.0, 1.0, 3.0));
+    assert_eq!(ray.direction, vec3f(1.0, 0.0, 0.0));
+    assert_eq!(ray.at(2.0), vec3f(-8.0, 1.0, 3.0));
+    assert_eq!(ray.intersect_aabb(&aabb), ray_vs_aabb(ray.origin, ray.direction, aabb.min, aabb.max));
+    assert!(approx(ray.intersect_aabb(&aabb).unwrap(), vec3f(-1.0, 1.0, 3.0), 0.0001));
+    assert!(ray.intersect_sphere(&Sphere::new(vec3f(0.0, 1.0, 0.0), 2.0)).is_none());
+    assert!(approx(ray.intersect_sphere(&Sphere::new(vec3f(0.0, 1.0, 3.0), 2.0)).unwrap(), vec3f(-2.0, 1.0, 3.0), 0.0001));
+    assert!(ray.intersect_capsule(&capsule).is_none());
+    assert_eq!(ray.distance(vec3f(0.0, 4.0, 3.0)), 3.0);
+    let down = Ray::new(vec3f(0.5, 10.0, 0.25), vec3f(0.0, -1.0, 0.0));
+    assert!(approx(down.intersect_plane(&plane).unwrap(), vec3f(0.5, 2.0, 0.25), 0.0001));
+    assert!(approx(down.intersect_capsule(&capsule).unwrap(), vec3f(0.5, 4.0 + f32::sqrt(0.6875), 0.25), 0.0001));
+    let ray2 = Ray::new(vec2f(-5.0, 0.5), vec2f(1.0, 0.0));
+    assert!(approx(ray2.intersect_aabb(&aabb2).unwrap(), vec2f(0.0, 0.5), 0.0001));
+
+    // triangle
+    let tri = Triangle::new(vec3f(0.0, 0.0, 0.0), vec3f(0.0, 0.0, 3.0), vec3f(3.0, 0.0, 0.0));
+    assert_eq!(tri.center(), vec3f(1.0, 0.0, 1.0));
+    assert_eq!(tri.aabb(), Aabb::new(vec3f(0.0, 0.0, 0.0), vec3f(3.0, 0.0, 3.0)));
+    assert!(approx(tri.normal(), vec3f(0.0, 1.0, 0.0), 0.0001));
+    assert!(approx(tri.plane().distance, 0.0, 0.0001));
+    let (u, v, w) = tri.barycentric(tri.center());
+    assert!(approx(vec3f(u, v, w), vec3f(1.0, 1.0, 1.0) / 3.0, 0.0001));
+    assert!(tri.contains_point(vec3f(1.0, 0.0, 1.0)));
+    assert!(!tri.contains_point(vec3f(3.0, 0.0, 3.0)));
+    let p = vec3f(3.0, 2.0, 3.0);
+    assert_eq!(tri.closest_point(p), closest_point_on_triangle(p, tri.v0, tri.v1, tri.v2));
+    assert_eq!(tri.distance(p), point_triangle_distance(p, tri.v0, tri.v1, tri.v2));
+    assert!(approx(down.intersect_triangle(&tri).unwrap(), vec3f(0.5, 0.0, 0.25), 0.0001));
+    let tri2 = Triangle::new(vec2f(0.0, 0.0), vec2f(2.0, 0.0), vec2f(0.0, 2.0));
+    assert!(tri2.contains_point(vec2f(0.5, 0.5)));
+    assert!(approx(tri2.distance(vec2f(2.0, 2.0)), f32::sqrt(2.0), 0.0001));
+
+    // obb
+    let obb = Obb::new(vec3f(1.0, 2.0, 3.0), vec3f(2.0, 1.0, 0.5), Mat3f::from_y_rotation(f32::pi() * 0.25));
+    let mat = obb.get_matrix();
+    assert!(approx(mat * vec3f(0.0, 0.0, 0.0), obb.center, 0.0001));
+    let from_mat = Obb::from_matrix(mat);
+    assert!(approx(from_mat.center, obb.center, 0.0001));
+    assert!(approx(from_mat.extents, obb.extents, 0.0001));
+    assert!(Mat3f::approx(from_mat.rotation, obb.rotation, 0.0001));
+    let corners = obb.corners();
+    for c in corners {
+        assert!(approx(obb.distance(c), 0.0, 0.0001));
+    }
+    let bounds = obb.aabb();
+    assert!(approx(bounds.min, Aabb::from_points(&corners).min, 0.0001));
+    assert!(approx(bounds.max, Aabb::from_points(&corners).max, 0.0001));
+    let p = vec3f(4.0, 2.5, 3.0);
+    assert_eq!(obb.contains_point(p), point_inside_obb(p, mat));
+    assert_eq!(obb.closest_point(p), closest_point_on_obb(p, mat));
+    assert_eq!(obb.distance(p), point_obb_distance(p, mat));
+    assert!(obb.contains_point(obb.center));
+    assert!(obb.intersects_aabb(&aabb));
+    assert!(aabb.intersects_obb(&obb));
+    assert!(!obb.intersects_aabb(&Aabb::new(vec3f(10.0, 10.0, 10.0), vec3f(11.0, 11.0, 11.0))));
+    assert!(obb.intersects_sphere(&Sphere::new(vec3f(1.0, 4.0, 3.0), 1.5)));
+    assert!(!obb.intersects_sphere(&Sphere::new(vec3f(1.0, 4.0, 3.0), 0.5)));
+    assert!(obb.intersects_obb(&Obb::from_aabb(&aabb)));
+    assert!(approx(Ray::new(vec3f(1.0, 10.0, 3.0), vec3f(0.0, -1.0, 0.0)).intersect_obb(&obb).unwrap(), vec3f(1.0, 3.0, 3.0), 0.0001));
+
+    // frustum
+    let view = Mat4f::from_translation(vec3f(0.0, 0.0, -10.0));
+    let proj = Mat4f::create_perspective_projection_lh_yup(f32::deg_to_rad(60.0), 1.0, 0.1, 100.0);
+    let frustum = Frustum::from_matrix(proj * view);
+    assert_eq!(frustum, Frustum::from_matrix_depth(proj * view, DepthRange::NegOneToOne));
+    assert_eq!(frustum.plane(0), Plane::from(frustum.planes[0]));
+    assert!(frustum.contains_point(vec3f(0.0, 0.0, -20.0)));
+    assert!(!frustum.contains_point(vec3f(0.0, 0.0, 20.0)));
+    assert!(frustum.intersects_sphere(&Sphere::new(vec3f(0.0, 0.0, -20.0), 1.0)));
+    assert!(!frustum.intersects_sphere(&Sphere::new(vec3f(0.0, 0.0, 20.0), 1.0)));
+    assert!(frustum.intersects_aabb(&Aabb::from_center_extents(vec3f(0.0, 0.0, -20.0), vec3f(1.0, 1.0, 1.0))));
+    assert!(!frustum.intersects_aabb(&Aabb::from_center_extents(vec3f(0.0, 0.0, 20.0), vec3f(1.0, 1.0, 1.0))));
+    let bounds = frustum.aabb();
+    for c in frustum.corners {
+        assert!(bounds.grow(0.001).contains_point(c));
+    }
+    assert!(bounds.contains_point(frustum.center()));
+
+    // the near plane matches the -1 to 1 depth of the projection, corners are ordered top left, top right, bottom left, bottom right
+    assert!(frustum.contains_point(vec3f(0.0, 0.0, 9.85)));
+    assert!(!frustum.contains_point(vec3f(0.0, 0.0, 9.95)));
+    assert!(frustum.contains_point(vec3f(0.0, 0.0, -89.0)));
+    assert!(!frustum.contains_point(vec3f(0.0, 0.0, -91.0)));
+    assert!(approx(frustum.corners[0].z, 9.9, 0.001) && approx(frustum.corners[4].z, -90.0, 0.01));
+    assert!(frustum.corners[0].x < frustum.corners[1].x && frustum.corners[0].y > frustum.corners[2].y);
+    assert!(frustum.plane(0).normal.x < 0.0 && frustum.plane(1).normal.y > 0.0);
+    assert!(frustum.plane(2).normal.x > 0.0 && frustum.plane(3).normal.y < 0.0);
+}
+
+#[test]
+fn primitive_traits() {
+    fn count_overlaps<A: Intersects<B>, B>(a: &[A], b: &[B]) -> usize {
+        a.iter().map(|a| b.iter().filter(|b| a.intersects(*b)).count()).sum()
+    }
+
+    fn nearest<S: ClosestPoint<Vec3f> + Distance<f32, Vec3f>>(shapes: &[S], p: Vec3f) -> Vec3f {
+        let mut best = (f32::max_value(), p);
+        for s in shapes {
+            let d = s.distance(p);
+            if d < best.0 {
+                best = (d, s.closest_point(p));
+            }
+        }
+        best.1
+    }
+
+    let aabb = Aabb::new(vec3f(-1.0, -1.0, -1.0), vec3f(1.0, 1.0, 1.0));
+    let sphere = Sphere::new(vec3f(1.5, 0.0, 0.0), 1.0);
+    let far_sphere = Sphere::new(vec3f(10.0, 0.0, 0.0), 1.0);
+    let capsule = Capsule::new(vec3f(0.0, -5.0, 0.0), vec3f(0.0, 5.0, 0.0), 0.5);
+    let obb = Obb::new(vec3f(0.0, 2.0, 0.0), vec3f(1.0, 1.0, 1.0), Mat3f::from_z_rotation(0.5));
+    let plane = Plane::from_normal_and_point(vec3f(0.0, 0.0, 0.0), vec3f(0.0, 1.0, 0.0));
+    let ray = Ray::new(vec3f(-10.0, 0.0, 0.0), vec3f(1.0, 0.0, 0.0));
+    let tri = Triangle::new(vec3f(0.0, -1.0, -1.0), vec3f(0.0, -1.0, 1.0), vec3f(0.0, 1.0, 0.0));
+    let frustum = Frustum::from_matrix(Mat4f::create_perspective_projection_lh_yup(f32::deg_to_rad(60.0), 1.0, 0.1, 100.0));
+
+    // symmetric pairs agree and match the free functions
+    assert!(aabb.intersects(&sphere) && sphere.intersects(&aabb));
+    assert_eq!(aabb.intersects(&far_sphere), aabb_vs_sphere(aabb.min, aabb.max, far_sphere.center, far_sphere.radius));
+    assert!(sphere.intersects(&sphere));
+    assert!(!sphere.intersects(&far_sphere));
+    assert!(capsule.intersects(&sphere) == sphere.intersects(&capsule));
+    assert!(capsule.intersects(&capsule));
+    assert!(aabb.intersects(&obb) && obb.intersects(&aabb));
+    assert!(!obb.intersects(&sphere) && !sphere.intersects(&obb));
+    assert!(obb.intersects(&obb));
+    assert!(aabb.intersects(&plane) && plane.intersects(&aabb));
+    assert!(!far_sphere.intersects(&Plane::from_normal_and_point(vec3f(5.0, 0.0, 0.0), vec3f(1.0, 0.0, 0.0))));
+    assert!(capsule.intersects(&plane) && plane.intersects(&sphere));
+    assert!(ray.intersects(&aabb) && aabb.intersects(&ray));
+    assert!(ray.intersects(&far_sphere) && far_sphere.intersects(&ray));
+    assert!(ray.intersects(&tri) && tri.intersects(&ray));
+    assert!(ray.intersects(&capsule) && capsule.intersects(&ray));
+    assert!(!ray.intersects(&obb) && !obb.intersects(&ray));
+    assert!(!Ray::new(vec3f(0.0, 1.0, 0.0), vec3f(0.0, 1.0, 0.0)).intersects(&plane));
+    assert!(Ray::new(vec3f(0.0, 1.0, 0.0), vec3f(0.0, -1.0, 0.0)).intersects(&plane));
+    let in_view = Sphere::new(vec3f(0.0, 0.0, -20.0), 1.0);
+    assert!(frustum.intersects(&in_view) && in_view.intersects(&frustum));
+    assert!(!frustum.intersects(&far_sphere) && !far_sphere.intersects(&frustum));
+    assert!(frustum.intersects(&aabb) && aabb.intersects(&frustum));
+    let behind = Aabb::from_center_extents(vec3f(0.0, 0.0, 5.0), splat3f(1.0));
+    assert!(!frustum.intersects(&behind) && !behind.intersects(&frustum));
+    assert!(frustum.intersects(&in_view.aabb()));
+
+    // generic broadphase over any pair
+    let spheres = [sphere, far_sphere, in_view];
+    assert_eq!(count_overlaps(&[aabb], &spheres), 1);
+    assert_eq!(count_overlaps(&spheres, &spheres), 3);
+    assert_eq!(count_overlaps(&[frustum], &spheres), 1);
+    assert_eq!(count_overlaps(&[ray], &spheres), 2);
+    assert_eq!(count_overlaps(&[aabb, obb.aabb()], &[obb]), 2);
+
+    // 2D pairs
+    let aabb2 = Aabb::new(vec2f(0.0, 0.0), vec2f(1.0, 1.0));
+    let circle = Sphere::new(vec2f(1.5, 0.5), 0.75);
+    let capsule2 = Capsule::new(vec2f(-1.0, 3.0), vec2f(3.0, 3.0), 0.5);
+    assert!(aabb2.intersects(&circle) && circle.intersects(&aabb2));
+    assert!(!capsule2.intersects(&circle) && !circle.intersects(&capsule2));
+    assert!(Ray::new(vec2f(-1.0, 0.5), vec2f(1.0, 0.0)).intersects(&circle));
+
+    // contains
+    assert!(aabb.contains(&vec3f(0.5, 0.5, 0.5)));
+    assert!(!aabb.contains(&vec3f(1.5, 0.5, 0.5)));
+    assert!(aabb.contains(&Aabb::new(vec3f(0.0, 0.0, 0.0), vec3f(0.5, 0.5, 0.5))));
+    assert!(!aabb.contains(&sphere));
+    assert!(aabb.contains(&Sphere::new(vec3f(0.0, 0.0, 0.0), 0.5)));
+    assert!(sphere.contains(&vec3f(2.0, 0.0, 0.0)));
+    assert!(sphere.contains(&Sphere::new(vec3f(2.0, 0.0, 0.0), 0.25)));
+    assert!(!sphere.contains(&Sphere::new(vec3f(2.0, 0.0, 0.0), 0.75)));
+    assert!(capsule.contains(&vec3f(0.0, 4.0, 0.0)));
+    assert!(tri.contains(&vec3f(0.0, 0.0, 0.0)));
+    assert!(obb.contains(&obb.center));
+    assert!(frustum.contains(&vec3f(0.0, 0.0, -20.0)));
+    assert!(frustum.contains(&in_view));
+    assert!(!frustum.contains(&Sphere::new(vec3f(0.0, 0.0, -0.5), 1.0)));
+    assert!(frustum.contains(&in_view.aabb()));
+    assert!(!frustum.contains(&Aabb::from_center_extents(vec3f(0.0, 0.0, -20.0), splat3f(50.0))));
+    assert!(circle.contains(&vec2f(1.5, 0.5)));
+
+    // closest point and distance dispatch to the shapes methods
+    let p = vec3f(0.0, 0.0, 10.0);
+    assert_eq!(ClosestPoint::closest_point(&aabb, p), vec3f(0.0, 0.0, 1.0));
+    assert_eq!(Distance::distance(&aabb, p), 9.0);
+    assert_eq!(Distance::distance(&plane, vec3f(0.0, -2.0, 0.0)), -2.0);
+    assert_eq!(ClosestPoint::closest_point(&plane, p), p);
+    assert!(approx(Distance::distance(&ray, p), 10.0, 0.0001));
+    assert!(approx(Distance::distance(&obb, obb.center), 0.0, 0.0001));
+    assert!(approx(nearest(&[sphere, far_sphere], vec3f(4.0, 0.0, 0.0)), vec3f(2.5, 0.0, 0.0), 0.0001));
+    assert!(approx(nearest(&[capsule, Capsule::new(vec3f(5.0, -5.0, 0.0), vec3f(5.0, 5.0, 0.0), 0.5)], vec3f(2.0, 1.0, 0.0)), vec3f(0.5, 1.0, 0.0), 0.0001));
+    assert!(approx(Distance::distance(&capsule2, vec2f(1.0, 5.0)), 1.5, 0.0001));
+}
+
+#[test]
+fn ray_hits() {
+    let eps = 0.0001;
+
+    // sphere from outside, inside and behind
+    let hit = ray_vs_sphere_hit(vec3f(-10.0, 0.0, 0.0), vec3f(1.0, 0.0, 0.0), vec3f(0.0, 0.0, 0.0), 2.0, f32::max_value()).unwrap();
+    assert!(approx(hit.t, 8.0, eps));
+    assert!(approx(hit.point, vec3f(-2.0, 0.0, 0.0), eps));
+    assert!(approx(hit.normal, vec3f(-1.0, 0.0, 0.0), eps));
+    assert!(hit.front_face);
+    assert_eq!(hit.barycentric(), (1.0, 0.0, 0.0));
+    let hit = ray_vs_sphere_hit(vec3f(0.0, 0.0, 0.0), vec3f(0.0, 1.0, 0.0), vec3f(0.0, 0.0, 0.0), 2.0, f32::max_value()).unwrap();
+    assert!(approx(hit.point, vec3f(0.0, 2.0, 0.0), eps));
+    assert!(approx(hit.normal, vec3f(0.0, 1.0, 0.0), eps));
+    assert!(!hit.front_face);
+    assert!(ray_vs_sphere_hit(vec3f(-10.0, 0.0, 0.0), vec3f(1.0, 0.0, 0.0), vec3f(0.0, 0.0, 0.0), 2.0, 7.0).is_none());
+    assert!(ray_vs_sphere_hit(vec3f(10.0, 0.0, 0.0), vec3f(1.0, 0.0, 0.0), vec3f(0.0, 0.0, 0.0), 2.0, f32::max_value()).is_none());
+    let hit = ray_vs_sphere_hit(vec2f(0.0, -5.0), vec2f(0.0, 2.0), vec2f(0.0, 0.0), 1.0, 10.0).unwrap();
+    assert!(approx(hit.t, 2.0, eps));
+    assert!(approx(hit.normal, vec2f(0.0, -1.0), eps));
+
+    // aabb entry and exit faces
+    let (emin, emax) = (vec3f(-1.0, -2.0, -3.0), vec3f(1.0, 2.0, 3.0));
+    let hit = ray_vs_aabb_hit(vec3f(0.0, 10.0, 0.0), vec3f(0.0, -1.0, 0.0), emin, emax, f32::max_value()).unwrap();
+    assert!(approx(hit.t, 8.0, eps));
+    assert_eq!(hit.normal, vec3f(0.0, 1.0, 0.0));
+    assert!(hit.front_face);
+    let hit = ray_vs_aabb_hit(vec3f(0.0, 0.0, 0.0), vec3f(0.0, 0.0, -1.0), emin, emax, f32::max_value()).unwrap();
+    assert!(approx(hit.point, vec3f(0.0, 0.0, -3.0), eps));
+    assert_eq!(hit.normal, vec3f(0.0, 0.0, -1.0));
+    assert!(!hit.front_face);
+    assert!(ray_vs_aabb_hit(vec3f(0.0, 10.0, 0.0), vec3f(0.0, -1.0, 0.0), emin, emax, 7.9).is_none());
+    let r0 = vec3f(-5.0, 1.0, 0.5);
+    let rv = normalize(vec3f(1.0, -0.1, 0.2));
+    let hit = ray_vs_aabb_hit(r0, rv, emin, emax, f32::max_value()).unwrap();
+    assert!(approx(hit.point, ray_vs_aabb(r0, rv, emin, emax).unwrap(), eps));
+    assert_eq!(hit.normal, vec3f(-1.0, 0.0, 0.0));
+    let hit = ray_vs_aabb_hit(vec2f(5.0, 0.0), vec2f(-1.0, 0.0), vec2f(-1.0, -1.0), vec2f(1.0, 1.0), 10.0).unwrap();
+    assert_eq!(hit.normal, vec2f(1.0, 0.0));
+
+    // obb normals are rotated with the box
+    let mat = Mat4f::from_translation(vec3f(0.0, 0.0, 5.0)) * Mat4f::from_z_rotation(f32::pi() * 0.25) * Mat4f::from_scale(vec3f(2.0, 1.0, 1.0));
+    let r0 = vec3f(-10.0, 0.0, 5.0);
+    let rv = vec3f(1.0, 0.0, 0.0);
+    let hit = ray_vs_obb_hit(r0, rv, mat, f32::max_value()).unwrap();
+    assert!(approx(hit.point, ray_vs_obb(r0, rv, mat).unwrap(), eps));
+    assert!(approx(hit.point, r0 + rv * hit.t, eps));
+    assert!(approx(hit.normal, normalize(vec3f(-1.0, 1.0, 0.0)), eps));
+    assert!(hit.front_face);
+    assert!(approx(point_obb_distance(hit.point, mat), 0.0, eps));
+    let hit = ray_vs_obb_hit(vec3f(0.0, 0.0, 5.0), vec3f(0.0, 0.0, 1.0), mat, f32::max_value()).unwrap();
+    assert!(approx(hit.point, vec3f(0.0, 0.0, 6.0), eps));
+    assert!(approx(hit.normal, vec3f(0.0, 0.0, 1.0), eps));
+    assert!(!hit.front_face);
+    assert!(ray_vs_obb_hit(r0, rv, mat, 5.0).is_none());
+
+    // triangle barycentrics and winding
+    let (t0, t1, t2) = (vec3f(0.0, 0.0, 0.0), vec3f(0.0, 0.0, 4.0), vec3f(4.0, 0.0, 0.0));
+    let r0 = vec3f(1.0, 5.0, 2.0);
+    let hit = ray_vs_triangle_hit(r0, vec3f(0.0, -1.0, 0.0), t0, t1, t2, f32::max_value()).unwrap();
+    assert!(approx(hit.t, 5.0, eps));
+    assert!(approx(hit.u, 0.5, eps));
+    assert!(approx(hit.v, 0.25, eps));
+    let (w, u, v) = hit.barycentric();
+    assert!(approx(t0 * w + t1 * u + t2 * v, hit.point, eps));
+    assert!(approx(hit.normal, get_triangle_normal(t0, t1, t2), eps));
+    assert!(hit.front_face);
+    let hit = ray_vs_triangle_hit(vec3f(1.0, -5.0, 2.0), vec3f(0.0, 1.0, 0.0), t0, t1, t2, f32::max_value()).unwrap();
+    assert!(!hit.front_face);
+    assert!(approx(hit.normal, vec3f(0.0, 1.0, 0.0), eps));
+    assert!(ray_vs_triangle_hit(r0, vec3f(0.0, -1.0, 0.0), t0, t1, t2, 4.0).is_none());
+
+    // capsule sides, caps and inside
+    let (c0, c1) = (vec3f(0.0, 0.0, 0.0), vec3f(0.0, 4.0, 0.0));
+    let hit = ray_vs_capsule_hit(vec3f(-5.0, 2.0, 0.0), vec3f(1.0, 0.0, 0.0), c0, c1, 1.0, f32::max_value()).unwrap();
+    assert!(approx(hit.point, vec3f(-1.0, 2.0, 0.0), eps));
+    assert!(approx(hit.normal, vec3f(-1.0, 0.0, 0.0), eps));
+    assert!(hit.front_face);
+    let hit = ray_vs_capsule_hit(vec3f(0.0, 10.0, 0.0), vec3f(0.0, -1.0, 0.0), c0, c1, 1.0, f32::max_value()).unwrap();
+    assert!(approx(hit.point, vec3f(0.0, 5.0, 0.0), eps));
+    assert!(approx(hit.normal, vec3f(0.0, 1.0, 0.0), eps));
+    let hit = ray_vs_capsule_hit(vec3f(0.0, 0.5, 0.0), vec3f(0.0, -1.0, 0.0), c0, c1, 1.0, f32::max_value()).unwrap();
+    assert!(approx(hit.point, vec3f(0.0, -1.0, 0.0), eps));
+    assert!(approx(hit.normal, vec3f(0.0, -1.0, 0.0), eps));
+    assert!(!hit.front_face);
+    let hit = ray_vs_capsule_hit(vec3f(0.0, 2.0, 0.0), vec3f(0.0, 0.0, 1.0), c0, c1, 1.0, f32::max_value()).unwrap();
+    assert!(approx(hit.point, vec3f(0.0, 2.0, 1.0), eps));
+    assert!(!hit.front_face);
+    assert!(ray_vs_capsule_hit(vec3f(-5.0, 2.0, 0.0), vec3f(1.0, 0.0, 0.0), c0, c1, 1.0, 3.0).is_none());
+    assert!(ray_vs_capsule_hit(vec3f(-5.0, 2.0, 0.0), vec3f(-1.0, 0.0, 0.0), c0, c1, 1.0, f32::max_value()).is_none());
+
+    // cylinder sides, caps and inside
+    let hit = ray_vs_cylinder_hit(vec3f(-5.0, 2.0, 0.0), vec3f(1.0, 0.0, 0.0), c0, c1, 1.0, f32::max_value()).unwrap();
+    assert!(approx(hit.point, vec3f(-1.0, 2.0, 0.0), eps));
+    assert!(approx(hit.normal, vec3f(-1.0, 0.0, 0.0), eps));
+    let hit = ray_vs_cylinder_hit(vec3f(0.5, 10.0, 0.0), vec3f(0.0, -1.0, 0.0), c0, c1, 1.0, f32::max_value()).unwrap();
+    assert!(approx(hit.point, vec3f(0.5, 4.0, 0.0), eps));
+    assert!(approx(hit.normal, vec3f(0.0, 1.0, 0.0), eps));
+    assert!(hit.front_face);
+    let hit = ray_vs_cylinder_hit(vec3f(0.5, 2.0, 0.0), vec3f(0.0, -1.0, 0.0), c0, c1, 1.0, f32::max_value()).unwrap();
+    assert!(approx(hit.point, vec3f(0.5, 0.0, 0.0), eps));
+    assert!(approx(hit.normal, vec3f(0.0, -1.0, 0.0), eps));
+    assert!(!hit.front_face);
+    assert!(ray_vs_cylinder_hit(vec3f(2.0, 10.0, 0.0), vec3f(0.0, -1.0, 0.0), c0, c1, 1.0, f32::max_value()).is_none());
+
+    // primitives
+    let ray = Ray::new(vec3f(-10.0, 0.0, 0.0), vec3f(1.0, 0.0, 0.0));
+    assert!(approx(ray.hit_sphere(&Sphere::new(vec3f(0.0, 0.0, 0.0), 1.0), 100.0).unwrap().t, 9.0, eps));
+    assert!(ray.hit_aabb(&Aabb::new(splat3f(-1.0), splat3f(1.0)), 8.0).is_none());
+    assert!(ray.hit_capsule(&Capsule::new(c0, c1, 1.0), 100.0).is_some());
+    assert!(ray.hit_obb(&Obb::from_matrix(mat), 100.0).is_none());
+    let tri = Triangle::new(vec3f(0.0, -1.0, -1.0), vec3f(0.0, 1.0, 0.0), vec3f(0.0, -1.0, 1.0));
+    assert!(!ray.hit_triangle(&tri, 100.0).unwrap().front_face);
+}
+
+#[test]
+fn ray_point_wrappers() {
+    // sphere and aabb keep their original results for rays starting inside or behind, capsule and cylinder return the exit point
+    let (c0, c1) = (vec3f(0.0, 0.0, 0.0), vec3f(0.0, 4.0, 0.0));
+    assert_eq!(ray_vs_sphere(vec3f(10.0, 0.0, 0.0), vec3f(1.0, 0.0, 0.0), vec3f(0.0, 0.0, 0.0), 2.0), Some(vec3f(2.0, 0.0, 0.0)));
+    assert_eq!(ray_vs_sphere(vec3f(0.0, 0.0, 0.0), vec3f(0.0, 1.0, 0.0), vec3f(0.0, 0.0, 0.0), 2.0), Some(vec3f(0.0, 2.0, 0.0)));
+    assert_eq!(ray_vs_capsule(vec3f(0.0, 0.5, 0.0), vec3f(0.0, -1.0, 0.0), c0, c1, 1.0), Some(vec3f(0.0, -1.0, 0.0)));
+    assert_eq!(ray_vs_capsule(vec3f(0.0, 2.0, 0.0), vec3f(0.0, 0.0, 1.0), c0, c1, 1.0), Some(vec3f(0.0, 2.0, 1.0)));
+    assert_eq!(ray_vs_cylinder(vec3f(0.5, 2.0, 0.0), vec3f(0.0, -1.0, 0.0), c0, c1, 1.0), Some(vec3f(0.5, 0.0, 0.0)));
+    assert_eq!(ray_vs_cylinder(vec3f(0.0, 2.0, 0.0), vec3f(0.0, 0.0, 1.0), c0, c1, 1.0), Some(vec3f(0.0, 2.0, 1.0)));
+    assert_eq!(ray_vs_aabb(vec3f(0.0, 0.0, 0.0), vec3f(0.0, 0.0, -1.0), vec3f(-1.0, -2.0, -3.0), vec3f(1.0, 2.0, 3.0)), Some(vec3f(0.0, 0.0, 3.0)));
+}
+
+#[test]
+fn epa_penetration() {
+    let eps = 0.001;
+    let square = |c: Vec2f, e: f32| vec![c + vec2f(-e, -e), c + vec2f(e, -e), c + vec2f(e, e), c + vec2f(-e, e)];
+    let cube = |m: Mat4f| Aabb::new(splat3f(-1.0), splat3f(1.0)).corners().map(|c| m * c).to_vec();
+
+    // 2D squares overlapping on x
+    let a = square(vec2f(0.0, 0.0), 1.0);
+    let b = square(vec2f(1.5, 0.2), 1.0);
+    let p = epa_2d(&a, &b).unwrap();
+    assert!(approx(p.depth, 0.5, eps));
+    assert!(approx(p.normal, vec2f(1.0, 0.0), eps));
+    assert!(approx(p.point_a - p.point_b, p.normal * p.depth, eps));
+    assert!(approx(p.point_a.x, 1.0, eps));
+    assert!(approx(p.point_b.x, 0.5, eps));
+    let b_moved: Vec<Vec2f> = b.iter().map(|v| *v + p.normal * (p.depth + 0.01)).collect();
+    assert!(!gjk_2d(&a, &b_moved));
+
+    // 2D overlapping on y the other way, and separated
+    let p = epa_2d(&a, &square(vec2f(0.3, -1.75), 1.0)).unwrap();
+    assert!(approx(p.depth, 0.25, eps));
+    assert!(approx(p.normal, vec2f(0.0, -1.0), eps));
+    assert!(epa_2d(&a, &square(vec2f(3.0, 0.0), 1.0)).is_none());
+
+    // 2D triangle into square
+    let tri = vec![vec2f(0.0, 0.6), vec2f(2.0, 3.0), vec2f(-2.0, 3.0)];
+    let p = epa_2d(&a, &tri).unwrap();
+    assert!(approx(p.depth, 0.4, eps));
+    assert!(approx(p.normal, vec2f(0.0, 1.0), eps));
+    assert!(approx(p.point_b, vec2f(0.0, 0.6), eps));
+
+    // 3D cubes
+    let a = cube(Mat4f::identity());
+    let b = cube(Mat4f::from_translation(vec3f(0.2, 1.7, -0.1)));
+    let p = epa_3d(&a, &b).unwrap();
+    assert!(approx(p.depth, 0.3, eps));
+    assert!(approx(p.normal, vec3f(0.0, 1.0, 0.0), eps));
+    assert!(approx(p.point_a - p.point_b, p.normal * p.depth, eps));
+    assert!(approx(p.point_a.y, 1.0, eps));
+    assert!(approx(p.point_b.y, 0.7, eps));
+    assert!(epa_3d(&a, &cube(Mat4f::from_translation(vec3f(0.0, 0.0, 2.5)))).is_none());
+
+    // 3D deep overlap picks the shallowest axis
+    let b = cube(Mat4f::from_translation(vec3f(-0.5, 0.1, -1.2)));
+    let p = epa_3d(&a, &b).unwrap();
+    assert!(approx(p.depth, 0.8, eps));
+    assert!(approx(p.normal, vec3f(0.0, 0.0, -1.0), eps));
+
+    // 3D rotated cube corner pushing into a face
+    let rot = Mat4f::from_translation(vec3f(0.0, 2.5, 0.0)) * Mat4f::from_z_rotation(f32::pi() * 0.25) * Mat4f::from_x_rotation(f32::pi() * 0.25);
+    let b = cube(rot);
+    let p = epa_3d(&a, &b).unwrap();
+    let lowest = b.iter().fold(f32::max_value(), |m, v| f32::min(m, v.y));
+    assert!(approx(p.depth, 1.0 - lowest, eps));
+    assert!(approx(p.normal, vec3f(0.0, 1.0, 0.0), eps));
+    let b_moved: Vec<Vec3f> = b.iter().map(|v| *v + p.normal * (p.depth + 0.01)).collect();
+    assert!(!gjk_3d(&a, &b_moved));
+
+    // f64 and touching hulls
+    let a = vec![vec3d(0.0, 0.0, 0.0), vec3d(1.0, 0.0, 0.0), vec3d(0.0, 1.0, 0.0), vec3d(0.0, 0.0, 1.0)];
+    let b: Vec<Vec3d> = a.iter().map(|v| *v + vec3d(0.25, 0.25, 0.25)).collect();
+    let p = epa_3d(&a, &b).unwrap();
+    assert!(p.depth > 0.0);
+    assert!(approx(p.point_a - p.point_b, p.normal * p.depth, 0.0001));
+    let b_moved: Vec<Vec3d> = b.iter().map(|v| *v + p.normal * (p.depth + 0.001)).collect();
+    assert!(!gjk_3d(&a, &b_moved));
+}
+
+#[test]
+fn epa_sphere_penetration() {
+    // curved supports need many expansions, the result must stay consistent when the polytope is still expanding
+    let mut seed = 99u32;
+    let mut rand = move || {
+        seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+        (seed >> 8) as f32 / (1u32 << 24) as f32
+    };
+    for _ in 0..200 {
+        let c0 = vec3f(rand(), rand(), rand()) * 4.0 - 2.0;
+        let r0 = rand() * 2.0 + 0.2;
+        let r1 = rand() * 2.0 + 0.2;
+        let dir = normalize(vec3f(rand(), rand(), rand()) - 0.5);
+        let c1 = c0 + dir * (r0 + r1) * (0.05 + rand() * 0.9);
+        let p = epa_3d(&Sphere::new(c0, r0), &Sphere::new(c1, r1)).unwrap();
+        assert!(approx(p.depth, r0 + r1 - dist(c0, c1), 0.01));
+        assert!(dot(p.normal, dir) > 0.99);
+        assert!(approx(p.point_a - p.point_b, p.normal * p.depth, 0.0001));
+
+        let c0 = vec2f(c0.x, c0.y);
+        let dir = normalize(vec2f(dir.x, dir.y));
+        let c1 = c0 + dir * (r0 + r1) * 0.5;
+        let p = epa_2d(&Sphere::new(c0, r0), &Sphere::new(c1, r1)).unwrap();
+        assert!(approx(p.depth, (r0 + r1) * 0.5, 0.01));
+        assert!(dot(p.normal, dir) > 0.99);
+        assert!(approx(p.point_a - p.point_b, p.normal * p.depth, 0.0001));
+    }
+}
+
+#[test]
+fn epa_axis_aligned_overlap() {
+    // gjk starts searching along x, overlaps along that axis leave it with a flat simplex which epa must expand
+    let eps = 0.01;
+    let sphere = Sphere::new(Vec3f::zero(), 1.0);
+    for dir in [Vec3f::unit_x(), -Vec3f::unit_x(), Vec3f::unit_y(), Vec3f::unit_z()] {
+        let c = dir * 1.5;
+        let p = epa_3d(&sphere, &Sphere::new(c, 1.0)).unwrap();
+        assert!(approx(p.depth, 0.5, eps));
+        assert!(dot(p.normal, dir) > 0.99);
+        let p = epa_3d(&sphere, &Aabb::new(c - splat3f(1.0), c + splat3f(1.0))).unwrap();
+        assert!(approx(p.depth, 0.5, eps));
+        assert!(dot(p.normal, dir) > 0.99);
+    }
+
+    // 2D circles along x and concentric
+    let circle = Sphere::new(Vec2f::zero(), 1.0);
+    let p = epa_2d(&circle, &Sphere::new(vec2f(1.5, 0.0), 1.0)).unwrap();
+    assert!(approx(p.depth, 0.5, eps));
+    assert!(p.normal.x > 0.99);
+    let p = epa_2d(&circle, &circle).unwrap();
+    assert!(approx(p.depth, 2.0, eps));
+    assert!(approx(mag(p.normal), 1.0, eps));
+
+    // coplanar triangles have a flat minkowski difference and only touch
+    let tri = vec![vec3f(-1.0, 0.0, -1.0), vec3f(1.0, 0.0, -1.0), vec3f(0.0, 0.0, 1.0)];
+    let shifted: Vec<Vec3f> = tri.iter().map(|v| *v + vec3f(0.5, 0.0, 0.0)).collect();
+    let p = epa_3d(&tri, &shifted).unwrap();
+    assert_eq!(p.depth, 0.0);
+    assert!(approx(abs(p.normal.y), 1.0, eps));
+}
+
+#[test]
+fn gjk_distance_witness_points() {
+    let eps = 0.001;
+    let square = |c: Vec2f, e: f32| vec![c + vec2f(-e, -e), c + vec2f(e, -e), c + vec2f(e, e), c + vec2f(-e, e)];
+    let cube = |m: Mat4f| Aabb::new(splat3f(-1.0), splat3f(1.0)).corners().map(|c| m * c).to_vec();
+
+    // 2D squares separated on x, closest points lie on the facing edges
+    let a = square(vec2f(0.0, 0.0), 1.0);
+    let (d, pa, pb) = gjk_distance(&a, &square(vec2f(3.0, 0.5), 1.0)).unwrap();
+    assert!(approx(d, 1.0, eps));
+    assert!(approx(pa.x, 1.0, eps));
+    assert!(approx(pb.x, 2.0, eps));
+    assert!(approx(pa.y, pb.y, eps));
+
+    // 2D corner to corner, and overlapping
+    let (d, pa, pb) = gjk_distance(&a, &square(vec2f(3.0, 3.0), 1.0)).unwrap();
+    assert!(approx(d, f32::sqrt(2.0), eps));
+    assert!(approx(pa, vec2f(1.0, 1.0), eps));
+    assert!(approx(pb, vec2f(2.0, 2.0), eps));
+    assert!(gjk_distance(&a, &square(vec2f(1.5, 0.0), 1.0)).is_none());
+
+    // 3D cubes face to face, edge to edge and overlapping
+    let a = cube(Mat4f::identity());
+    let (d, pa, pb) = gjk_distance(&a, &cube(Mat4f::from_translation(vec3f(0.2, 2.5, -0.3)))).unwrap();
+    assert!(approx(d, 0.5, eps));
+    assert!(approx(pa.y, 1.0, eps));
+    assert!(approx(pb.y, 1.5, eps));
+    assert!(approx(dist(pa, pb), d, eps));
+
+    let (d, pa, pb) = gjk_distance(&a, &cube(Mat4f::from_translation(vec3f(3.0, 3.0, 0.0)))).unwrap();
+    assert!(approx(d, f32::sqrt(2.0), eps));
+    assert!(approx(pa.xy(), vec2f(1.0, 1.0), eps));
+    assert!(approx(pb.xy(), vec2f(2.0, 2.0), eps));
+    assert!(gjk_distance(&a, &cube(Mat4f::from_translation(vec3f(1.5, 0.5, 0.0)))).is_none());
+    assert!(!gjk_3d(&a, &cube(Mat4f::from_translation(vec3f(3.0, 3.0, 0.0)))));
+
+    // 3D rotated cube corner above a face
+    let rot = Mat4f::from_translation(vec3f(0.0, 4.0, 0.0)) * Mat4f::from_z_rotation(f32::pi() * 0.25) * Mat4f::from_x_rotation(f32::pi() * 0.25);
+    let b = cube(rot);
+    let lowest = b.iter().fold(f32::max_value(), |m, v| f32::min(m, v.y));
+    let (d, pa, pb) = gjk_distance(&a, &b).unwrap();
+    assert!(approx(d, lowest - 1.0, eps));
+    assert!(approx(pa.y, 1.0, eps));
+    assert!(approx(pb.y, lowest, eps));
+
+    // f64 tetrahedron point to face
+    let a = vec![vec3d(0.0, 0.0, 0.0), vec3d(1.0, 0.0, 0.0), vec3d(0.0, 1.0, 0.0), vec3d(0.0, 0.0, 1.0)];
+    let b: Vec<Vec3d> = a.iter().map(|v| -*v + splat3d(1.0)).collect();
+    let (d, pa, pb) = gjk_distance(&a, &b).unwrap();
+    let expected = (2.0 / f64::sqrt(3.0)) - (1.0 / f64::sqrt(3.0));
+    assert!(approx(d, expected, 0.0001));
+    assert!(approx(pa, splat3d(1.0 / 3.0), 0.0001));
+    assert!(approx(pb, splat3d(2.0 / 3.0), 0.0001));
+}
+
+#[test]
+fn support_shapes() {
+    let eps = 0.001;
+
+    // supports of the implicit shapes
+    let cylinder = Cylinder::new(vec3f(0.0, -1.0, 0.0), vec3f(0.0, 1.0, 0.0), 0.5);
+    assert!(approx(cylinder.support(vec3f(1.0, 1.0, 0.0)), vec3f(0.5, 1.0, 0.0), eps));
+    assert!(approx(cylinder.support(vec3f(0.0, -1.0, -2.0)), vec3f(0.0, -1.0, -0.5), eps));
+    assert!(approx(cylinder.aabb().max, vec3f(0.5, 1.0, 0.5), eps));
+
+    let cone = Cone::new(vec3f(0.0, 2.0, 0.0), vec3f(0.0, -1.0, 0.0), 2.0, 1.0);
+    assert!(approx(cone.support(vec3f(0.0, 1.0, 0.0)), vec3f(0.0, 2.0, 0.0), eps));
+    assert!(approx(cone.support(vec3f(1.0, -0.1, 0.0)), vec3f(1.0, 0.0, 0.0), eps));
+    assert!(approx(cone.aabb().min, vec3f(-1.0, 0.0, -1.0), eps));
+    assert!(cone.contains_point(vec3f(0.1, 0.5, 0.0)));
+
+    // the pairs the cylinder and cone helpers support through Intersects and Contains
+    let ray = Ray::new(vec3f(-5.0, 0.0, 0.0), vec3f(1.0, 0.0, 0.0));
+    assert!(ray.intersects(&cylinder) && cylinder.intersects(&ray));
+    assert!(approx(ray.intersect_cylinder(&cylinder).unwrap(), vec3f(-0.5, 0.0, 0.0), eps));
+    assert!(approx(ray.hit_cylinder(&cylinder, 10.0).unwrap().t, 4.5, eps));
+    assert!(ray.hit_cylinder(&cylinder, 4.0).is_none());
+    assert!(!Ray::new(vec3f(-5.0, 2.0, 0.0), vec3f(1.0, 0.0, 0.0)).intersects(&cylinder));
+    assert!(cylinder.contains(&vec3f(0.2, 0.9, 0.0)));
+    assert!(!cylinder.contains(&vec3f(0.6, 0.0, 0.0)));
+    let plane = Plane::from_normal_and_point(vec3f(0.0, 1.0, 0.0), vec3f(0.0, 1.0, 0.0));
+    assert!(cone.intersects(&plane) && plane.intersects(&cone));
+    assert_eq!(cone.classify_plane(&Plane::from_normal_and_point(vec3f(0.0, 3.0, 0.0), vec3f(0.0, 1.0, 0.0))), Classification::Behind);
+    assert!(!cone.intersects(&Plane::from_normal_and_point(vec3f(0.0, -0.5, 0.0), vec3f(0.0, 1.0, 0.0))));
+    assert!(cone.contains(&vec3f(0.1, 0.5, 0.0)));
+    assert!(!cone.contains(&vec3f(0.9, 1.5, 0.0)));
+
+    let aabb = Aabb::new(splat3f(-1.0), splat3f(1.0));
+    assert!(approx(aabb.support(vec3f(1.0, -2.0, 0.5)), vec3f(1.0, -1.0, 1.0), eps));
+    let obb = Obb::from_matrix(Mat4f::from_y_rotation(f32::pi() * 0.25));
+    let corners = obb.corners();
+    assert!(approx(obb.support(vec3f(1.0, 0.2, 0.3)), corners.support(vec3f(1.0, 0.2, 0.3)), eps));
+
+    // transformed and rounded shapes
+    let moved = Transformed::new(cylinder, Mat4f::from_translation(vec3f(5.0, 0.0, 0.0)) * Mat4f::from_z_rotation(f32::pi() * 0.5));
+    assert!(approx(moved.aabb().min, vec3f(4.0, -0.5, -0.5), eps));
+    assert!(approx(moved.aabb().max, vec3f(6.0, 0.5, 0.5), eps));
+    let rounded = MinkowskiSum::new(aabb, Sphere::new(Vec3f::zero(), 0.5));
+    assert!(approx(rounded.support(vec3f(1.0, 1.0, 0.0)), vec3f(1.0 + f32::sqrt(0.125), 1.0 + f32::sqrt(0.125), 1.0), eps));
+
+    // gjk, distance and epa on implicit shapes without tessellating
+    let sphere = Sphere::new(vec3f(3.0, 0.0, 0.0), 1.0);
+    let (d, pa, pb) = gjk_distance(&Sphere::new(Vec3f::zero(), 1.0), &sphere).unwrap();
+    assert!(approx(d, 1.0, eps));
+    assert!(approx(pa, vec3f(1.0, 0.0, 0.0), eps));
+    assert!(approx(pb, vec3f(2.0, 0.0, 0.0), eps));
+
+    let (d, _, _) = gjk_distance(&rounded, &Sphere::new(vec3f(3.0, 0.2, 0.0), 0.5)).unwrap();
+    assert!(approx(d, 1.0, eps));
+    assert!(gjk_3d(&rounded, &Sphere::new(vec3f(1.9, 0.0, 0.0), 0.5)));
+    assert!(!gjk_3d(&aabb, &Sphere::new(vec3f(1.9, 0.0, 0.0), 0.5)));
+
+    let capsule = Capsule::new(vec3f(-2.0, 0.0, 0.0), vec3f(4.5, 0.0, 0.0), 0.25);
+    assert!(gjk_3d(&capsule, &moved));
+    assert!(gjk_3d(&capsule, &cone));
+    assert!(!gjk_3d(&capsule, &Cone::new(vec3f(0.0, 3.0, 0.0), vec3f(0.0, -1.0, 0.0), 2.0, 1.0)));
+    let p = epa_3d(&capsule, &Sphere::new(vec3f(0.0, 0.5, 0.0), 0.5)).unwrap();
+    assert!(approx(p.depth, 0.25, eps));
+    assert!(approx(p.normal, vec3f(0.0, 1.0, 0.0), 0.01));
+
+    // mixing vertex lists and shapes, and 2D circles
+    let square = vec![vec2f(-1.0, -1.0), vec2f(1.0, -1.0), vec2f(1.0, 1.0), vec2f(-1.0, 1.0)];
+    let circle = Sphere::new(vec2f(1.5, 0.0), 1.0);
+    assert!(gjk_2d(&square, &circle));
+    assert!(convex_hull_vs_convex_hull(&circle, &square));
+    let p = epa_2d(&square, &circle).unwrap();
+    assert!(approx(p.depth, 0.5, eps));
+    assert!(approx(p.normal, vec2f(1.0, 0.0), 0.01));
+    let (d, _, pb) = gjk_distance(&square, &Sphere::new(vec2f(4.0, 0.0), 1.0)).unwrap();
+    assert!(approx(d, 2.0, eps));
+    assert!(approx(pb, vec2f(3.0, 0.0), eps));
+    assert!(gjk_3d(&aabb, &obb.corners().to_vec()));
+}
+
+#[test]
+fn sat_mtv_and_contacts() {
+    let eps = 0.001;
+    let square = |c: Vec2f, e: f32| ConvexPolygon::new(&[c + vec2f(-e, -e), c + vec2f(e, -e), c + vec2f(e, e), c + vec2f(-e, e)]);
+    let contains = |m: &Manifold<f32>, p: Vec2f| m.contacts.iter().any(|c| approx(*c, p, eps));
+
+    // boxes resting face to face give 2 contacts clipped to the overlap
+    let a = square(vec2f(0.0, 0.0), 1.0);
+    let b = square(vec2f(0.5, 1.8), 1.0);
+    let m = a.sat(&b).unwrap();
+    assert!(approx(m.depth, 0.2, eps));
+    assert!(approx(m.normal, vec2f(0.0, 1.0), eps));
+    assert!(approx(m.mtv(), vec2f(0.0, 0.2), eps));
+    assert_eq!(m.contacts.len(), 2);
+    assert!(contains(&m, vec2f(-0.5, 0.8)));
+    assert!(contains(&m, vec2f(1.0, 0.8)));
+    assert!(a.sat(&b.translated(m.mtv() * 1.01)).is_none());
+
+    // reversed the normal flips and contacts are on the other shape
+    let m = b.sat(&a).unwrap();
+    assert!(approx(m.normal, vec2f(0.0, -1.0), eps));
+    assert!(contains(&m, vec2f(-0.5, 1.0)));
+
+    // rotated box corner into a face
+    let diamond = ConvexPolygon::new(&[vec2f(0.0, 0.9), vec2f(1.0, 1.9), vec2f(0.0, 2.9), vec2f(-1.0, 1.9)]);
+    let m = a.sat(&diamond).unwrap();
+    assert!(approx(m.depth, 0.1, eps));
+    assert!(approx(m.normal, vec2f(0.0, 1.0), eps));
+    assert_eq!(m.contacts.len(), 1);
+    assert!(contains(&m, vec2f(0.0, 0.9)));
+    assert!(a.sat(&square(vec2f(2.0, 0.0), 1.0)).is_none());
+    assert!(a.sat(&square(vec2f(3.0, 0.5), 1.0)).is_none());
+
+    // circles
+    let c0: Sphere<f32, Vec2f> = Sphere::new(vec2f(0.0, 0.0), 1.0);
+    let c1: Sphere<f32, Vec2f> = Sphere::new(vec2f(1.5, 0.0), 1.0);
+    let m = c0.sat(&c1).unwrap();
+    assert!(approx(m.depth, 0.5, eps));
+    assert!(approx(m.normal, vec2f(1.0, 0.0), eps));
+    assert!(contains(&m, vec2f(0.5, 0.0)));
+    assert!(c0.sat(&Sphere::new(vec2f(2.0, 1.0), 1.0)).is_none());
+
+    // circle against box face, corner region and with its centre inside
+    let m = a.sat(&Sphere::new(vec2f(1.5, 0.3), 1.0)).unwrap();
+    assert!(approx(m.depth, 0.5, eps));
+    assert!(approx(m.normal, vec2f(1.0, 0.0), eps));
+    assert!(contains(&m, vec2f(0.5, 0.3)));
+    let corner = vec2f(1.5, 1.5);
+    let m = a.sat(&Sphere::new(corner, 1.0)).unwrap();
+    assert!(approx(m.depth, 1.0 - f32::sqrt(0.5), eps));
+    assert!(approx(m.normal, normalize(vec2f(1.0, 1.0)), eps));
+    assert!(a.sat(&Sphere::new(vec2f(1.8, 1.8), 1.0)).is_none());
+    let m = Sphere::new(vec2f(0.7, 0.2), 0.5).sat(&a).unwrap();
+    assert!(approx(m.depth, 0.8, eps));
+    assert!(approx(m.normal, vec2f(-1.0, 0.0), eps));
+
+    // capsule lying on a box gives 2 contacts, standing on it gives 1
+    let lying: Capsule<f32, Vec2f> = Capsule::new(vec2f(-0.5, 1.2), vec2f(3.0, 1.2), 0.25);
+    let m = a.sat(&lying).unwrap();
+    assert!(approx(m.depth, 0.05, eps));
+    assert!(approx(m.normal, vec2f(0.0, 1.0), eps));
+    assert_eq!(m.contacts.len(), 2);
+    assert!(contains(&m, vec2f(-0.5, 0.95)));
+    assert!(contains(&m, vec2f(1.0, 0.95)));
+    let standing: Capsule<f32, Vec2f> = Capsule::new(vec2f(0.2, 1.1), vec2f(0.2, 3.0), 0.25);
+    let m = standing.sat(&a).unwrap();
+    assert!(approx(m.depth, 0.15, eps));
+    assert!(approx(m.normal, vec2f(0.0, -1.0), eps));
+    assert_eq!(m.contacts.len(), 1);
+    assert!(contains(&m, vec2f(0.2, 1.0)));
+
+    // capsule through a box, capsule pairs and circle against capsule
+    let through: Capsule<f32, Vec2f> = Capsule::new(vec2f(-3.0, 0.5), vec2f(3.0, 0.5), 0.1);
+    let m = a.sat(&through).unwrap();
+    assert!(approx(m.depth, 0.6, eps));
+    assert!(approx(m.normal, vec2f(0.0, 1.0), eps));
+    let m = lying.sat(&Capsule::new(vec2f(0.0, 1.6), vec2f(2.0, 1.6), 0.25)).unwrap();
+    assert!(approx(m.depth, 0.1, eps));
+    assert_eq!(m.contacts.len(), 2);
+    let m = c0.sat(&Capsule::new(vec2f(1.2, -1.0), vec2f(1.2, 1.0), 0.5)).unwrap();
+    assert!(approx(m.depth, 0.3, eps));
+    assert!(approx(m.normal, vec2f(1.0, 0.0), eps));
+    assert!(lying.sat(&Sphere::new(vec2f(0.0, -0.1), 1.0)).is_none());
+
+    // free function with raw cores and f64
+    let m = sat_2d(&[vec2d(0.0, 0.0)], 1.0, &[vec2d(0.0, 1.5), vec2d(2.0, 1.5)], 1.0).unwrap();
+    assert!(approx(m.depth, 0.5, 0.0001));
+    assert!(approx(m.normal, vec2d(0.0, 1.0), 0.0001));
+
+    // polygons support gjk
+    assert!(gjk_2d(&a, &c1));
+}
+
+#[test]
+fn swept_time_of_impact() {
+    let eps = 0.001;
+
+    // sphere vs plane from above, from below, missing and starting in contact
+    let toi = sweep_sphere_vs_plane(vec3f(0.0, 5.0, 0.0), 1.0, vec3f(0.0, -8.0, 0.0), Vec3f::zero(), Vec3f::unit_y()).unwrap();
+    assert!(approx(toi.t, 0.5, eps));
+    assert!(approx(toi.normal, Vec3f::unit_y(), eps));
+    assert!(approx(toi.point, Vec3f::zero(), eps));
+    let toi = sweep_sphere_vs_plane(vec3f(2.0, -3.0, 0.0), 1.0, vec3f(0.0, 4.0, 0.0), Vec3f::zero(), Vec3f::unit_y()).unwrap();
+    assert!(approx(toi.t, 0.5, eps));
+    assert!(approx(toi.normal, -Vec3f::unit_y(), eps));
+    assert!(sweep_sphere_vs_plane(vec3f(0.0, 5.0, 0.0), 1.0, vec3f(0.0, -2.0, 0.0), Vec3f::zero(), Vec3f::unit_y()).is_none());
+    assert!(sweep_sphere_vs_plane(vec3f(0.0, 5.0, 0.0), 1.0, vec3f(0.0, 8.0, 0.0), Vec3f::zero(), Vec3f::unit_y()).is_none());
+    assert_eq!(sweep_sphere_vs_plane(vec2f(0.0, 0.5), 1.0, vec2f(0.0, 8.0), Vec2f::zero(), Vec2f::unit_y()).unwrap().t, 0.0);
+
+    // fast projectile would tunnel through a thin triangle with a static test at the end of the step
+    let (t0, t1, t2) = (vec3f(-1.0, -1.0, 0.0), vec3f(1.0, -1.0, 0.0), vec3f(0.0, 1.0, 0.0));
+    let s0 = vec3f(0.0, 0.0, -10.0);
+    let v = vec3f(0.0, 0.0, 20.0);
+    assert!(point_triangle_distance(s0 + v, t0, t1, t2) > 0.1);
+    let toi = sweep_sphere_vs_triangle(s0, 0.1, v, t0, t1, t2).unwrap();
+    assert!(approx(toi.t, 9.9 / 20.0, eps));
+    assert!(approx(toi.normal, vec3f(0.0, 0.0, -1.0), eps));
+    assert!(approx(toi.point, Vec3f::zero(), eps));
+
+    // triangle edge and vertex, a miss and starting in contact
+    assert!(sweep_sphere_vs_triangle(vec3f(0.0, -2.0, -5.0), 0.5, vec3f(0.0, 0.0, 10.0), t0, t1, t2).is_none());
+    let toi = sweep_sphere_vs_triangle(vec3f(0.0, -1.25, -5.0), 0.5, vec3f(0.0, 0.0, 10.0), t0, t1, t2).unwrap();
+    assert!(approx(toi.point, vec3f(0.0, -1.0, 0.0), eps));
+    assert!(approx(toi.t, (5.0 - f32::sqrt(0.25 - 0.0625)) / 10.0, eps));
+    assert!(sweep_sphere_vs_triangle(vec3f(3.0, 0.0, -5.0), 0.5, vec3f(0.0, 0.0, 10.0), t0, t1, t2).is_none());
+    let toi = sweep_sphere_vs_triangle(vec3f(0.0, 0.0, 0.05), 0.1, vec3f(0.0, 0.0, 10.0), t0, t1, t2).unwrap();
+    assert_eq!(toi.t, 0.0);
+    assert!(approx(toi.normal, vec3f(0.0, 0.0, 1.0), eps));
+
+    // sphere vs aabb face, edge and corner
+    let (bmin, bmax) = (splat3f(-1.0), splat3f(1.0));
+    let toi = sweep_sphere_vs_aabb(vec3f(-5.0, 0.2, 0.3), 1.0, vec3f(6.0, 0.0, 0.0), bmin, bmax).unwrap();
+    assert!(approx(toi.t, 0.5, eps));
+    assert!(approx(toi.normal, vec3f(-1.0, 0.0, 0.0), eps));
+    assert!(approx(toi.point, vec3f(-1.0, 0.2, 0.3), eps));
+    let toi = sweep_sphere_vs_aabb(vec3f(-5.0, 1.5, 0.0), 1.0, vec3f(10.0, 0.0, 0.0), bmin, bmax).unwrap();
+    assert!(approx(toi.point, vec3f(-1.0, 1.0, 0.0), eps));
+    assert!(approx(toi.t, (4.0 - f32::sqrt(0.75)) / 10.0, eps));
+    let toi = sweep_sphere_vs_aabb(splat3f(5.0), 1.0, splat3f(-10.0), bmin, bmax).unwrap();
+    assert!(approx(toi.point, splat3f(1.0), eps));
+    assert!(approx(toi.normal, normalize(splat3f(1.0)), eps));
+    assert!(sweep_sphere_vs_aabb(vec3f(-5.0, 2.1, 0.0), 1.0, vec3f(10.0, 0.0, 0.0), bmin, bmax).is_none());
+    let toi = sweep_sphere_vs_aabb(vec3f(0.8, 0.0, 0.0), 0.5, vec3f(10.0, 0.0, 0.0), bmin, bmax).unwrap();
+    assert_eq!(toi.t, 0.0);
+    assert!(approx(toi.normal, Vec3f::unit_x(), eps));
+
+    // sphere vs capsule
+    let toi = sweep_sphere_vs_capsule(vec3f(-5.0, 0.0, 0.0), 0.5, vec3f(10.0, 0.0, 0.0), vec3f(0.0, -2.0, 0.0), vec3f(0.0, 2.0, 0.0), 0.5).unwrap();
+    assert!(approx(toi.t, 0.4, eps));
+    assert!(approx(toi.normal, vec3f(-1.0, 0.0, 0.0), eps));
+    assert!(approx(toi.point, vec3f(-0.5, 0.0, 0.0), eps));
+    assert!(sweep_sphere_vs_capsule(vec3f(-5.0, 3.5, 0.0), 0.5, vec3f(10.0, 0.0, 0.0), vec3f(0.0, -2.0, 0.0), vec3f(0.0, 2.0, 0.0), 0.5).is_none());
+
+    // moving aabb vs moving aabb, 3D and 2D
+    let toi = sweep_aabb_vs_aabb(splat3f(-1.0), splat3f(1.0), vec3f(10.0, 0.0, 0.0), vec3f(5.0, -0.5, -0.5), vec3f(6.0, 0.5, 0.5), vec3f(-10.0, 0.0, 0.0)).unwrap();
+    assert!(approx(toi.t, 0.2, eps));
+    assert!(approx(toi.normal, vec3f(-1.0, 0.0, 0.0), eps));
+    assert!(approx(toi.point, vec3f(3.0, 0.0, 0.0), eps));
+    let toi = sweep_aabb_vs_aabb(splat2f(-1.0), splat2f(1.0), vec2f(4.0, 4.0), vec2f(2.0, 3.0), vec2f(4.0, 5.0), Vec2f::zero()).unwrap();
+    assert!(approx(toi.t, 0.5, eps));
+    assert!(approx(toi.normal, vec2f(0.0, -1.0), eps));
+    assert!(sweep_aabb_vs_aabb(splat2f(-1.0), splat2f(1.0), vec2f(4.0, 0.0), vec2f(2.0, 3.0), vec2f(4.0, 5.0), Vec2f::zero()).is_none());
+    let toi = sweep_aabb_vs_aabb(splat2f(-1.0), splat2f(1.0), vec2f(4.0, 0.0), vec2f(0.8, -3.0), vec2f(4.0, 3.0), Vec2f::zero()).unwrap();
+    assert_eq!(toi.t, 0.0);
+    assert!(approx(toi.normal, vec2f(-1.0, 0.0), eps));
+
+    // conservative advancement with vertex lists, implicit shapes and both moving
+    let cube = Aabb::new(splat3f(-1.0), splat3f(1.0)).corners().to_vec();
+    let toi = conservative_advancement(&Sphere::new(vec3f(-5.0, 0.3, 0.0), 1.0), vec3f(8.0, 0.0, 0.0), &cube, Vec3f::zero()).unwrap();
+    assert!(approx(toi.t, 3.0 / 8.0, eps));
+    assert!(approx(toi.normal, vec3f(-1.0, 0.0, 0.0), eps));
+    assert!(approx(toi.point.x, -1.0, eps));
+    let rot = Obb::from_matrix(Mat4f::from_translation(vec3f(6.0, 0.0, 0.0)) * Mat4f::from_z_rotation(f32::pi() * 0.25));
+    let toi = conservative_advancement(&cube, vec3f(2.0, 0.0, 0.0), &rot, vec3f(-2.0, 0.0, 0.0)).unwrap();
+    assert!(approx(toi.t, (5.0 - f32::sqrt(2.0)) / 4.0, eps));
+    assert!(conservative_advancement(&cube, vec3f(2.0, 5.0, 0.0), &rot, vec3f(-2.0, 0.0, 0.0)).is_none());
+    let toi = conservative_advancement(&cube, Vec3f::unit_x(), &Sphere::new(vec3f(1.5, 0.0, 0.0), 1.0), Vec3f::zero()).unwrap();
+    assert_eq!(toi.t, 0.0);
+    let circle: Sphere<f32, Vec2f> = Sphere::new(vec2f(0.0, 10.0), 0.5);
+    let ground = vec![vec2f(-10.0, -1.0), vec2f(10.0, -1.0), vec2f(10.0, 0.0), vec2f(-10.0, 0.0)];
+    let toi = conservative_advancement(&circle, vec2f(0.0, -100.0), &ground, Vec2f::zero()).unwrap();
+    assert!(approx(toi.t, 9.5 / 100.0, eps));
+    assert!(approx(toi.normal, vec2f(0.0, 1.0), eps));
+}
+
+#[test]
+fn bvh_queries() {
+    // bumpy grid mesh of triangles
+    let height = |x: f32, z: f32| f32::sin(x * 0.7) * f32::cos(z * 0.3);
+    let mut tris = Vec::new();
+    for z in 0..16 {
+        for x in 0..16 {
+            let (x0, z0, x1, z1) = (x as f32, z as f32, x as f32 + 1.0, z as f32 + 1.0);
+            let p = |x: f32, z: f32| vec3f(x, height(x, z), z);
+            tris.push(Triangle::new(p(x0, z0), p(x1, z0), p(x1, z1)));
+            tris.push(Triangle::new(p(x0, z0), p(x1, z1), p(x0, z1)));
+        }
+    }
+    let bvh = Bvh::from_items(&tris);
+    assert!(bvh.nodes.len() > 1);
+    let mut sorted = bvh.indices.clone();
+    sorted.sort();
+    assert_eq!(sorted, (0..tris.len()).collect::<Vec<usize>>());
+    for node in &bvh.nodes {
+        if !node.is_leaf() {
+            assert!(node.aabb.contains_aabb(&bvh.nodes[node.first].aabb));
+            assert!(node.aabb.contains_aabb(&bvh.nodes[node.first + 1].aabb));
+        }
+    }
+
+    // closest and any hits match a brute force loop over every triangle
+    let brute = |tris: &[Triangle<f32>], r0: Vec3f, rv: Vec3f| {
+        tris.iter().enumerate().filter_map(|(i, t)| ray_vs_triangle_hit(r0, rv, t.v0, t.v1, t.v2, f32::max_value()).map(|h| (i, h.t)))
+            .fold(None, |best: Option<(usize, f32)>, h| if best.is_none_or(|b| h.1 < b.1) { Some(h) } else { best })
+    };
+    for i in 0..32 {
+        let r0 = vec3f(i as f32 * 0.37, 5.0, 16.0 - i as f32 * 0.41);
+        let rv = normalize(vec3f(0.3, -1.0, 0.1 * i as f32 - 1.5));
+        let expected = brute(&tris, r0, rv);
+        let hit = bvh.ray_closest_triangle(&tris, r0, rv, f32::max_value());
+        assert_eq!(hit.map(|(i, _)| i), expected.map(|(i, _)| i));
+        if let (Some((_, hit)), Some((_, t))) = (hit, expected) {
+            assert!(approx(hit.t, t, 0.0001));
+        }
+        let any = bvh.ray_any(r0, rv, f32::max_value(), |i| ray_vs_triangle(r0, rv, tris[i].v0, tris[i].v1, tris[i].v2).is_some());
+        assert_eq!(any.is_some(), expected.is_some());
+    }
+    assert!(bvh.ray_closest_triangle(&tris, vec3f(8.0, 5.0, 8.0), vec3f(0.0, 1.0, 0.0), f32::max_value()).is_none());
+    assert!(bvh.ray_closest_triangle(&tris, vec3f(8.0, 5.0, 8.0), vec3f(0.0, -1.0, 0.0), 1.0).is_none());
+
+    // overlap queries return every item whose bounds overlap
+    let bounds: Vec<Aabb<f32>> = tris.iter().map(|t| t.aabb()).collect();
+    let query = Aabb::new(vec3f(3.2, -2.0, 4.5), vec3f(5.7, 2.0, 6.1));
+    let mut found = bvh.query_aabb(&query);
+    found.sort();
+    let expected: Vec<usize> = (0..tris.len()).filter(|i| bounds[*i].intersects_aabb(&query)).collect();
+    assert_eq!(found, expected);
+    let sphere = Sphere::new(vec3f(10.0, 0.0, 10.0), 1.5);
+    let mut found = bvh.query_sphere(&sphere);
+    found.sort();
+    let expected: Vec<usize> = (0..tris.len()).filter(|i| bounds[*i].intersects_sphere(&sphere)).collect();
+    assert_eq!(found, expected);
+    assert!(!expected.is_empty());
+
+    // frustum looking down -z from above the grid sees only part of it
+    let proj = Mat4f::create_perspective_projection_lh_yup(f32::pi() * 0.1, 1.0, 0.1, 100.0);
+    let view = Mat4f::from_translation(vec3f(-4.0, -2.0, -30.0));
+    let frustum = Frustum::from_matrix(proj * view);
+    let found = bvh.query_frustum(&frustum);
+    let expected = (0..tris.len()).filter(|i| frustum.intersects_aabb(&bounds[*i])).count();
+    assert_eq!(found.len(), expected);
+    assert!(expected > 0 && expected < tris.len());
+
+    // refit after animating the vertices
+    let mut bvh = bvh;
+    let lifted: Vec<Triangle<f32>> = tris.iter().map(|t| Triangle::new(t.v0 + vec3f(0.0, 10.0, 0.0), t.v1 + vec3f(0.0, 10.0, 0.0), t.v2 + vec3f(0.0, 10.0, 0.0))).collect();
+    bvh.refit_items(&lifted);
+    assert!(approx(bvh.aabb().min.y, lifted.iter().fold(f32::max_value(), |m, t| f32::min(m, t.aabb().min.y)), 0.0001));
+    let r0 = vec3f(7.5, 20.0, 7.5);
+    let (i, hit) = bvh.ray_closest_triangle(&lifted, r0, vec3f(0.0, -1.0, 0.0), f32::max_value()).unwrap();
+    let (j, rest) = brute(&tris, r0, vec3f(0.0, -1.0, 0.0)).unwrap();
+    assert_eq!(i, j);
+    assert!(approx(hit.point.y, 30.0 - rest, 0.0001));
+    assert!(bvh.query_aabb(&Aabb::new(splat3f(-1.0), vec3f(20.0, 2.0, 20.0))).is_empty());
+
+    // 2D circles, a single item and empty
+    let circles: Vec<Sphere<f32, Vec2f>> = (0..50).map(|i| Sphere::new(vec2f((i % 10) as f32 * 2.0, (i / 10) as f32 * 2.0), 0.5)).collect();
+    let bvh2 = Bvh::from_items(&circles);
+    let mut found = bvh2.query_aabb(&Aabb::new(vec2f(2.2, 2.2), vec2f(4.2, 4.2)));
+    found.sort();
+    assert_eq!(found, vec![11, 12, 21, 22]);
+    let hit = bvh2.ray_closest(vec2f(-5.0, 4.0), vec2f(1.0, 0.0), f32::max_value(), |i, t_max| {
+        ray_vs_sphere_hit(vec2f(-5.0, 4.0), vec2f(1.0, 0.0), circles[i].center, circles[i].radius, t_max).map(|h| h.t)
+    });
+    assert_eq!(hit.map(|(i, _)| i), Some(20));
+    assert!(approx(hit.unwrap().1, 4.5, 0.0001));
+    let single = Bvh::from_items(&[Aabb::new(splat3f(0.0), splat3f(1.0))]);
+    assert_eq!(single.query_aabb(&Aabb::new(splat3f(0.5), splat3f(2.0))), vec![0]);
+    let empty: Bvh<f32> = Bvh::build(&[]);
+    assert!(empty.query_aabb(&query).is_empty());
+    assert!(empty.ray_closest(Vec3f::zero(), Vec3f::unit_x(), 1.0, |_, _| Some(0.0)).is_none());
+    assert!(empty.aabb().is_empty());
+}
+
+#[test]
+fn dynamic_tree_broadphase() {
+    // pseudo random boxes scattered in a 100 unit cube
+    let mut seed = 12345u32;
+    let mut rand = move || {
+        seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+        (seed >> 8) as f32 / (1u32 << 24) as f32
+    };
+    let mut boxes: Vec<Aabb<f32>> = (0..200).map(|_| {
+        let c = vec3f(rand(), rand(), rand()) * 100.0;
+        Aabb::from_center_extents(c, vec3f(rand(), rand(), rand()) * 3.0 + 0.5)
+    }).collect();
+
+    let mut tree = DynamicTree::new(0.1);
+    let proxies: Vec<usize> = boxes.iter().map(|b| tree.insert(b)).collect();
+    assert_eq!(tree.len(), 200);
+    assert!(tree.max_balance() <= 1);
+    assert!(tree.height() <= 16);
+    for (p, b) in proxies.iter().zip(&boxes) {
+        assert!(tree.fat_aabb(*p).contains_aabb(b));
+        assert!(tree.aabb().contains_aabb(b));
+    }
+
+    // pairs match a brute force test of the fat aabbs
+    let brute_pairs = |tree: &DynamicTree<f32>, proxies: &[usize]| {
+        let mut pairs = Vec::new();
+        for (i, a) in proxies.iter().enumerate() {
+            for b in &proxies[i + 1..] {
+                if tree.fat_aabb(*a).intersects_aabb(&tree.fat_aabb(*b)) {
+                    pairs.push((min(*a, *b), max(*a, *b)));
+                }
+            }
+        }
+        pairs.sort();
+        pairs
+    };
+    let expected = brute_pairs(&tree, &proxies);
+    assert!(!expected.is_empty());
+    assert_eq!(tree.all_pairs(), expected);
+    assert_eq!(tree.update_pairs(), expected);
+    assert!(tree.update_pairs().is_empty());
+
+    // small moves stay inside the fat aabb, large moves are reinserted and reported
+    assert!(!tree.move_proxy(proxies[0], &Aabb::new(boxes[0].min + splat3f(0.05), boxes[0].max + splat3f(0.05)), splat3f(0.05)));
+    assert!(tree.update_pairs().is_empty());
+    boxes[1] = Aabb::new(boxes[0].min + splat3f(0.5), boxes[0].max + splat3f(0.5));
+    assert!(tree.move_proxy(proxies[1], &boxes[1], vec3f(1.0, 0.0, 0.0)));
+    assert!(tree.fat_aabb(proxies[1]).max.x > boxes[1].max.x + 3.9);
+    let pairs = tree.update_pairs();
+    assert!(pairs.contains(&(proxies[0], proxies[1])));
+    assert!(pairs.iter().all(|(a, b)| *a == proxies[1] || *b == proxies[1]));
+    assert_eq!(tree.all_pairs(), brute_pairs(&tree, &proxies));
+
+    // remove half and check the ids are reused
+    for p in proxies.iter().step_by(2) {
+        tree.remove(*p);
+    }
+    let remaining: Vec<usize> = proxies.iter().skip(1).step_by(2).copied().collect();
+    assert_eq!(tree.len(), 100);
+    assert!(tree.max_balance() <= 1);
+    assert_eq!(tree.all_pairs(), brute_pairs(&tree, &remaining));
+    let reused = tree.insert(&boxes[0]);
+    assert!(proxies.contains(&reused));
+    tree.remove(reused);
+
+    // overlap queries
+    let query = Aabb::new(splat3f(20.0), splat3f(60.0));
+    let mut found = tree.query_aabb(&query);
+    found.sort();
+    let expected: Vec<usize> = remaining.iter().copied().filter(|p| tree.fat_aabb(*p).intersects_aabb(&query)).collect();
+    assert_eq!(found, expected);
+    let sphere = Sphere::new(splat3f(50.0), 20.0);
+    let mut found = tree.query_sphere(&sphere);
+    found.sort();
+    let expected: Vec<usize> = remaining.iter().copied().filter(|p| tree.fat_aabb(*p).intersects_sphere(&sphere)).collect();
+    assert_eq!(found, expected);
+    let proj = Mat4f::create_perspective_projection_lh_yup(f32::pi() * 0.25, 1.0, 0.1, 100.0);
+    let view = Mat4f::from_translation(vec3f(-50.0, -50.0, -150.0));
+    let frustum = Frustum::from_matrix(proj * view);
+    let found = tree.query_frustum(&frustum);
+    let expected = remaining.iter().filter(|p| frustum.intersects_aabb(&tree.fat_aabb(**p))).count();
+    assert_eq!(found.len(), expected);
+    assert!(expected > 0 && expected < remaining.len());
+
+    // ray casts against the actual boxes match brute force, proxy ids map back to the box they were inserted with
+    let box_of = |p: usize| boxes[proxies.iter().position(|q| *q == p).unwrap()];
+    for i in 0..16 {
+        let r0 = vec3f(-10.0, i as f32 * 6.0 + 2.0, 50.0);
+        let rv = normalize(vec3f(1.0, 0.05, (i as f32 - 8.0) * 0.05));
+        let hit = tree.ray_closest(r0, rv, f32::max_value(), |p, t_max| ray_vs_aabb_hit(r0, rv, box_of(p).min, box_of(p).max, t_max).map(|h| h.t));
+        let expected = remaining.iter().filter_map(|p| ray_vs_aabb_hit(r0, rv, box_of(*p).min, box_of(*p).max, f32::max_value()).map(|h| (*p, h.t)))
+            .fold(None, |best: Option<(usize, f32)>, h| if best.is_none_or(|b| h.1 < b.1) { Some(h) } else { best });
+        assert_eq!(hit.map(|h| h.0), expected.map(|h| h.0));
+        let any = tree.ray_any(r0, rv, f32::max_value(), |p| ray_vs_aabb(r0, rv, box_of(p).min, box_of(p).max).is_some());
+        assert_eq!(any.is_some(), expected.is_some());
+    }
+
+    // sorted insertion along a line stays balanced thanks to rotations
+    let mut line: DynamicTree<f32, Vec2f> = DynamicTree::new(0.0);
+    for i in 0..1024 {
+        line.insert(&Aabb::new(vec2f(i as f32, 0.0), vec2f(i as f32 + 0.5, 1.0)));
+    }
+    assert!(line.max_balance() <= 1);
+    assert!(line.height() <= 20);
+    assert_eq!(line.query_aabb(&Aabb::new(vec2f(10.2, 0.0), vec2f(11.2, 1.0))).len(), 2);
+    assert_eq!(line.all_pairs().len(), 0);
+    let mut empty: DynamicTree<f32> = DynamicTree::new(0.1);
+    assert!(empty.is_empty() && empty.update_pairs().is_empty() && empty.aabb().is_empty());
+    assert!(empty.ray_closest(Vec3f::zero(), Vec3f::unit_x(), 1.0, |_, _| Some(0.0)).is_none());
+}